@@ -0,0 +1,166 @@
+#!/usr/bin/env python3
+"""Regenerates the TA-Lib conformance fixtures in src/indicators/fixtures/talib.
+
+Every fixture is the output of the native library through the `talib` Python package
+(`pip install TA-Lib`, which needs the TA-Lib C library). The script refuses to run
+without it and records the package version in every fixture as `talib_version`.
+
+Usage: python3 scripts/talib_fixtures.py
+"""
+
+import json
+import math
+import os
+import sys
+
+try:
+    import numpy as np
+    import talib
+    from talib import abstract
+except ImportError:
+    sys.exit("The talib package is required to generate TA-Lib fixtures: pip install TA-Lib")
+
+OUT_DIR = os.path.join(os.path.dirname(__file__), "..", "src", "indicators", "fixtures", "talib")
+BARS = 400
+SEED = 20240601
+NAN = float("nan")
+
+
+def generate_bars():
+    state = SEED
+
+    def rnd():
+        nonlocal state
+        state = (6364136223846793005 * state + 1442695040888963407) % 2**64
+        return (state >> 11) / 2**53
+
+    bars = {"open": [], "high": [], "low": [], "close": [], "volume": []}
+    prev_close = 100.0
+    for i in range(BARS):
+        open_ = round(prev_close + (rnd() - 0.5) * 0.8, 4)
+        close = round(open_ * (1.0 + (rnd() - 0.5) * 0.04), 4)
+        high = round(max(open_, close) + rnd() * 1.2, 4)
+        low = round(min(open_, close) - rnd() * 1.2, 4)
+        volume = float(round(1000 + rnd() * 9000))
+        if i % 97 == 50:
+            # Flat bar: exercises the high == low guards.
+            high = low = open_ = close
+        for key, value in zip(("open", "high", "low", "close", "volume"), (open_, high, low, close, volume)):
+            bars[key].append(value)
+        prev_close = close
+    return bars
+
+
+# Our names for TA-Lib's outputs, in TA-Lib's order, where they are not just `real`.
+OUTPUT_NAMES = {
+    "AROON": ["aroon_down", "aroon_up"],
+    "BBANDS": ["upper_band", "middle_band", "lower_band"],
+    "MINMAX": ["min", "max"],
+    "MINMAXINDEX": ["min_index", "max_index"],
+}
+
+MATH_FUNCTIONS = [
+    "ADD", "SUB", "MULT", "DIV",
+    # ACOS and ASIN are NaN on every price of the dataset.
+    "ATAN", "CEIL", "COS", "COSH", "EXP", "FLOOR", "LN", "LOG10", "SIN", "SINH", "SQRT", "TAN", "TANH",
+]
+
+
+def periods(name, values, talib_name="timeperiod"):
+    return [({name: p}, {talib_name: p}) for p in values]
+
+
+def cases(bars):
+    """Fixture name, TA-Lib function and (our params, TA-Lib params) of every case."""
+    first_high = bars["high"][0]
+    fixtures = {
+        "ad": ("AD", [({}, {})]),
+        "adosc": ("ADOSC", [({"short_period": 3, "long_period": 10}, {"fastperiod": 3, "slowperiod": 10})]),
+        "adx": ("ADX", periods("period", (14, 5))),
+        "apo": ("APO", [({"fast_period": 12, "slow_period": 26}, {"fastperiod": 12, "slowperiod": 26, "matype": 1})]),
+        "aroon": ("AROON", periods("period", (14, 25))),
+        "atr": ("ATR", periods("period", (14, 5))),
+        "avgprice": ("AVGPRICE", [({}, {})]),
+        "bbands": ("BBANDS", [
+            ({"period": p, "std_dev_multiplier": k}, {"timeperiod": p, "nbdevup": k, "nbdevdn": k, "matype": 0})
+            for p, k in ((20, 2.0), (10, 1.5))
+        ]),
+        "bop": ("BOP", [({}, {})]),
+        "cci": ("CCI", periods("period", (14, 5))),
+        "cmo": ("CMO", periods("period", (14, 5))),
+        "medprice": ("MEDPRICE", [({}, {})]),
+        "mfi": ("MFI", periods("period", (14, 5))),
+        "midpoint": ("MIDPOINT", periods("period", (14, 5))),
+        "midprice": ("MIDPRICE", periods("period", (14, 5))),
+        "mom": ("MOM", periods("period", (10, 3))),
+        "natr": ("NATR", periods("period", (14, 5))),
+        "obv": ("OBV", [({}, {})]),
+        "sar": ("SAR", [
+            ({"acceleration": a, "maximum": m}, {"acceleration": a, "maximum": m}) for a, m in ((0.02, 0.2), (0.05, 0.3))
+        ]),
+        "sarext": ("SAREXT", [
+            ({}, {}),
+            ({"start_value": -(first_high + 1.0), "offset_on_reverse": 0.01, "acceleration_init_long": 0.01,
+              "acceleration_long": 0.03, "acceleration_max_long": 0.25, "acceleration_init_short": 0.04,
+              "acceleration_short": 0.01, "acceleration_max_short": 0.1},
+             {"startvalue": -(first_high + 1.0), "offsetonreverse": 0.01, "accelerationinitlong": 0.01,
+              "accelerationlong": 0.03, "accelerationmaxlong": 0.25, "accelerationinitshort": 0.04,
+              "accelerationshort": 0.01, "accelerationmaxshort": 0.1}),
+        ]),
+        "trix": ("TRIX", periods("period", (30, 5))),
+        "typprice": ("TYPPRICE", [({}, {})]),
+        "ultosc": ("ULTOSC", [
+            ({"period1": a, "period2": b, "period3": c}, {"timeperiod1": a, "timeperiod2": b, "timeperiod3": c})
+            for a, b, c in ((7, 14, 28), (10, 3, 5))
+        ]),
+        "wclprice": ("WCLPRICE", [({}, {})]),
+    }
+    for function in ("MAX", "MAXINDEX", "MIN", "MININDEX", "MINMAX", "MINMAXINDEX", "SUM"):
+        fixtures[function.lower()] = (function, periods("period", (30, 5)))
+    for function in ("ROC", "ROCP", "ROCR", "ROCR100"):
+        fixtures[function.lower()] = (function, periods("period", (10, 3)))
+    for function in MATH_FUNCTIONS:
+        # TA-Lib's default inputs: the high and the low for operators, the close for transforms.
+        fixtures[function.lower()] = (function, [({}, {})])
+    return fixtures
+
+
+def run(function, arrays, talib_params):
+    result = abstract.Function(function)(arrays, **talib_params)
+    result = result if isinstance(result, list) else [result]
+    names = OUTPUT_NAMES.get(function, ["real"])
+    outputs = {name: [float(x) for x in series] for name, series in zip(names, result)}
+    if function == "AROON":
+        outputs["aroon_osc"] = [float(x) for x in abstract.Function("AROONOSC")(arrays, **talib_params)]
+    return outputs
+
+
+def to_json(series):
+    return [None if math.isnan(value) else value for value in series]
+
+
+def main():
+    bars = generate_bars()
+    arrays = {k: np.asarray(v, dtype=float) for k, v in bars.items()}
+    os.makedirs(OUT_DIR, exist_ok=True)
+    with open(os.path.join(OUT_DIR, "ohlcv.json"), "w") as f:
+        json.dump(bars, f)
+        f.write("\n")
+    for name, (function, entries) in cases(bars).items():
+        document = {
+            "function": function,
+            "talib_version": talib.__version__,
+            "cases": [
+                {"params": params, "talib_params": talib_params,
+                 "outputs": {key: to_json(series) for key, series in run(function, arrays, talib_params).items()}}
+                for params, talib_params in entries
+            ],
+        }
+        with open(os.path.join(OUT_DIR, name + ".json"), "w") as f:
+            json.dump(document, f)
+            f.write("\n")
+    print("fixtures written with talib", talib.__version__)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,80 @@
+use ndarray::ArrayView1;
+use crate::backtest::Trade;
+
+/// Summary statistics of a backtest. Ratios are not annualized for a risk-free rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Metrics {
+    pub total_return: f64,
+    /// Compound annual growth rate.
+    pub cagr: f64,
+    /// Annualized mean over standard deviation of bar returns.
+    pub sharpe: f64,
+    /// Annualized mean over downside deviation of bar returns.
+    pub sortino: f64,
+    /// Largest peak-to-trough equity decline, as a positive fraction of the peak.
+    pub max_drawdown: f64,
+    /// Fraction of trades with a positive PnL, NaN without trades.
+    pub win_rate: f64,
+    /// Fraction of bars with an open position.
+    pub exposure: f64,
+}
+
+impl Metrics {
+    pub fn calculate(equity: ArrayView1<f64>, trades: &[Trade], exposure: f64, periods_per_year: f64) -> Self {
+        let length = equity.len();
+        let returns: Vec<f64> = (1..length).map(|i| equity[i] / equity[i - 1] - 1.0).collect();
+        let periods = returns.len() as f64;
+
+        let total_return = if length > 0 { equity[length - 1] / equity[0] - 1.0 } else { 0.0 };
+        let cagr = (1.0 + total_return).powf(periods_per_year / periods) - 1.0;
+
+        let mean = returns.iter().sum::<f64>() / periods;
+        let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (periods - 1.0);
+        let downside = (returns.iter().map(|r| r.min(0.0).powi(2)).sum::<f64>() / periods).sqrt();
+        let annualization = periods_per_year.sqrt();
+
+        let mut peak = f64::MIN;
+        let mut max_drawdown: f64 = 0.0;
+        for &value in equity.iter() {
+            peak = peak.max(value);
+            max_drawdown = max_drawdown.max((peak - value) / peak);
+        }
+
+        let wins = trades.iter().filter(|trade| trade.pnl > 0.0).count();
+
+        Metrics {
+            total_return,
+            cagr,
+            sharpe: mean / variance.sqrt() * annualization,
+            sortino: mean / downside * annualization,
+            max_drawdown,
+            win_rate: wins as f64 / trades.len() as f64,
+            exposure,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+    use super::*;
+
+    #[test]
+    fn test_metrics() {
+        let equity = array![100.0, 110.0, 99.0, 108.9, 121.0];
+
+        let metrics = Metrics::calculate(equity.view(), &[], 1.0, 4.0);
+
+        assert!((metrics.total_return - 0.21).abs() < 1e-12);
+        // Four bar returns make up exactly one year.
+        assert!((metrics.cagr - 0.21).abs() < 1e-12);
+        assert!((metrics.max_drawdown - 0.1).abs() < 1e-12);
+        assert!(metrics.win_rate.is_nan());
+
+        let returns = [0.1, -0.1, 0.1, 121.0 / 108.9 - 1.0];
+        let mean = returns.iter().sum::<f64>() / 4.0;
+        let std = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / 3.0).sqrt();
+        assert!((metrics.sharpe - mean / std * 2.0).abs() < 1e-12);
+        assert!((metrics.sortino - mean / (0.01_f64 / 4.0).sqrt() * 2.0).abs() < 1e-12);
+    }
+}
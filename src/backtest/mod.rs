@@ -0,0 +1,304 @@
+//! Single-asset backtests driven by a target position series.
+//!
+//! `positions[i]` is the target exposure decided at the close of bar `i` (1 = fully long,
+//! -1 = fully short, 0 = flat, NaN = flat). It is filled on bar `i + 1`, at its open or close
+//! depending on [`Fill`], so a position series computed from indicators has no look-ahead.
+
+use ndarray::{Array1, ArrayView1};
+use crate::models::data::{BarField, InputDataView};
+use crate::models::indicator::IndicatorError;
+use crate::signals::{Direction, Signal};
+use crate::validation::validator::Validator;
+
+pub mod metrics;
+
+use metrics::Metrics;
+
+/// Bar price a target position is filled at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fill {
+    NextOpen,
+    NextClose,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PositionSizing {
+    /// Trades `|position| * fraction` of the current equity.
+    FractionOfEquity(f64),
+    /// Trades `|position| * units` units regardless of equity.
+    FixedUnits(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestConfig {
+    pub initial_capital: f64,
+    /// Fraction of the traded notional paid on every fill.
+    pub commission: f64,
+    /// Fraction of the price lost on every fill, against the direction of the trade.
+    pub slippage: f64,
+    pub fill: Fill,
+    pub sizing: PositionSizing,
+    /// Bars per year, used to annualize CAGR, Sharpe and Sortino.
+    pub periods_per_year: f64,
+}
+
+impl Default for BacktestConfig {
+    fn default() -> Self {
+        BacktestConfig {
+            initial_capital: 10_000.0,
+            commission: 0.0,
+            slippage: 0.0,
+            fill: Fill::NextOpen,
+            sizing: PositionSizing::FractionOfEquity(1.0),
+            periods_per_year: 252.0,
+        }
+    }
+}
+
+/// A round trip from opening a position to closing or reversing it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trade {
+    pub direction: Direction,
+    pub entry_index: usize,
+    /// Bar of the closing fill, or the last bar for a position still open at the end.
+    pub exit_index: usize,
+    pub entry_price: f64,
+    pub exit_price: f64,
+    /// Equity change over the trade, net of commission and slippage.
+    pub pnl: f64,
+    /// `pnl` relative to the equity at entry.
+    pub return_pct: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct BacktestResult {
+    /// Equity at the close of every bar.
+    pub equity: Array1<f64>,
+    pub trades: Vec<Trade>,
+    pub metrics: Metrics,
+}
+
+/// Holds the latest signal's direction: long on bullish signals, short (or flat if
+/// `allow_short` is false) on bearish ones, flat before the first signal.
+pub fn positions_from_signals(signals: &[Signal], length: usize, allow_short: bool) -> Array1<f64> {
+    let mut positions = Array1::zeros(length);
+    let mut sorted = signals.to_vec();
+    sorted.sort_by_key(|signal| signal.index);
+
+    for (i, signal) in sorted.iter().enumerate() {
+        let end = sorted.get(i + 1).map_or(length, |next| next.index).min(length);
+        let position = match signal.direction {
+            Direction::Bullish => 1.0,
+            Direction::Bearish if allow_short => -1.0,
+            Direction::Bearish => 0.0,
+        };
+        for j in signal.index.min(end)..end {
+            positions[j] = position;
+        }
+    }
+    positions
+}
+
+struct Account {
+    cash: f64,
+    units: f64,
+    open_trade: Option<Trade>,
+    /// Equity just before the open trade's first fill.
+    entry_equity: f64,
+    trades: Vec<Trade>,
+}
+
+impl Account {
+    fn equity(&self, price: f64) -> f64 {
+        self.cash + self.units * price
+    }
+
+    fn trade_to(&mut self, index: usize, units: f64, price: f64, config: &BacktestConfig) {
+        let traded = units - self.units;
+        if traded == 0.0 {
+            return;
+        }
+        let fill_price = price * (1.0 + config.slippage * traded.signum());
+        self.cash -= traded * fill_price + traded.abs() * fill_price * config.commission;
+        self.units = units;
+        if units == 0.0 {
+            if let Some(trade) = self.open_trade.as_mut() {
+                trade.exit_index = index;
+                trade.exit_price = fill_price;
+            }
+            self.close_trade(price);
+        } else if self.open_trade.is_none() {
+            self.open_trade = Some(Trade {
+                direction: if units > 0.0 { Direction::Bullish } else { Direction::Bearish },
+                entry_index: index,
+                exit_index: index,
+                entry_price: fill_price,
+                exit_price: fill_price,
+                pnl: 0.0,
+                return_pct: 0.0,
+            });
+        }
+    }
+
+    fn close_trade(&mut self, price: f64) {
+        if let Some(mut trade) = self.open_trade.take() {
+            trade.pnl = self.equity(price) - self.entry_equity;
+            trade.return_pct = trade.pnl / self.entry_equity;
+            self.trades.push(trade);
+        }
+        self.entry_equity = self.equity(price);
+    }
+}
+
+/// Simulates trading `positions` over `data`, which needs `CLOSE` and, for [`Fill::NextOpen`], `OPEN`.
+pub fn run(data: &InputDataView, positions: ArrayView1<f64>, config: &BacktestConfig) -> Result<BacktestResult, IndicatorError> {
+    let mut required_fields = vec![BarField::CLOSE];
+    if config.fill == Fill::NextOpen {
+        required_fields.push(BarField::OPEN);
+    }
+    Validator::new(required_fields, vec![]).validate_data(data)?;
+
+    let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+    let fill_prices = match config.fill {
+        Fill::NextOpen => data.get_by_bar_field(&BarField::OPEN).unwrap(),
+        Fill::NextClose => close,
+    };
+    let length = close.len();
+    if positions.len() != length {
+        return Err(IndicatorError::InvalidInput("Positions must have the same length as the bars.".to_string()));
+    }
+
+    let mut account = Account {
+        cash: config.initial_capital,
+        units: 0.0,
+        open_trade: None,
+        entry_equity: config.initial_capital,
+        trades: Vec::new(),
+    };
+    let mut equity = Array1::zeros(length);
+    let mut target = 0.0;
+    let mut exposed_bars = 0;
+
+    for i in 0..length {
+        let next_target = if i == 0 || positions[i - 1].is_nan() { 0.0 } else { positions[i - 1] };
+        if next_target != target {
+            let price = fill_prices[i];
+            // Reversals close the current trade before opening the opposite one.
+            if account.units != 0.0 && next_target * target <= 0.0 {
+                account.trade_to(i, 0.0, price, config);
+            }
+            let units = match config.sizing {
+                PositionSizing::FractionOfEquity(fraction) => next_target * fraction * account.equity(price) / price,
+                PositionSizing::FixedUnits(units) => next_target * units,
+            };
+            account.trade_to(i, units, price, config);
+            target = next_target;
+        }
+
+        equity[i] = account.equity(close[i]);
+        if account.units != 0.0 {
+            exposed_bars += 1;
+        }
+    }
+
+    if let Some(trade) = account.open_trade.as_mut() {
+        trade.exit_index = length - 1;
+        trade.exit_price = close[length - 1];
+        account.close_trade(close[length - 1]);
+    }
+
+    let exposure = exposed_bars as f64 / length as f64;
+    let metrics = Metrics::calculate(equity.view(), &account.trades, exposure, config.periods_per_year);
+    Ok(BacktestResult { equity, trades: account.trades, metrics })
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+    use crate::models::data::InputData;
+    use super::*;
+
+    fn input_data() -> InputData {
+        InputData {
+            open: Some(array![10.0, 10.0, 11.0, 12.0, 13.0, 12.0, 11.0]),
+            high: None,
+            low: None,
+            close: Some(array![10.0, 11.0, 12.0, 13.0, 12.0, 11.0, 10.0]),
+            volume: None,
+        }
+    }
+
+    #[test]
+    fn test_long_trade_fills_on_next_open() {
+        let data = input_data();
+        let positions = array![1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+        let config = BacktestConfig { initial_capital: 1000.0, ..BacktestConfig::default() };
+
+        let result = run(&data.view(), positions.view(), &config).unwrap();
+
+        // Buys 100 units at 10 on bar 1, sells them at 13 on bar 4.
+        assert_eq!(result.trades.len(), 1);
+        let trade = &result.trades[0];
+        assert_eq!((trade.entry_index, trade.exit_index), (1, 4));
+        assert_eq!((trade.entry_price, trade.exit_price), (10.0, 13.0));
+        assert!((trade.pnl - 300.0).abs() < 1e-9);
+        assert!((result.equity[6] - 1300.0).abs() < 1e-9);
+        assert_eq!(result.equity[0], 1000.0);
+        assert!((result.metrics.win_rate - 1.0).abs() < 1e-12);
+        assert!((result.metrics.exposure - 3.0 / 7.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_costs_reduce_pnl() {
+        let data = input_data();
+        let positions = array![1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+        let config = BacktestConfig {
+            initial_capital: 1000.0,
+            commission: 0.001,
+            slippage: 0.01,
+            fill: Fill::NextClose,
+            sizing: PositionSizing::FixedUnits(10.0),
+            ..BacktestConfig::default()
+        };
+
+        let result = run(&data.view(), positions.view(), &config).unwrap();
+
+        // Buys 10 units at 11 * 1.01 on bar 1, sells them at 12 * 0.99 on bar 4.
+        let (entry, exit) = (11.0 * 1.01, 12.0 * 0.99);
+        let expected_pnl = 10.0 * (exit - entry) - 10.0 * (entry + exit) * 0.001;
+        assert!((result.trades[0].pnl - expected_pnl).abs() < 1e-9);
+        assert!((result.equity[6] - (1000.0 + expected_pnl)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_reversal_splits_trades() {
+        let data = input_data();
+        let positions = array![1.0, 1.0, -1.0, -1.0, -1.0, -1.0, -1.0];
+
+        let result = run(&data.view(), positions.view(), &BacktestConfig::default()).unwrap();
+
+        assert_eq!(result.trades.len(), 2);
+        assert_eq!(result.trades[0].direction, Direction::Bullish);
+        assert_eq!(result.trades[1].direction, Direction::Bearish);
+        assert_eq!(result.trades[1].entry_index, 3);
+        assert_eq!(result.trades[1].exit_index, 6);
+        // The short from 12 is still open at the last close of 10.
+        assert!(result.trades[1].pnl > 0.0);
+    }
+
+    #[test]
+    fn test_positions_from_signals() {
+        let signals = [Signal::new(4, Direction::Bearish), Signal::new(1, Direction::Bullish)];
+
+        assert_eq!(positions_from_signals(&signals, 6, true), array![0.0, 1.0, 1.0, 1.0, -1.0, -1.0]);
+        assert_eq!(positions_from_signals(&signals, 6, false), array![0.0, 1.0, 1.0, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_rejects_mismatched_positions() {
+        let data = input_data();
+        let positions = array![1.0, 0.0];
+
+        assert!(run(&data.view(), positions.view(), &BacktestConfig::default()).is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//! Information-driven bars (López de Prado, *Advances in Financial Machine Learning*, ch. 2).
+//!
+//! Every tick is signed with the tick rule: +1 on an uptick, -1 on a downtick and the previous
+//! sign when the price is unchanged. A bar closes once the observed imbalance (or run) exceeds
+//! what is expected for a bar, `E[T] * |E[b·v]|`, where `E[T]` is the expected number of ticks
+//! per bar and `E[b·v]` the expected signed value per tick. Both expectations are exponentially
+//! weighted averages over the previous bars.
+//!
+//! When buys and sells balance, `E[b·v]` approaches zero and so would the threshold, closing a
+//! bar on every tick. Imbalance bars therefore expect an imbalance of at least `imbalance_floor`
+//! times the value of `initial_expected_ticks` ticks. The floor is tied to the configured bar
+//! size rather than `E[T]`: scaled by `E[T]` it would shrink along with the bars.
+
+use crate::bars::Tick;
+
+/// Value of a tick that imbalances and runs are accumulated in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Measure {
+    Tick,
+    Volume,
+    Dollar,
+}
+
+impl Measure {
+    fn value(&self, tick: &Tick) -> f64 {
+        match self {
+            Measure::Tick => 1.0,
+            Measure::Volume => tick.size,
+            Measure::Dollar => tick.price * tick.size,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InformationBars {
+    pub measure: Measure,
+    /// `E[T]` before the first bar has closed.
+    pub initial_expected_ticks: f64,
+    /// Weight of the latest bar in the exponentially weighted expectations, in (0, 1].
+    pub alpha: f64,
+    /// Smallest expected imbalance of an imbalance bar, as a fraction of the expected buy plus
+    /// sell value of `initial_expected_ticks` ticks, in [0, 1].
+    pub imbalance_floor: f64,
+}
+
+impl InformationBars {
+    pub fn new(measure: Measure, initial_expected_ticks: f64) -> Self {
+        InformationBars { measure, initial_expected_ticks, alpha: 0.1, imbalance_floor: 0.5 }
+    }
+
+    pub(crate) fn is_valid(&self) -> bool {
+        self.initial_expected_ticks > 0.0
+            && self.alpha > 0.0 && self.alpha <= 1.0
+            && (0.0..=1.0).contains(&self.imbalance_floor)
+    }
+}
+
+/// Running state of imbalance or run bars.
+#[derive(Debug, Clone)]
+pub(crate) struct InformationState {
+    config: InformationBars,
+    runs: bool,
+    previous_price: Option<f64>,
+    sign: f64,
+    ticks: f64,
+    buy: f64,
+    sell: f64,
+    expected_ticks: f64,
+    /// Expected value per tick of buys and sells, `None` until the first bar closes.
+    expected: Option<(f64, f64)>,
+}
+
+impl InformationState {
+    pub(crate) fn new(config: InformationBars, runs: bool) -> Self {
+        InformationState {
+            config,
+            runs,
+            previous_price: None,
+            sign: 1.0,
+            ticks: 0.0,
+            buy: 0.0,
+            sell: 0.0,
+            expected_ticks: config.initial_expected_ticks,
+            expected: None,
+        }
+    }
+
+    /// Adds `tick` to the current bar and returns whether the bar is complete.
+    pub(crate) fn push(&mut self, tick: &Tick) -> bool {
+        if let Some(previous) = self.previous_price {
+            if tick.price > previous {
+                self.sign = 1.0;
+            } else if tick.price < previous {
+                self.sign = -1.0;
+            }
+        }
+        self.previous_price = Some(tick.price);
+
+        let value = self.config.measure.value(tick);
+        if self.sign > 0.0 {
+            self.buy += value;
+        } else {
+            self.sell += value;
+        }
+        self.ticks += 1.0;
+
+        // Until a bar has closed, the current bar's ticks are the only estimate available.
+        let (expected_buy, expected_sell) = self.expected.unwrap_or((self.buy / self.ticks, self.sell / self.ticks));
+        let (observed, threshold) = if self.runs {
+            (self.buy.max(self.sell), self.expected_ticks * expected_buy.max(expected_sell))
+        } else {
+            let floor = self.config.imbalance_floor * self.config.initial_expected_ticks * (expected_buy + expected_sell);
+            ((self.buy - self.sell).abs(), (self.expected_ticks * (expected_buy - expected_sell).abs()).max(floor))
+        };
+
+        let complete = observed >= threshold;
+        if complete {
+            self.close_bar();
+        }
+        complete
+    }
+
+    fn close_bar(&mut self) {
+        let alpha = self.config.alpha;
+        let (buy, sell) = (self.buy / self.ticks, self.sell / self.ticks);
+        self.expected = Some(match self.expected {
+            Some((expected_buy, expected_sell)) => (
+                alpha * buy + (1.0 - alpha) * expected_buy,
+                alpha * sell + (1.0 - alpha) * expected_sell,
+            ),
+            None => (buy, sell),
+        });
+        self.expected_ticks = alpha * self.ticks + (1.0 - alpha) * self.expected_ticks;
+        self.ticks = 0.0;
+        self.buy = 0.0;
+        self.sell = 0.0;
+    }
+}
@@ -0,0 +1,305 @@
+//! OHLCV bars built from a stream of trades.
+//!
+//! [`BarBuilder`] consumes trades one at a time and emits each bar as it completes, so the same
+//! code serves live feeds and, through [`build_bars`], historical arrays.
+
+use ndarray::{Array1, ArrayView1};
+use crate::models::data::{InputData, TimestampedData};
+use crate::models::indicator::IndicatorError;
+use crate::timeframe::Timeframe;
+
+pub mod information;
+
+use information::{InformationBars, InformationState};
+
+/// A single trade.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tick {
+    /// Unix epoch milliseconds.
+    pub timestamp: i64,
+    pub price: f64,
+    pub size: f64,
+}
+
+/// A completed bar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bar {
+    /// Bucket start for time bars, otherwise the timestamp of the first trade.
+    pub timestamp: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+}
+
+/// When a bar is complete. Trades are never split across bars, so volume and dollar bars
+/// close on the trade that reaches the threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BarRule {
+    /// One bar per timeframe bucket that contains trades.
+    Time(Timeframe),
+    /// A fixed number of trades.
+    Tick(usize),
+    /// A fixed traded volume.
+    Volume(f64),
+    /// A fixed traded value, `price * size`.
+    Dollar(f64),
+    /// Tick, volume or dollar imbalance bars.
+    Imbalance(InformationBars),
+    /// Tick, volume or dollar run bars.
+    Run(InformationBars),
+}
+
+#[derive(Debug, Clone)]
+enum RuleState {
+    Time(Timeframe),
+    Tick(usize),
+    Volume(f64),
+    Dollar(f64),
+    Information(InformationState),
+}
+
+/// Incrementally builds bars from trades.
+#[derive(Debug, Clone)]
+pub struct BarBuilder {
+    state: RuleState,
+    current: Option<Bar>,
+    ticks: usize,
+    dollars: f64,
+    /// Timestamp of the latest trade, which time bars require to not decrease.
+    last_timestamp: Option<i64>,
+}
+
+impl BarBuilder {
+    pub fn new(rule: BarRule) -> Result<Self, IndicatorError> {
+        let state = match rule {
+            BarRule::Time(timeframe) if timeframe.duration > 0 => RuleState::Time(timeframe),
+            BarRule::Tick(count) if count > 0 => RuleState::Tick(count),
+            BarRule::Volume(volume) if volume > 0.0 => RuleState::Volume(volume),
+            BarRule::Dollar(dollars) if dollars > 0.0 => RuleState::Dollar(dollars),
+            BarRule::Imbalance(config) | BarRule::Run(config) if config.is_valid() => {
+                RuleState::Information(InformationState::new(config, matches!(rule, BarRule::Run(_))))
+            }
+            _ => return Err(IndicatorError::InvalidParameters("Bar rule thresholds must be positive".to_string())),
+        };
+        Ok(BarBuilder { state, current: None, ticks: 0, dollars: 0.0, last_timestamp: None })
+    }
+
+    /// Adds a trade and returns the bar it completed, if any.
+    ///
+    /// Time bars complete when the first trade of a later bucket arrives; that trade opens
+    /// the next bar, so they reject trades older than the previous one. Every other rule
+    /// completes a bar on the trade that meets its threshold.
+    pub fn push(&mut self, tick: Tick) -> Result<Option<Bar>, IndicatorError> {
+        let mut completed = None;
+        if let RuleState::Time(timeframe) = &self.state {
+            if self.last_timestamp.is_some_and(|last| tick.timestamp < last) {
+                return Err(IndicatorError::InvalidInput("Timestamps must not decrease.".to_string()));
+            }
+            self.last_timestamp = Some(tick.timestamp);
+            let bucket = timeframe.bucket_start(tick.timestamp);
+            if self.current.is_some_and(|bar| bar.timestamp != bucket) {
+                completed = self.current.take();
+            }
+        }
+
+        let bar = self.current.get_or_insert(Bar {
+            timestamp: match &self.state {
+                RuleState::Time(timeframe) => timeframe.bucket_start(tick.timestamp),
+                _ => tick.timestamp,
+            },
+            open: tick.price,
+            high: tick.price,
+            low: tick.price,
+            close: tick.price,
+            volume: 0.0,
+        });
+        bar.high = bar.high.max(tick.price);
+        bar.low = bar.low.min(tick.price);
+        bar.close = tick.price;
+        bar.volume += tick.size;
+        self.ticks += 1;
+        self.dollars += tick.price * tick.size;
+
+        let complete = match &mut self.state {
+            RuleState::Time(_) => false,
+            RuleState::Tick(count) => self.ticks >= *count,
+            RuleState::Volume(volume) => bar.volume >= *volume,
+            RuleState::Dollar(dollars) => self.dollars >= *dollars,
+            RuleState::Information(state) => state.push(&tick),
+        };
+        if complete {
+            completed = self.current.take();
+        }
+        if self.current.is_none() {
+            self.ticks = 0;
+            self.dollars = 0.0;
+        }
+        Ok(completed)
+    }
+
+    /// Returns the incomplete bar, leaving the builder empty.
+    pub fn flush(&mut self) -> Option<Bar> {
+        self.ticks = 0;
+        self.dollars = 0.0;
+        self.current.take()
+    }
+}
+
+/// Collects bars into input data with every OHLCV field and the bar timestamps.
+pub fn bars_to_input_data(bars: &[Bar]) -> TimestampedData {
+    let field = |value: fn(&Bar) -> f64| Some(bars.iter().map(value).collect::<Array1<f64>>());
+    InputData {
+        open: field(|bar| bar.open),
+        high: field(|bar| bar.high),
+        low: field(|bar| bar.low),
+        close: field(|bar| bar.close),
+        volume: field(|bar| bar.volume),
+    }.with_timestamps(bars.iter().map(|bar| bar.timestamp).collect())
+}
+
+/// Builds bars from trade arrays in one pass. The trailing incomplete bar is included only if
+/// `include_partial` is set.
+pub fn build_bars(timestamp: ArrayView1<i64>, price: ArrayView1<f64>, size: ArrayView1<f64>, rule: BarRule, include_partial: bool) -> Result<TimestampedData, IndicatorError> {
+    if price.len() != timestamp.len() || size.len() != timestamp.len() {
+        return Err(IndicatorError::InvalidInput("Trade series must have the same length.".to_string()));
+    }
+
+    let mut builder = BarBuilder::new(rule)?;
+    let mut bars = Vec::new();
+    for i in 0..timestamp.len() {
+        bars.extend(builder.push(Tick { timestamp: timestamp[i], price: price[i], size: size[i] })?);
+    }
+    if include_partial {
+        bars.extend(builder.flush());
+    }
+
+    Ok(bars_to_input_data(&bars))
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+    use crate::bars::information::Measure;
+    use super::*;
+
+    fn trades() -> (Array1<i64>, Array1<f64>, Array1<f64>) {
+        (
+            array![0, 10_000, 30_000, 61_000, 65_000, 119_000, 125_000],
+            array![10.0, 11.0, 9.0, 10.0, 12.0, 11.0, 10.0],
+            array![1.0, 2.0, 3.0, 1.0, 1.0, 4.0, 2.0],
+        )
+    }
+
+    #[test]
+    fn test_time_bars() {
+        let (timestamp, price, size) = trades();
+
+        let TimestampedData { bars, timestamp } = build_bars(timestamp.view(), price.view(), size.view(), BarRule::Time(Timeframe::minutes(1)), true).unwrap();
+
+        assert_eq!(timestamp.unwrap(), array![0, 60_000, 120_000]);
+        assert_eq!(bars.open.unwrap(), array![10.0, 10.0, 10.0]);
+        assert_eq!(bars.high.unwrap(), array![11.0, 12.0, 10.0]);
+        assert_eq!(bars.low.unwrap(), array![9.0, 10.0, 10.0]);
+        assert_eq!(bars.close.unwrap(), array![9.0, 11.0, 10.0]);
+        assert_eq!(bars.volume.unwrap(), array![6.0, 6.0, 2.0]);
+    }
+
+    #[test]
+    fn test_time_bars_reject_decreasing_timestamps() {
+        let (mut timestamp, price, size) = trades();
+        timestamp[3] = timestamp[2] - 1;
+
+        assert!(build_bars(timestamp.view(), price.view(), size.view(), BarRule::Time(Timeframe::minutes(1)), true).is_err());
+        assert!(build_bars(timestamp.view(), price.view(), size.view(), BarRule::Tick(3), true).is_ok());
+    }
+
+    #[test]
+    fn test_tick_volume_and_dollar_bars() {
+        let (timestamp, price, size) = trades();
+
+        let tick = build_bars(timestamp.view(), price.view(), size.view(), BarRule::Tick(3), false).unwrap();
+        assert_eq!(tick.bars.close.unwrap(), array![9.0, 11.0]);
+
+        let volume = build_bars(timestamp.view(), price.view(), size.view(), BarRule::Volume(5.0), true).unwrap();
+        assert_eq!(volume.bars.volume.unwrap(), array![6.0, 6.0, 2.0]);
+        assert_eq!(volume.timestamp.unwrap(), array![0, 61_000, 125_000]);
+
+        // Traded values: 10, 22, 27 | 10, 12, 44 | 20
+        let dollar = build_bars(timestamp.view(), price.view(), size.view(), BarRule::Dollar(50.0), false).unwrap();
+        assert_eq!(dollar.bars.close.unwrap(), array![9.0, 11.0]);
+    }
+
+    #[test]
+    fn test_incremental_matches_batch() {
+        let (timestamp, price, size) = trades();
+        let rule = BarRule::Volume(5.0);
+
+        let mut builder = BarBuilder::new(rule).unwrap();
+        let mut emitted = Vec::new();
+        for i in 0..timestamp.len() {
+            emitted.extend(builder.push(Tick { timestamp: timestamp[i], price: price[i], size: size[i] }).unwrap());
+        }
+
+        let batch = build_bars(timestamp.view(), price.view(), size.view(), rule, false).unwrap();
+        assert_eq!(bars_to_input_data(&emitted).bars.close, batch.bars.close);
+        assert_eq!(builder.flush().unwrap().volume, 2.0);
+        assert!(builder.flush().is_none());
+    }
+
+    #[test]
+    fn test_imbalance_bars_close_on_one_sided_flow() {
+        // A steady uptrend is pure buy imbalance, so bars close after about `E[T]` ticks.
+        let length = 40;
+        let timestamp = Array1::from_shape_fn(length, |i| i as i64);
+        let price = Array1::from_shape_fn(length, |i| 100.0 + i as f64);
+        let size = Array1::ones(length);
+        let rule = BarRule::Imbalance(InformationBars::new(Measure::Tick, 5.0));
+
+        let bars = build_bars(timestamp.view(), price.view(), size.view(), rule, false).unwrap();
+
+        assert_eq!(bars.bars.volume.unwrap(), Array1::from_elem(8, 5.0));
+    }
+
+    #[test]
+    fn test_imbalance_bars_on_balanced_flow() {
+        // Upticks and downticks in random order: the expected imbalance per tick is close to
+        // zero, and without a floor every trade would close a bar.
+        let length = 2000;
+        let (mut state, mut last): (u64, f64) = (7, 100.0);
+        let price = Array1::from_shape_fn(length, |_| {
+            state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            last += if state >> 63 == 1 { 0.01 } else { -0.01 };
+            last
+        });
+        let timestamp = Array1::from_shape_fn(length, |i| i as i64);
+        let size = Array1::ones(length);
+        let count = |imbalance_floor| {
+            let rule = BarRule::Imbalance(InformationBars { imbalance_floor, ..InformationBars::new(Measure::Tick, 10.0) });
+            build_bars(timestamp.view(), price.view(), size.view(), rule, false).unwrap().bars.close.unwrap().len()
+        };
+
+        // Without a floor the threshold collapses to a single trade.
+        assert!(count(0.0) > length * 9 / 10);
+        let bars = count(InformationBars::new(Measure::Tick, 10.0).imbalance_floor);
+        assert!(bars > 0 && bars < length / 10, "{} bars", bars);
+    }
+
+    #[test]
+    fn test_run_bars_close_sooner_than_imbalance_bars_on_mixed_flow() {
+        // Two upticks per downtick: sells offset part of the imbalance but never shorten the buy run.
+        let length = 60;
+        let timestamp = Array1::from_shape_fn(length, |i| i as i64);
+        let price = Array1::from_shape_fn(length, |i| if i % 3 == 0 { 100.0 } else { 101.0 + i as f64 / 100.0 });
+        let size = Array1::ones(length);
+
+        let count = |rule| build_bars(timestamp.view(), price.view(), size.view(), rule, false).unwrap().bars.close.unwrap().len();
+        let imbalance = count(BarRule::Imbalance(InformationBars::new(Measure::Volume, 6.0)));
+        let runs = count(BarRule::Run(InformationBars::new(Measure::Volume, 6.0)));
+
+        assert!(imbalance > 0);
+        assert!(runs > imbalance);
+        assert!(BarBuilder::new(BarRule::Tick(0)).is_err());
+    }
+}
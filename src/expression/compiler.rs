@@ -0,0 +1,297 @@
+use ndarray::{s, Array1, Zip};
+use serde_json::Value;
+use crate::expression::parser::{parse, Expr};
+use crate::indicators::mathoperator::Operator;
+use crate::indicators::mathtransform::Function;
+use crate::indicators::utils::{exponential_moving_average_into, rolling_extremum_into, rolling_mean_into, rolling_sum_into, RollingExtremum};
+use crate::models::data::GenericInputDataView;
+use crate::models::float::Float;
+use crate::models::indicator::IndicatorError;
+use crate::models::price_source::PriceSource;
+
+/// Function over the last `period` values of a series.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Window {
+    Sma,
+    Ema,
+    Sum,
+    Min,
+    Max,
+    /// The value `period` bars back.
+    Lag,
+}
+
+/// Type-checked expression: names resolved, window periods known and constant subexpressions
+/// folded, so a `Constant` only ever appears as an operand of `Binary`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    Constant(f64),
+    Column(PriceSource),
+    Negate(Box<Node>),
+    Abs(Box<Node>),
+    Transform(Function, Box<Node>),
+    Binary(Operator, Box<Node>, Box<Node>),
+    Window { window: Window, period: usize, input: Box<Node> },
+}
+
+/// Largest window period accepted, far beyond any series length.
+const MAX_PERIOD: f64 = u32::MAX as f64;
+
+fn error(message: String, position: usize) -> IndicatorError {
+    IndicatorError::InvalidParameters(format!("{} at position {}.", message, position))
+}
+
+fn window(name: &str) -> Option<Window> {
+    match name {
+        "sma" => Some(Window::Sma),
+        "ema" => Some(Window::Ema),
+        "sum" => Some(Window::Sum),
+        "min" => Some(Window::Min),
+        "max" => Some(Window::Max),
+        "lag" => Some(Window::Lag),
+        _ => None,
+    }
+}
+
+fn transform(name: &str) -> Option<Function> {
+    match name {
+        "acos" => Some(Function::Acos),
+        "asin" => Some(Function::Asin),
+        "atan" => Some(Function::Atan),
+        "ceil" => Some(Function::Ceil),
+        "cos" => Some(Function::Cos),
+        "cosh" => Some(Function::Cosh),
+        "exp" => Some(Function::Exp),
+        "floor" => Some(Function::Floor),
+        "ln" => Some(Function::Ln),
+        "log10" => Some(Function::Log10),
+        "sin" => Some(Function::Sin),
+        "sinh" => Some(Function::Sinh),
+        "sqrt" => Some(Function::Sqrt),
+        "tan" => Some(Function::Tan),
+        "tanh" => Some(Function::Tanh),
+        _ => None,
+    }
+}
+
+fn check_call(name: &str, arguments: &[Expr], position: usize) -> Result<Node, IndicatorError> {
+    let arity = if window(name).is_some() { 2 } else if name == "abs" || transform(name).is_some() { 1 } else {
+        return Err(error(format!("Unknown function '{}'", name), position));
+    };
+    if arguments.len() != arity {
+        return Err(error(format!("'{}' takes {} argument(s), got {}", name, arity, arguments.len()), position));
+    }
+    let input = check(&arguments[0])?;
+
+    if let Some(window) = window(name) {
+        if matches!(input, Node::Constant(_)) {
+            return Err(error(format!("The first argument of '{}' must depend on a column", name), position));
+        }
+        let period = match check(&arguments[1])? {
+            Node::Constant(period) if period >= 1.0 && period.fract() == 0.0 => period as usize,
+            _ => return Err(error(format!("The period of '{}' must be a positive integer constant", name), position)),
+        };
+        if period as f64 > MAX_PERIOD {
+            return Err(error(format!("The period of '{}' must be at most {}", name, MAX_PERIOD), position));
+        }
+        return Ok(Node::Window { window, period, input: Box::new(input) });
+    }
+
+    Ok(match (transform(name), input) {
+        (None, Node::Constant(value)) => Node::Constant(value.abs()),
+        (None, input) => Node::Abs(Box::new(input)),
+        (Some(function), Node::Constant(value)) => Node::Constant(function.apply(value)),
+        (Some(function), input) => Node::Transform(function, Box::new(input)),
+    })
+}
+
+/// Resolves columns and functions of a parsed expression, checking argument counts and types.
+pub fn check(expr: &Expr) -> Result<Node, IndicatorError> {
+    match expr {
+        Expr::Number(value) => Ok(Node::Constant(*value)),
+        Expr::Identifier { name, position } => serde_json::from_value(Value::String(name.clone()))
+            .map(Node::Column)
+            .map_err(|_| error(format!("Unknown column '{}'", name), *position)),
+        Expr::Negate(inner) => Ok(match check(inner)? {
+            Node::Constant(value) => Node::Constant(-value),
+            inner => Node::Negate(Box::new(inner)),
+        }),
+        Expr::Binary { operator, left, right } => Ok(match (check(left)?, check(right)?) {
+            (Node::Constant(left), Node::Constant(right)) => Node::Constant(operator.apply(left, right)),
+            (left, right) => Node::Binary(*operator, Box::new(left), Box::new(right)),
+        }),
+        Expr::Call { name, arguments, position } => check_call(name, arguments, *position),
+    }
+}
+
+/// Parses and checks `source`, which must depend on at least one column.
+pub fn compile(source: &str) -> Result<Node, IndicatorError> {
+    match check(&parse(source)?)? {
+        Node::Constant(_) => Err(IndicatorError::InvalidParameters("The expression does not depend on any column.".to_string())),
+        node => Ok(node),
+    }
+}
+
+/// `window` over `values`, starting after their leading NaNs (the warm-up of the input). NaN
+/// until `period` values are available. Later NaNs are skipped by `min` and `max`, make `sma` and
+/// `sum` NaN while they are in the window and restart `ema`, which is seeded again after them.
+fn rolling<T: Float>(window: Window, period: usize, values: Array1<T>) -> Array1<T> {
+    let length = values.len();
+    let mut out = Array1::from_elem(length, T::nan());
+    if window == Window::Lag {
+        if period < length {
+            out.slice_mut(s![period..]).assign(&values.slice(s![..length - period]));
+        }
+        return out;
+    }
+    if window == Window::Ema {
+        let mut start = 0;
+        while start < length {
+            let end = (start..length).find(|&i| values[i].is_nan()).unwrap_or(length);
+            if end - start >= period {
+                let mut result = Array1::zeros(end - start);
+                exponential_moving_average_into(values.slice(s![start..end]), period, &mut result);
+                out.slice_mut(s![start..end]).assign(&result);
+            }
+            start = end + 1;
+        }
+        return out;
+    }
+
+    let first = values.iter().position(|value| !value.is_nan()).unwrap_or(length);
+    if first.saturating_add(period) > length {
+        return out;
+    }
+    let valid = values.slice(s![first..]);
+    let mut result = Array1::zeros(valid.len());
+    match window {
+        Window::Sma => rolling_mean_into(valid, 0, period, &mut result),
+        Window::Sum => rolling_sum_into(valid, period, &mut result),
+        Window::Min => rolling_extremum_into(valid, RollingExtremum::min(period), &mut result),
+        Window::Max => rolling_extremum_into(valid, RollingExtremum::max(period), &mut result),
+        Window::Lag | Window::Ema => unreachable!(),
+    }
+    out.slice_mut(s![first..]).assign(&result);
+    out
+}
+
+impl Node {
+    /// Evaluates the expression over `data`, one value per bar.
+    pub fn evaluate<T: Float>(&self, data: &GenericInputDataView<T>) -> Result<Array1<T>, IndicatorError> {
+        match self {
+            Node::Constant(_) => unreachable!("constants are folded into their parent"),
+            Node::Column(source) => source.series(data),
+            Node::Negate(inner) => Ok(inner.evaluate(data)?.mapv(|value| -value)),
+            Node::Abs(inner) => Ok(inner.evaluate(data)?.mapv(|value| value.abs())),
+            Node::Transform(function, inner) => Ok(inner.evaluate(data)?.mapv(|value| function.apply(value))),
+            Node::Binary(operator, left, right) => match (left.as_ref(), right.as_ref()) {
+                (Node::Constant(left), right) => {
+                    let left = T::cast(*left);
+                    Ok(right.evaluate(data)?.mapv(|right| operator.apply(left, right)))
+                }
+                (left, Node::Constant(right)) => {
+                    let right = T::cast(*right);
+                    Ok(left.evaluate(data)?.mapv(|left| operator.apply(left, right)))
+                }
+                (left, right) => {
+                    let (left, right) = (left.evaluate(data)?, right.evaluate(data)?);
+                    if left.len() != right.len() {
+                        return Err(IndicatorError::InvalidInput("Input data series of the bars must have the same length.".to_string()));
+                    }
+                    Ok(Zip::from(&left).and(&right).map_collect(|&left, &right| operator.apply(left, right)))
+                }
+            },
+            Node::Window { window, period, input } => Ok(rolling(*window, *period, input.evaluate(data)?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+    use crate::models::data::InputData;
+    use super::*;
+
+    fn message(source: &str) -> String {
+        match compile(source) {
+            Err(IndicatorError::InvalidParameters(message)) => message,
+            _ => panic!("Expected a compile error for {}", source),
+        }
+    }
+
+    #[test]
+    fn test_check_folds_constants() {
+        assert_eq!(
+            compile("close * (2 + 3) - abs(-4)").unwrap(),
+            Node::Binary(
+                Operator::Sub,
+                Box::new(Node::Binary(Operator::Mult, Box::new(Node::Column(PriceSource::Close)), Box::new(Node::Constant(5.0)))),
+                Box::new(Node::Constant(4.0)),
+            )
+        );
+        assert_eq!(
+            compile("sma(typical, 7 * 2)").unwrap(),
+            Node::Window { window: Window::Sma, period: 14, input: Box::new(Node::Column(PriceSource::Typical)) }
+        );
+    }
+
+    #[test]
+    fn test_check_errors() {
+        assert_eq!(message("price * 2"), "Unknown column 'price' at position 0.");
+        assert_eq!(message("rsi(close, 14)"), "Unknown function 'rsi' at position 0.");
+        assert_eq!(message("ema(close)"), "'ema' takes 2 argument(s), got 1 at position 0.");
+        assert_eq!(message("close - max(high, 2.5)"), "The period of 'max' must be a positive integer constant at position 8.");
+        assert_eq!(message("sma(close, high)"), "The period of 'sma' must be a positive integer constant at position 0.");
+        assert_eq!(message("sma(lag(close, 1), 100000000000000000000000)"), "The period of 'sma' must be at most 4294967295 at position 0.");
+        assert_eq!(message("sum(3, 2)"), "The first argument of 'sum' must depend on a column at position 0.");
+        assert_eq!(message("ln(10) * 2"), "The expression does not depend on any column.");
+    }
+
+    #[test]
+    fn test_evaluate_windows_after_warm_up() {
+        let input_data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(array![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]),
+            volume: None,
+        };
+        let data = input_data.view();
+
+        let sums = compile("sum(lag(close, 2), 3)").unwrap().evaluate(&data).unwrap();
+        assert!(sums.slice(s![..4]).iter().all(|value| value.is_nan()));
+        assert_eq!(sums.slice(s![4..]), array![6.0, 9.0]);
+
+        let spread = compile("max(close, 3) - min(close, 3) + sqrt(close * 0) - -close / 2").unwrap().evaluate(&data).unwrap();
+        assert_eq!(spread.slice(s![2..]), array![3.5, 4.0, 4.5, 5.0]);
+        assert!(compile("sma(close, 7)").unwrap().evaluate(&data).unwrap().iter().all(|value| value.is_nan()));
+        assert!(compile("sma(lag(close, 1), 4294967295)").unwrap().evaluate(&data).unwrap().iter().all(|value| value.is_nan()));
+        assert!(compile("close - volume").unwrap().evaluate(&data).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_windows_recover_after_nan() {
+        // The 3-bar range is flat over bars 2 to 4, so the stochastic is NaN on bar 4 only.
+        let input_data = InputData {
+            open: None,
+            high: Some(array![2.0, 3.0, 2.0, 2.0, 2.0, 4.0, 5.0, 6.0, 7.0]),
+            low: Some(array![1.0, 2.0, 2.0, 2.0, 2.0, 3.0, 4.0, 5.0, 6.0]),
+            close: Some(array![1.5, 3.0, 2.0, 2.0, 2.0, 4.0, 4.5, 5.0, 7.0]),
+            volume: None,
+        };
+        let data = input_data.view();
+        let stochastic = "(close - min(low, 3)) / (max(high, 3) - min(low, 3))";
+        let evaluate = |window: &str| compile(&format!("{}({}, 2)", window, stochastic)).unwrap().evaluate(&data).unwrap();
+
+        let k = compile(stochastic).unwrap().evaluate(&data).unwrap();
+        assert!(k[4].is_nan() && k.iter().enumerate().all(|(i, value)| i == 4 || i < 2 || !value.is_nan()));
+        let sum = evaluate("sum");
+        assert!(sum[4].is_nan() && sum[5].is_nan());
+        assert_eq!(sum.slice(s![6..]), array![k[5] + k[6], k[6] + k[7], k[7] + k[8]]);
+        assert_eq!(evaluate("sma").slice(s![6..]), array![k[5] + k[6], k[6] + k[7], k[7] + k[8]] / 2.0);
+        assert_eq!(evaluate("max")[5], k[5]);
+        let ema = evaluate("ema");
+        assert!(ema[4].is_nan() && ema[5].is_nan());
+        assert_eq!(ema[6], (k[5] + k[6]) / 2.0);
+    }
+}
@@ -0,0 +1,147 @@
+//! Custom indicators written as expressions over bar columns, e.g. `ema(close, 12) - ema(close, 26)`
+//! or `(close - min(low, 14)) / (max(high, 14) - min(low, 14))`.
+//!
+//! * Columns are the `PriceSource` names: `open`, `high`, `low`, `close`, `volume`, `average`,
+//!   `median`, `typical` and `weighted_close`.
+//! * Operators are `+`, `-`, `*`, `/` and unary `-`, with the usual precedence.
+//! * `sma`, `ema`, `sum`, `min`, `max` and `lag` take a series and a constant integer period. They
+//!   start after the leading NaNs of their input, so windows can be nested. A NaN later on, such
+//!   as a division by a flat range, only affects the windows it is in.
+//! * `abs` and the math transforms (`ln`, `sqrt`, `sin`, ...) apply to every value.
+//!
+//! `CustomIndicator::compile` parses and type-checks the expression once; the result implements
+//! `Indicator` and can be registered like any built-in.
+
+use std::collections::HashSet;
+use serde_json::Value;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::GenericInputDataView;
+use crate::models::float::Float;
+use crate::models::groups::Group;
+use crate::models::indicator::{Indicator, IndicatorError};
+
+pub mod compiler;
+pub mod parser;
+
+use compiler::{compile, Node};
+
+/// An indicator defined by an expression. It has a single output series and no parameters.
+pub struct CustomIndicator {
+    short_name: String,
+    source: String,
+    expression: Node,
+    groups: HashSet<Group>,
+}
+
+impl CustomIndicator {
+    /// Compiles `source` into an indicator named `short_name`.
+    pub fn compile(short_name: &str, source: &str) -> Result<Self, IndicatorError> {
+        let expression = compile(source)?;
+        Ok(CustomIndicator {
+            short_name: short_name.to_string(),
+            source: source.to_string(),
+            expression,
+            groups: HashSet::new(),
+        })
+    }
+
+    /// Groups the indicator is listed under in a registry.
+    pub fn with_groups(mut self, groups: HashSet<Group>) -> Self {
+        self.groups = groups;
+        self
+    }
+}
+
+impl<T: Float> Indicator<T> for CustomIndicator {
+    fn short_name(&self) -> &str {
+        &self.short_name
+    }
+
+    /// The expression itself.
+    fn name(&self) -> &str {
+        &self.source
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        if !(params.is_null() || params.as_object().is_some_and(|params| params.is_empty())) {
+            return Err(IndicatorError::InvalidParameters(format!("'{}' takes no parameters", self.short_name)));
+        }
+
+        let values = self.expression.evaluate(data)?;
+        out.single(values.len()).assign(&values);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::indicators::apo::APO;
+    use crate::models::data::{InputData, OutputData};
+    use crate::models::groups::UseCase;
+    use crate::models::registry::{IndicatorRegistry, Registry};
+    use super::*;
+
+    fn input_data() -> InputData {
+        let close = array![10.0, 11.0, 10.5, 12.0, 13.0, 12.5, 14.0, 13.5, 15.0, 16.0, 15.5, 17.0, 16.5, 18.0];
+        InputData {
+            open: None,
+            high: Some(&close + 0.5),
+            low: Some(&close - 1.0),
+            close: Some(close),
+            volume: None,
+        }
+    }
+
+    #[test]
+    fn test_expression_matches_builtin() {
+        let data = input_data();
+        let custom = CustomIndicator::compile("MYAPO", "ema(close, 3) - ema(close, 6)").unwrap();
+
+        let expected = APO::new().calculate(&data, json!({ "fast_period": 3, "slow_period": 6 })).unwrap();
+        let actual = custom.calculate(&data, json!({})).unwrap();
+        let (expected, actual) = (expected.series("real").unwrap(), actual.series("real").unwrap());
+        for (expected, actual) in expected.iter().zip(actual.iter()) {
+            assert!((expected.is_nan() && actual.is_nan()) || (expected - actual).abs() < 1e-12, "{} != {}", actual, expected);
+        }
+        assert!(custom.calculate(&data, json!({ "period": 3 })).is_err());
+    }
+
+    #[test]
+    fn test_stochastic_expression() {
+        let data = input_data();
+        let stochastic = CustomIndicator::compile("STOCHK", "100 * (close - min(low, 3)) / (max(high, 3) - min(low, 3))").unwrap();
+
+        if let OutputData::SingleSeries(k) = stochastic.calculate(&data, json!({})).unwrap() {
+            assert!(k[0].is_nan() && k[1].is_nan());
+            // Lowest low 9, highest high 11.5.
+            assert_eq!(k[2], 100.0 * (10.5 - 9.0) / (11.5 - 9.0));
+        } else {
+            panic!("Unexpected output format");
+        }
+        assert!(stochastic.calculate(&InputData { high: None, ..input_data() }, json!({})).is_err());
+    }
+
+    #[test]
+    fn test_register_at_runtime() {
+        let mut registry = IndicatorRegistry::with_builtins();
+        let momentum = HashSet::from([Group::UseCase(UseCase::MomentumDetection)]);
+        registry.register_indicator(Box::new(CustomIndicator::compile("SPREAD", "close - lag(close, 2)").unwrap().with_groups(momentum)));
+
+        let spread = registry.get("SPREAD").unwrap();
+        assert_eq!(spread.name(), "close - lag(close, 2)");
+        assert_eq!(spread.calculate(&input_data(), json!({})).unwrap().series("real").unwrap()[2], 0.5);
+        assert!(registry.short_names_in_group(&Group::UseCase(UseCase::MomentumDetection)).contains(&"SPREAD"));
+
+        // Replacing the definition drops the old one along with its name.
+        registry.register_indicator(Box::new(CustomIndicator::compile("SPREAD", "close - lag(close, 1)").unwrap()));
+        assert_eq!(registry.get("SPREAD").unwrap().name(), "close - lag(close, 1)");
+        assert!(!registry.short_names_in_group(&Group::UseCase(UseCase::MomentumDetection)).contains(&"SPREAD"));
+        assert!(CustomIndicator::compile("BROKEN", "close +").is_err());
+    }
+}
@@ -0,0 +1,298 @@
+use crate::indicators::mathoperator::Operator;
+use crate::models::indicator::IndicatorError;
+
+/// Untyped syntax tree of an expression. `position` is the byte offset of the node in the source,
+/// for error messages.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Identifier { name: String, position: usize },
+    Negate(Box<Expr>),
+    Binary { operator: Operator, left: Box<Expr>, right: Box<Expr> },
+    Call { name: String, arguments: Vec<Expr>, position: usize },
+}
+
+/// Deepest an expression may nest parentheses, calls and operators, so that parsing, checking
+/// and evaluating it stay within the stack.
+pub const MAX_DEPTH: usize = 256;
+
+/// A syntax tree and its height.
+type Parsed = (Expr, usize);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Identifier(String),
+    Operator(Operator),
+    Minus,
+    LeftParen,
+    RightParen,
+    Comma,
+    End,
+}
+
+fn error(message: &str, position: usize) -> IndicatorError {
+    IndicatorError::InvalidParameters(format!("{} at position {}.", message, position))
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, IndicatorError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some(&(position, c)) = chars.peek() {
+        let token = match c {
+            c if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '0'..='9' | '.' => {
+                let mut end = position;
+                while let Some(&(index, c)) = chars.peek() {
+                    if !(c.is_ascii_digit() || c == '.') {
+                        break;
+                    }
+                    end = index + c.len_utf8();
+                    chars.next();
+                }
+                let number = source[position..end].parse().map_err(|_| error("Invalid number", position))?;
+                tokens.push((Token::Number(number), position));
+                continue;
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut end = position;
+                while let Some(&(index, c)) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    end = index + c.len_utf8();
+                    chars.next();
+                }
+                tokens.push((Token::Identifier(source[position..end].to_string()), position));
+                continue;
+            }
+            '+' => Token::Operator(Operator::Add),
+            '-' => Token::Minus,
+            '*' => Token::Operator(Operator::Mult),
+            '/' => Token::Operator(Operator::Div),
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            ',' => Token::Comma,
+            _ => return Err(error(&format!("Unexpected character '{}'", c), position)),
+        };
+        tokens.push((token, position));
+        chars.next();
+    }
+    tokens.push((Token::End, source.len()));
+    Ok(tokens)
+}
+
+/// Recursive descent over the tokens, one method per precedence level. `depth` counts the
+/// nested calls, parentheses and negations being parsed.
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    next: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.next].0
+    }
+
+    fn position(&self) -> usize {
+        self.tokens[self.next].1
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.next].0.clone();
+        if token != Token::End {
+            self.next += 1;
+        }
+        token
+    }
+
+    fn expect(&mut self, expected: Token, description: &str) -> Result<(), IndicatorError> {
+        if *self.peek() == expected {
+            self.advance();
+            Ok(())
+        } else {
+            Err(error(&format!("Expected {}", description), self.position()))
+        }
+    }
+
+    /// Starts parsing a nested call, parenthesis or negation at `position`.
+    fn enter(&mut self, position: usize) -> Result<(), IndicatorError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(error("Expression is nested too deeply", position));
+        }
+        Ok(())
+    }
+
+    /// Builds a node of `height`, which must not exceed `MAX_DEPTH`.
+    fn node(&self, expr: Expr, height: usize, position: usize) -> Result<Parsed, IndicatorError> {
+        if height > MAX_DEPTH {
+            return Err(error("Expression is nested too deeply", position));
+        }
+        Ok((expr, height))
+    }
+
+    /// `term (('+' | '-') term)*`
+    fn expression(&mut self) -> Result<Parsed, IndicatorError> {
+        let (mut left, mut height) = self.term()?;
+        loop {
+            let position = self.position();
+            let operator = match self.peek() {
+                Token::Operator(Operator::Add) => Operator::Add,
+                Token::Minus => Operator::Sub,
+                _ => return Ok((left, height)),
+            };
+            self.advance();
+            let (right, right_height) = self.term()?;
+            let binary = Expr::Binary { operator, left: Box::new(left), right: Box::new(right) };
+            (left, height) = self.node(binary, height.max(right_height) + 1, position)?;
+        }
+    }
+
+    /// `unary (('*' | '/') unary)*`
+    fn term(&mut self) -> Result<Parsed, IndicatorError> {
+        let (mut left, mut height) = self.unary()?;
+        loop {
+            let position = self.position();
+            let operator = match self.peek() {
+                Token::Operator(operator @ (Operator::Mult | Operator::Div)) => *operator,
+                _ => return Ok((left, height)),
+            };
+            self.advance();
+            let (right, right_height) = self.unary()?;
+            let binary = Expr::Binary { operator, left: Box::new(left), right: Box::new(right) };
+            (left, height) = self.node(binary, height.max(right_height) + 1, position)?;
+        }
+    }
+
+    /// `'-' unary | primary`
+    fn unary(&mut self) -> Result<Parsed, IndicatorError> {
+        if *self.peek() == Token::Minus {
+            let position = self.position();
+            self.advance();
+            self.enter(position)?;
+            let (inner, height) = self.unary()?;
+            self.depth -= 1;
+            return self.node(Expr::Negate(Box::new(inner)), height + 1, position);
+        }
+        self.primary()
+    }
+
+    /// `number | identifier | identifier '(' arguments ')' | '(' expression ')'`
+    fn primary(&mut self) -> Result<Parsed, IndicatorError> {
+        let position = self.position();
+        match self.advance() {
+            Token::Number(value) => Ok((Expr::Number(value), 1)),
+            Token::Identifier(name) if *self.peek() == Token::LeftParen => {
+                self.advance();
+                self.enter(position)?;
+                let (mut arguments, mut height) = (Vec::new(), 0);
+                if *self.peek() != Token::RightParen {
+                    loop {
+                        let (argument, argument_height) = self.expression()?;
+                        arguments.push(argument);
+                        height = height.max(argument_height);
+                        if *self.peek() != Token::Comma {
+                            break;
+                        }
+                        self.advance();
+                    }
+                }
+                self.expect(Token::RightParen, "')' or ','")?;
+                self.depth -= 1;
+                self.node(Expr::Call { name, arguments, position }, height + 1, position)
+            }
+            Token::Identifier(name) => Ok((Expr::Identifier { name, position }, 1)),
+            Token::LeftParen => {
+                self.enter(position)?;
+                let inner = self.expression()?;
+                self.expect(Token::RightParen, "')'")?;
+                self.depth -= 1;
+                Ok(inner)
+            }
+            Token::End => Err(error("Unexpected end of expression", position)),
+            _ => Err(error("Expected a number, column or function call", position)),
+        }
+    }
+}
+
+/// Parses `source` into a syntax tree, without checking names or argument types. Trees deeper
+/// than [`MAX_DEPTH`] are rejected.
+pub fn parse(source: &str) -> Result<Expr, IndicatorError> {
+    let mut parser = Parser { tokens: tokenize(source)?, next: 0, depth: 0 };
+    let (expr, _) = parser.expression()?;
+    if *parser.peek() != Token::End {
+        return Err(error("Unexpected input", parser.position()));
+    }
+    Ok(expr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identifier(name: &str, position: usize) -> Box<Expr> {
+        Box::new(Expr::Identifier { name: name.to_string(), position })
+    }
+
+    fn message(source: &str) -> String {
+        match parse(source) {
+            Err(IndicatorError::InvalidParameters(message)) => message,
+            _ => panic!("Expected a parse error for {}", source),
+        }
+    }
+
+    #[test]
+    fn test_parse_precedence() {
+        // Multiplication binds tighter than subtraction, and both associate left.
+        let expr = parse("close - 2 * -high - low").unwrap();
+        assert_eq!(expr, Expr::Binary {
+            operator: Operator::Sub,
+            left: Box::new(Expr::Binary {
+                operator: Operator::Sub,
+                left: identifier("close", 0),
+                right: Box::new(Expr::Binary {
+                    operator: Operator::Mult,
+                    left: Box::new(Expr::Number(2.0)),
+                    right: Box::new(Expr::Negate(identifier("high", 13))),
+                }),
+            }),
+            right: identifier("low", 20),
+        });
+    }
+
+    #[test]
+    fn test_parse_calls() {
+        let expr = parse("ema(close, 12) / (max(high,14))").unwrap();
+        let Expr::Binary { operator: Operator::Div, left, right } = expr else { panic!("Unexpected expression") };
+        assert_eq!(*left, Expr::Call { name: "ema".to_string(), arguments: vec![*identifier("close", 4), Expr::Number(12.0)], position: 0 });
+        assert!(matches!(*right, Expr::Call { ref name, ref arguments, position: 18 } if name == "max" && arguments.len() == 2));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(message("close +"), "Unexpected end of expression at position 7.");
+        assert_eq!(message("ema(close, 12"), "Expected ')' or ',' at position 13.");
+        assert_eq!(message("close $ 2"), "Unexpected character '$' at position 6.");
+        assert_eq!(message("close high"), "Unexpected input at position 6.");
+        assert_eq!(message("1.2.3"), "Invalid number at position 0.");
+    }
+
+    #[test]
+    fn test_parse_rejects_deep_nesting() {
+        let nested = |depth: usize| format!("{}close{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(parse(&nested(MAX_DEPTH)).is_ok());
+        assert_eq!(message(&nested(200_000)), format!("Expression is nested too deeply at position {}.", MAX_DEPTH));
+
+        let calls = format!("{}close{}", "abs(".repeat(200_000), ")".repeat(200_000));
+        assert!(message(&calls).starts_with("Expression is nested too deeply"));
+        assert!(message(&format!("{}close", "-".repeat(200_000))).starts_with("Expression is nested too deeply"));
+        // Long operator chains nest without recursing in the parser.
+        assert!(parse(&vec!["close"; MAX_DEPTH].join(" + ")).is_ok());
+        assert!(message(&vec!["close"; 200_000].join(" * ")).starts_with("Expression is nested too deeply"));
+    }
+}
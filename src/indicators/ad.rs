@@ -1,7 +1,9 @@
 use std::collections::HashSet;
 use serde_json::Value;
-use crate::indicators::utils::calculate_adl;
-use crate::models::data::{BarField, InputData, OutputData};
+use crate::indicators::utils::calculate_adl_into;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, GenericInputDataView};
+use crate::models::float::Float;
 use crate::models::groups::{Group, UseCase, MathematicalBasis, DataInputType, SignalType, OutputFormat, TimeframeFocus, ComplexityLevel, MarketSuitability, TradingStrategySuitability, SmoothingTechnique, CalculationMethodology, SignalInterpretation};
 use crate::models::indicator::{Indicator, IndicatorError};
 use crate::validation::validator::Validator;
@@ -46,7 +48,13 @@ impl AD {
     }
 }
 
-impl Indicator for AD {
+impl Default for AD {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for AD {
     fn short_name(&self) -> &'static str {
         "AD"
     }
@@ -59,17 +67,14 @@ impl Indicator for AD {
         &self.groups
     }
 
-    fn calculate(&self, data: &InputData, params: Value) -> Result<OutputData, IndicatorError> {
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, _params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
         self.validator.validate_data(data)?;
         let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
         let low = data.get_by_bar_field(&BarField::LOW).unwrap();
         let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
         let volume = data.get_by_bar_field(&BarField::VOLUME).unwrap();
 
-        let ad_line = calculate_adl(high, low, close, volume)?;
-
-        Ok(OutputData::SingleSeries(ad_line))
-
+        calculate_adl_into(high, low, close, volume, out.single(high.len()))
     }
 }
 
@@ -2,8 +2,10 @@ use std::collections::HashSet;
 use ndarray::s;
 use serde_json::Value;
 use serde::{Deserialize, Serialize};
-use crate::indicators::utils::{calculate_adl, calculate_ema, validate_parameter_within_data_length};
-use crate::models::data::{BarField, InputData, OutputData};
+use crate::indicators::utils::{calculate_adl_into, calculate_ema_into, validate_parameter_within_data_length};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
 use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
 use crate::models::indicator::{Indicator, IndicatorError};
 use crate::validation::validator::{IParameter, ParamRule, Validator};
@@ -43,8 +45,8 @@ fn create_validator() -> Validator {
             ParamRule::PositiveInteger("short_period"),
             ParamRule::PositiveInteger("long_period"),
             ParamRule::CorrectPeriod {left: "short_period", right: "long_period"},
-            ParamRule::Custom(Box::new(|value: &Value, data: &InputData| validate_parameter_within_data_length(value, data, "short_period", BarField::HIGH))),
-            ParamRule::Custom(Box::new(|value: &Value, data: &InputData| validate_parameter_within_data_length(value, data, "long_period", BarField::HIGH))),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "short_period", BarField::HIGH))),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "long_period", BarField::HIGH))),
         ],
     )
 }
@@ -57,6 +59,12 @@ impl ADOSC {
     }
 }
 
+impl Default for ADOSC {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 fn create_groups() -> HashSet<Group> {
     let mut groups = HashSet::new();
     groups.insert(Group::UseCase(UseCase::MomentumDetection));
@@ -80,7 +88,7 @@ fn create_groups() -> HashSet<Group> {
     groups
 }
 
-impl Indicator for ADOSC {
+impl<T: Float> Indicator<T> for ADOSC {
     fn short_name(&self) -> &'static str {
         "ADOSC"
     }
@@ -93,7 +101,7 @@ impl Indicator for ADOSC {
         &self.groups
     }
 
-    fn calculate(&self, data: &InputData, params: Value) -> Result<OutputData, IndicatorError> {
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
         let params: ADOSCParams = serde_json::from_value(params)
             .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
 
@@ -103,18 +111,21 @@ impl Indicator for ADOSC {
         let low = data.get_by_bar_field(&BarField::LOW).unwrap();
         let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
         let volume = data.get_by_bar_field(&BarField::VOLUME).unwrap();
+        let [adl, short_ema, long_ema] = scratch.arrays(high.len());
 
         // Step 1: Calculate the Accumulation/Distribution Line (ADL)
-        let adl = calculate_adl(high, low, close, volume)?;
+        calculate_adl_into(high, low, close, volume, adl)?;
 
         // Step 2: Calculate EMAs of the ADL
-        let short_ema = calculate_ema(&adl, params.short_period)?;
-        let long_ema = calculate_ema(&adl, params.long_period)?;
+        calculate_ema_into(adl.view(), params.short_period, short_ema)?;
+        calculate_ema_into(adl.view(), params.long_period, long_ema)?;
 
         let start_index = params.long_period - 1;
-        let oscillator_values = &short_ema.slice(s![start_index..]) - &long_ema.slice(s![start_index..]);
+        let oscillator_values = out.single(high.len() - start_index);
+        oscillator_values.assign(&short_ema.slice(s![start_index..]));
+        *oscillator_values -= &long_ema.slice(s![start_index..]);
 
-        Ok(OutputData::SingleSeries(oscillator_values.to_owned()))
+        Ok(())
     }
 }
 
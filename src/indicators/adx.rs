@@ -1,9 +1,11 @@
 use std::collections::HashSet;
-use ndarray::{s, Array1};
+use ndarray::s;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use crate::indicators::utils::{calculate_directional_movements, calculate_true_range, validate_parameter_within_data_length, wilder_smoothing};
-use crate::models::data::{BarField, InputData, OutputData};
+use crate::indicators::utils::{calculate_directional_movements_into, calculate_true_range_into, validate_parameter_within_data_length, wilder_smoothing_in_place};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
 use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
 use crate::models::indicator::{Indicator, IndicatorError};
 use crate::validation::validator::{IParameter, ParamRule, Validator};
@@ -56,7 +58,7 @@ fn create_validator() -> Validator {
         vec![
             ParamRule::Required("period"),
             ParamRule::PositiveInteger("period"),
-            ParamRule::Custom(Box::new(|value: &Value, data: &InputData| validate_parameter_within_data_length(value, data, "period", BarField::HIGH))),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::HIGH))),
         ]
     )
 }
@@ -71,7 +73,13 @@ impl ADX {
     }
 }
 
-impl Indicator for ADX {
+impl Default for ADX {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for ADX {
     fn short_name(&self) -> &'static str {
         "ADX"
     }
@@ -81,7 +89,7 @@ impl Indicator for ADX {
     fn get_groups(&mut self) -> &HashSet<Group> {
         &self.groups
     }
-    fn calculate(&self, data: &InputData, params: Value) -> Result<OutputData, IndicatorError> {
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
         // Parse parameters
 
         let params: ADXParams = serde_json::from_value(params)
@@ -93,59 +101,43 @@ impl Indicator for ADX {
         let low = data.get_by_bar_field(&BarField::LOW).unwrap();
         let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
         let length = high.len();
-
+        let [tr, plus_dm, minus_dm] = scratch.arrays(length);
 
         // Step 1: Calculate True Range (TR)
-        let tr = calculate_true_range(high, low, close)?;
+        calculate_true_range_into(high, low, close, tr)?;
 
         // Step 2: Calculate +DM and -DM
-        let (plus_dm, minus_dm) = calculate_directional_movements(high, low)?;
+        calculate_directional_movements_into(high, low, plus_dm, minus_dm)?;
 
         // Step 3: Calculate smoothed TR, +DM, -DM
-        let smoothed_tr = wilder_smoothing(&tr, params.period)?;
-        let smoothed_plus_dm = wilder_smoothing(&plus_dm, params.period)?;
-        let smoothed_minus_dm = wilder_smoothing(&minus_dm, params.period)?;
-
-        // Step 4: Calculate +DI and -DI
-        let plus_di = (smoothed_plus_dm / &smoothed_tr) * 100.0;
-        let minus_di = (smoothed_minus_dm / &smoothed_tr) * 100.0;
-
-        // Step 5: Calculate DX
-        let di_sum = &plus_di + &minus_di;
-        let di_diff = (&plus_di - &minus_di).mapv(f64::abs);
-
-        // Handle division by zero
-        let dx = di_diff / di_sum * 100.0;
-        let dx = dx.mapv(|x| if x.is_nan() || x.is_infinite() { 0.0 } else { x });
+        wilder_smoothing_in_place(tr, params.period)?;
+        wilder_smoothing_in_place(plus_dm, params.period)?;
+        wilder_smoothing_in_place(minus_dm, params.period)?;
+
+        let hundred = T::cast(100.0);
+        let adx = out.single(length);
+        for i in 0..length {
+            // Step 4: Calculate +DI and -DI
+            let plus_di = (plus_dm[i] / tr[i]) * hundred;
+            let minus_di = (minus_dm[i] / tr[i]) * hundred;
+
+            // Step 5: Calculate DX
+            let di_sum = plus_di + minus_di;
+            let di_diff = (plus_di - minus_di).abs();
+
+            // Handle division by zero
+            let dx = di_diff / di_sum * hundred;
+            adx[i] = if dx.is_nan() || dx.is_infinite() { T::zero() } else { dx };
+        }
 
         // Step 6: Calculate ADX as the smoothed DX
-        let adx = wilder_smoothing(&dx, params.period)?;
+        wilder_smoothing_in_place(adx, params.period)?;
 
-        // Initialize the full ADX array with NaNs
-        let mut full_adx = Array1::<f64>::from_elem(length, f64::NAN);
+        // Values before the starting index of valid ADX values are NaN
+        let start_index = (2 * (params.period - 1)).min(length);
+        adx.slice_mut(s![..start_index]).fill(T::nan());
 
-        if length < params.period {
-            Ok(OutputData::SingleSeries(full_adx))
-        } else {
-            // Determine the starting index for valid ADX values
-            let start_index = 2 * (params.period - 1);
-
-            // Take slices of adx and full_adx starting from start_index
-            let valid_adx = adx.slice(s![start_index..]);
-            let valid_length = valid_adx.len();
-
-            if start_index + valid_length > length {
-                return Err(IndicatorError::CalculationError(
-                    "Calculated ADX length exceeds input data length.".to_string(),
-                ));
-            }
-
-            // Assign valid ADX values to full_adx starting from start_index
-            full_adx.slice_mut(s![start_index..start_index + valid_length])
-                .assign(&valid_adx);
-
-            Ok(OutputData::SingleSeries(full_adx))
-        }
+        Ok(())
     }
 }
 
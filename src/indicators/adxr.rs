@@ -1,10 +1,11 @@
 use std::collections::HashSet;
-use ndarray::Array1;
 use serde::{Deserialize, Serialize};
-use serde_json::{json, Value};
+use serde_json::Value;
 use crate::indicators::adx::ADX;
 use crate::indicators::utils::validate_parameter_within_data_length;
-use crate::models::data::{BarField, InputData, OutputData};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
 use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
 use crate::models::indicator::{Indicator, IndicatorError};
 use crate::validation::validator::{IParameter, ParamRule, Validator};
@@ -22,6 +23,7 @@ impl IParameter for ADXRParams {}
 pub struct ADXR {
     groups: HashSet<Group>,
     validator: Validator,
+    adx: ADX,
 }
 
 fn create_groups() -> HashSet<Group> {
@@ -54,7 +56,7 @@ fn create_validator() -> Validator {
         vec![
             ParamRule::Required("period"),
             ParamRule::PositiveInteger("period"),
-            ParamRule::Custom(Box::new(|value: &Value, data: &InputData| validate_parameter_within_data_length(value, data, "period", BarField::HIGH))),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::HIGH))),
         ]
     )
 }
@@ -63,11 +65,17 @@ impl ADXR {
     pub fn new() -> Self {
         let groups = create_groups();
         let validator = create_validator();
-        Self { groups, validator }
+        Self { groups, validator, adx: ADX::new() }
     }
 }
 
-impl Indicator for ADXR {
+impl Default for ADXR {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for ADXR {
     fn short_name(&self) -> &'static str {
         "ADXR"
     }
@@ -80,38 +88,39 @@ impl Indicator for ADXR {
         &self.groups
     }
 
-    fn calculate(&self, data: &InputData, params: Value) -> Result<OutputData, IndicatorError> {
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
         let adxr_params: ADXRParams = serde_json::from_value(params.clone()).map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
         self.validator.validate(data, &adxr_params)?;
-        let adx_indicator = ADX::new();
-        let adx_result = adx_indicator.calculate(data, params)?;
+        self.adx.calculate_view_into(data, params, out, scratch)?;
 
-        let adx_values = match adx_result {
-            OutputData::SingleSeries(series) => series,
-            _ => return Err(IndicatorError::CalculationError("Invalid ADX output.".to_string())),
-        };
+        let adx = out.as_single()
+            .ok_or_else(|| IndicatorError::CalculationError("Invalid ADX output.".to_string()))?;
+        let length = adx.len();
+        let [adx_values] = scratch.arrays(length);
+        adx_values.assign(adx);
 
-        let length = adx_values.len();
         // Initialize ADXR array with NaNs
-        let mut adxr_values = Array1::<f64>::from_elem(length, f64::NAN);
+        let adxr_values = out.single(length);
+        adxr_values.fill(T::nan());
 
         // Compute ADXR
         for i in adxr_params.period..adxr_values.len() {
             if adx_values[i].is_nan() || adx_values[i - adxr_params.period].is_nan() {
                 continue;
             }
-            adxr_values[i] = (adx_values[i] + adx_values[i - adxr_params.period]) / 2.0;
+            adxr_values[i] = (adx_values[i] + adx_values[i - adxr_params.period]) / T::cast(2.0);
         }
 
-        Ok(OutputData::SingleSeries(adxr_values))
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use ndarray::array;
+    use serde_json::json;
     use super::*;
-    use crate::models::data::InputData;
+    use crate::models::data::{InputData, OutputData};
 
     #[test]
     fn test_adxr() {
@@ -3,11 +3,13 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use crate::indicators::utils;
 use crate::indicators::utils::validate_parameter_within_data_length;
-use crate::models::data::{BarField, InputData, OutputData};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
 use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
 use crate::models::indicator::{Indicator, IndicatorError};
 use crate::validation::validator::{IParameter, ParamRule, Validator};
-use utils::exponential_moving_average;
+use utils::exponential_moving_average_into;
 
 #[derive(Serialize, Deserialize)]
 pub struct APOParams {
@@ -58,8 +60,8 @@ fn create_validator() -> Validator {
             ParamRule::PositiveInteger("fast_period"),
             ParamRule::PositiveInteger("slow_period"),
             ParamRule::CorrectPeriod { left: "fast_period", right: "slow_period" },
-            ParamRule::Custom(Box::new(|value: &Value, data: &InputData| validate_parameter_within_data_length(value, data, "fast_period", BarField::CLOSE))),
-            ParamRule::Custom(Box::new(|value: &Value, data: &InputData| validate_parameter_within_data_length(value, data, "slow_period", BarField::CLOSE))),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "fast_period", BarField::CLOSE))),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "slow_period", BarField::CLOSE))),
         ],
     )
 }
@@ -72,7 +74,13 @@ impl APO {
     }
 }
 
-impl Indicator for APO {
+impl Default for APO {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for APO {
     fn short_name(&self) -> &'static str {
         "APO"
     }
@@ -85,20 +93,22 @@ impl Indicator for APO {
         &self.groups
     }
 
-    fn calculate(&self, data: &InputData, params: Value) -> Result<OutputData, IndicatorError> {
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
         let params: APOParams = serde_json::from_value(params)
             .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
 
         self.validator.validate(data, &params)?;
 
         let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+        let [slow_ema] = scratch.arrays(close.len());
+        let apo_values = out.single(close.len());
 
-        let fast_ema = exponential_moving_average(close, params.fast_period);
-        let slow_ema = exponential_moving_average(close, params.slow_period);
+        exponential_moving_average_into(close, params.fast_period, apo_values);
+        exponential_moving_average_into(close, params.slow_period, slow_ema);
 
-        let apo_values = fast_ema - slow_ema;
+        *apo_values -= &*slow_ema;
 
-        Ok(OutputData::SingleSeries(apo_values))
+        Ok(())
     }
 }
 
@@ -107,7 +117,7 @@ impl Indicator for APO {
 mod tests {
     use ndarray::array;
     use serde_json::json;
-    use crate::models::data::InputData;
+    use crate::models::data::{InputData, OutputData};
     use super::*;
 
     #[test]
@@ -1,10 +1,10 @@
-use std::collections::{HashMap, HashSet};
-use ndarray::{s, Array1};
-use ndarray_stats::QuantileExt;
+use std::collections::HashSet;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use crate::indicators::utils::validate_parameter_within_data_length;
-use crate::models::data::{BarField, InputData, OutputData};
+use crate::indicators::utils::{validate_parameter_within_data_length, RollingExtremum};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
 use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
 use crate::models::indicator::{Indicator, IndicatorError};
 use crate::validation::validator::{IParameter, ParamRule, Validator};
@@ -65,8 +65,8 @@ fn create_validator() -> Validator {
         vec![
             ParamRule::Required("period"),
             ParamRule::PositiveInteger("period"),
-            ParamRule::Custom(Box::new(|value: &Value, data: &InputData| validate_parameter_within_data_length(value, data, "period", BarField::HIGH))),
-            ParamRule::Custom(Box::new(|value: &Value, data: &InputData| validate_parameter_within_data_length(value, data, "period", BarField::LOW))),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::HIGH))),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::LOW))),
         ]
     )
 }
@@ -79,7 +79,13 @@ impl AROON {
     }
 }
 
-impl Indicator for AROON {
+impl Default for AROON {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for AROON {
     fn short_name(&self) -> &'static str {
         "AROON"
     }
@@ -92,7 +98,7 @@ impl Indicator for AROON {
         &self.groups
     }
     // It's possible to apply smoothing on osc (EMA), for better indication
-    fn calculate(&self, data: &InputData, params: Value) -> Result<OutputData, IndicatorError> {
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
         let params: AROONParams = serde_json::from_value(params)
             .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
 
@@ -102,35 +108,32 @@ impl Indicator for AROON {
         let low = data.get_by_bar_field(&BarField::LOW).unwrap();
         let length = high.len();
         let period = params.period;
-        let mut up = Array1::<f64>::from_elem(length, f64::NAN);
-        let mut down = Array1::<f64>::from_elem(length, f64::NAN);
-        let mut osc = Array1::<f64>::from_elem(length, f64::NAN);
+        let [up, down, osc] = out.multi(["aroon_up", "aroon_down", "aroon_osc"], length);
+        up.fill(T::nan());
+        down.fill(T::nan());
+        osc.fill(T::nan());
+
+        // Ties go to the oldest bar, so a repeated extreme keeps ageing.
+        let mut highest = RollingExtremum::max(period).oldest_on_ties();
+        let mut lowest = RollingExtremum::min(period).oldest_on_ties();
+        for i in 0..length {
+            let high_max = highest.push(high[i]);
+            let low_min = lowest.push(low[i]);
+            if i + 1 < period {
+                continue;
+            }
 
-        // Iterate over the valid indices
-        for i in (period - 1)..length {
+            // Positions of the extremes within the window, 0 for its oldest bar; NaN if the
+            // window has no prices
             let start_index = i + 1 - period;
-            let end_index = i + 1;
-
-            // Extract the window slices
-            let high_window = high.slice(s![start_index..end_index]);
-            let low_window = low.slice(s![start_index..end_index]);
-
-            // Use ndarray-stats methods to find the index of the max and min
-            let high_max_index = high_window.argmax().unwrap();
-            let low_min_index = low_window.argmin().unwrap();
-
-            // Calculate Aroon Up and Aroon Down
-            up[i] = ((high_max_index + 1) as f64 / period as f64) * 100.0;
-            down[i] = ((low_min_index + 1) as f64 / period as f64) * 100.0;
+            let aroon = |extreme: Option<(usize, T)>| extreme
+                .map_or(T::nan(), |(index, _)| T::cast(((index - start_index + 1) as f64 / period as f64) * 100.0));
+            up[i] = aroon(high_max);
+            down[i] = aroon(low_min);
             osc[i] = up[i] - down[i];
         }
 
-        let mut output = HashMap::new();
-        output.insert("aroon_up", up);
-        output.insert("aroon_down", down);
-        output.insert("aroon_osc", osc);
-
-        Ok(OutputData::MultiSeries(output))
+        Ok(())
     }
 }
 
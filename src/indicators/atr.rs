@@ -1,24 +1,79 @@
 use std::collections::HashSet;
-use ndarray::{s, Array1};
+use ndarray::ArrayView1;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use rayon::prelude::*;
-use crate::indicators::utils::validate_parameter_within_data_length;
-use crate::models::data::{BarField, InputData, OutputData};
+use crate::indicators::utils::{calculate_session_true_range_into, calculate_true_range_into, true_range, validate_parameter_within_data_length};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
 use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
 use crate::models::indicator::{Indicator, IndicatorError};
+use crate::timeframe::calendar::TradingCalendar;
+use crate::timeframe::session::{OvernightGap, Session, TradingSessions};
 use crate::validation::validator::{IParameter, ParamRule, Validator};
 
 #[derive(Serialize, Deserialize)]
 pub struct ATRParams {
     #[serde(default = "default_period")]
     pub period: usize,
+    /// Session boundaries used by `overnight_gap`. Excluding the gap requires timestamps.
+    #[serde(default)]
+    pub session: Option<Session>,
+    /// Regular hours used by `overnight_gap` instead of `session`: bars outside them, and the
+    /// first bar of every session, ignore the previous close.
+    #[serde(default)]
+    pub calendar: Option<TradingCalendar>,
+    #[serde(default)]
+    pub overnight_gap: OvernightGap,
 }
 
 fn default_period() -> usize { 14 }
 
 impl IParameter for ATRParams {}
 
+/// Wilder's average true range over bars that arrive one at a time: the mean of the first
+/// `period` true ranges, then smoothed by `1 / period`. ATR runs it over whole series, and the
+/// ATR-based stops build on it.
+#[derive(Debug, Clone)]
+pub struct AverageTrueRange<T: Float = f64> {
+    period: usize,
+    previous_close: Option<T>,
+    seed: Vec<T>,
+    atr: Option<T>,
+}
+
+impl<T: Float> AverageTrueRange<T> {
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        if period == 0 {
+            return Err(IndicatorError::InvalidParameters("The ATR period must be positive".to_string()));
+        }
+        Ok(AverageTrueRange { period, previous_close: None, seed: Vec::with_capacity(period), atr: None })
+    }
+
+    /// Adds a bar and returns the ATR, once `period` bars have been added.
+    pub fn push(&mut self, high: T, low: T, close: T) -> Option<T> {
+        let previous_close = self.previous_close.replace(close);
+        self.push_true_range(true_range(high, low, previous_close))
+    }
+
+    /// Adds the true range of a bar, for callers that measure it themselves.
+    pub fn push_true_range(&mut self, true_range: T) -> Option<T> {
+        match self.atr {
+            Some(atr) => {
+                let period = T::cast(self.period as f64);
+                self.atr = Some((atr * (period - T::one()) + true_range) / period);
+            }
+            None => {
+                self.seed.push(true_range);
+                if self.seed.len() == self.period {
+                    self.atr = ArrayView1::from(&self.seed[..]).mean();
+                }
+            }
+        }
+        self.atr
+    }
+}
+
 pub struct ATR {
     groups: HashSet<Group>,
     validator: Validator,
@@ -30,7 +85,7 @@ fn create_validator() -> Validator {
         vec![
             ParamRule::Required("period"),
             ParamRule::PositiveInteger("period"),
-            ParamRule::Custom(Box::new(|value: &Value, data: &InputData| validate_parameter_within_data_length(value, data, "period", BarField::HIGH))),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::HIGH))),
         ],
     )
 }
@@ -78,7 +133,13 @@ impl ATR {
     }
 }
 
-impl Indicator for ATR {
+impl Default for ATR {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for ATR {
     fn short_name(&self) -> &'static str {
         "ATR"
     }
@@ -91,7 +152,7 @@ impl Indicator for ATR {
         &self.groups
     }
 
-    fn calculate(&self, data: &InputData, params: Value) -> Result<OutputData, IndicatorError> {
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
         let params: ATRParams = serde_json::from_value(params)
             .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
 
@@ -104,33 +165,38 @@ impl Indicator for ATR {
         let period = params.period;
 
         // Calculate True Range (TR)
-        let tr_vec: Vec<f64> = (1..length)
-            .into_par_iter()
-            .map(|i| {
-                let hl = high[i] - low[i];
-                let hpc = (high[i] - close[i - 1]).abs();
-                let lpc = (low[i] - close[i - 1]).abs();
-                hl.max(hpc).max(lpc)
-            })
-            .collect();
-
-        // Combine first TR value
-        let mut tr = Array1::<f64>::zeros(length);
-        tr[0] = high[0] - low[0];
-        tr.slice_mut(s![1..]).assign(&Array1::from(tr_vec));
+        let [tr] = scratch.arrays(length);
+        let sessions: Option<&dyn TradingSessions> = match (&params.session, &params.calendar) {
+            (Some(_), Some(_)) => {
+                return Err(IndicatorError::InvalidParameters("Only one of 'session' and 'calendar' can be set".to_string()));
+            }
+            (Some(session), None) => Some(session),
+            (None, Some(calendar)) => Some(calendar),
+            (None, None) => None,
+        };
+        match (params.overnight_gap, sessions) {
+            (OvernightGap::Include, _) => calculate_true_range_into(high, low, close, tr)?,
+            (OvernightGap::Exclude, Some(sessions)) => {
+                let timestamps = data.timestamp
+                    .ok_or_else(|| IndicatorError::InvalidInput("Excluding overnight gaps requires timestamps.".to_string()))?;
+                if timestamps.len() != length {
+                    return Err(IndicatorError::InvalidInput("Input data series of the bars must have the same length.".to_string()));
+                }
+                calculate_session_true_range_into(high, low, close, &sessions.first_bars(timestamps), tr)?;
+            }
+            (OvernightGap::Exclude, None) => {
+                return Err(IndicatorError::InvalidParameters("Excluding overnight gaps requires a 'session' or 'calendar'".to_string()));
+            }
+        }
 
         // Calculate ATR
-        let mut atr = Array1::<f64>::from_elem(length, f64::NAN);
-        // Initial ATR value as the mean of the first 'period' TR values
-        atr[period - 1] = tr.slice(s![0..period]).mean().unwrap();
-
-        // Subsequent ATR values
-        let period_f64 = period as f64;
-        for i in period..length {
-            atr[i] = (atr[i - 1] * (period_f64 - 1.0) + tr[i]) / period_f64;
+        let atr = out.single(length);
+        let mut average = AverageTrueRange::new(period)?;
+        for i in 0..length {
+            atr[i] = average.push_true_range(tr[i]).unwrap_or(T::nan());
         }
 
-        Ok(OutputData::SingleSeries(atr))
+        Ok(())
     }
 }
 
@@ -282,4 +348,111 @@ mod test {
             panic!("Unexpected output format");
         }
     }
+
+    #[test]
+    fn test_atr_calculate_into_reuses_buffers() {
+        let high = array![48.70, 48.72, 48.90, 48.87, 48.82];
+        let low = array![47.79, 48.14, 48.39, 48.37, 48.24];
+        let close = array![48.16, 48.61, 48.75, 48.63, 48.74];
+
+        let input_data = InputData {
+            open: None,
+            high: Some(high.clone()),
+            low: Some(low.clone()),
+            close: Some(close.clone()),
+            volume: None,
+        };
+
+        let indicator = ATR::new();
+        let mut out = OutputBuffers::new();
+        let mut scratch = Workspace::new();
+
+        indicator.calculate_into(&input_data, json!({ "period": 3 }), &mut out, &mut scratch).unwrap();
+        let first_pointer = out.as_single().unwrap().as_ptr();
+        indicator.calculate_into(&input_data, json!({ "period": 3 }), &mut out, &mut scratch).unwrap();
+        assert_eq!(out.as_single().unwrap().as_ptr(), first_pointer);
+
+        if let OutputData::SingleSeries(atr_values) = indicator.calculate(&input_data, json!({ "period": 3 })).unwrap() {
+            let reused = out.as_single().unwrap();
+            for i in 2..atr_values.len() {
+                assert_eq!(reused[i], atr_values[i], "Mismatch at index {}", i);
+            }
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+
+    #[test]
+    fn test_atr_excludes_overnight_gap() {
+        // Two bars per day; the second day opens 10 points higher.
+        let day = 86_400_000;
+        let input_data = InputData {
+            open: None,
+            high: Some(array![11.0, 11.0, 21.0, 21.0]),
+            low: Some(array![10.0, 10.0, 20.0, 20.0]),
+            close: Some(array![10.5, 10.5, 20.5, 20.5]),
+            volume: None,
+        }.with_timestamps(array![0, 3_600_000, day, day + 3_600_000]);
+
+        let indicator = ATR::new();
+        let with_gap = indicator.calculate_view(&input_data.view(), json!({ "period": 2 })).unwrap();
+        let without_gap = indicator.calculate_view(&input_data.view(), json!({ "period": 2, "session": {}, "overnight_gap": "exclude" })).unwrap();
+
+        assert_eq!(with_gap.series("real").unwrap()[2], (1.0 + 10.5) / 2.0);
+        assert_eq!(without_gap.series("real").unwrap().to_vec()[1..], [1.0, 1.0, 1.0]);
+        assert!(indicator.calculate_view(&input_data.view(), json!({ "period": 2, "overnight_gap": "exclude" })).is_err());
+    }
+
+    #[test]
+    fn test_atr_excludes_gaps_outside_regular_hours() {
+        // 09:30, 10:30 and 13:30 New York time (UTC-5) on 2024-07-03; the last bar gaps up 10.
+        let open = 19_907 * 86_400_000 + 52_200_000;
+        let input_data = InputData {
+            open: None,
+            high: Some(array![11.0, 11.0, 21.0]),
+            low: Some(array![10.0, 10.0, 20.0]),
+            close: Some(array![10.5, 10.5, 20.5]),
+            volume: None,
+        }.with_timestamps(array![open, open + 3_600_000, open + 4 * 3_600_000]);
+        let params = |half_days: Value| json!({
+            "period": 2,
+            "overnight_gap": "exclude",
+            "calendar": { "session": { "utc_offset_minutes": -300, "start_minute": 570 }, "close_minute": 960, "half_days": half_days },
+        });
+
+        let regular = ATR::new().calculate_view(&input_data.view(), params(json!({}))).unwrap();
+        let half_day = ATR::new().calculate_view(&input_data.view(), params(json!({ "2024-07-03": 780 }))).unwrap();
+
+        // On a regular day the 13:30 bar is in session, so its gap counts; after a 13:00 early
+        // close it is after hours and its true range is only its own range.
+        assert_eq!(regular.series("real").unwrap()[2], (1.0 + 10.5) / 2.0);
+        assert_eq!(half_day.series("real").unwrap()[2], 1.0);
+        let both = json!({ "period": 2, "overnight_gap": "exclude", "session": {}, "calendar": { "close_minute": 960 } });
+        assert!(ATR::new().calculate_view(&input_data.view(), both).is_err());
+    }
+
+    #[test]
+    fn test_atr_stream_matches_batch() {
+        let high = array![48.70, 48.72, 48.90, 48.87, 48.82, 49.05, 49.20];
+        let low = array![47.79, 48.14, 48.39, 48.37, 48.24, 48.64, 48.94];
+        let close = array![48.16, 48.61, 48.75, 48.63, 48.74, 49.03, 49.07];
+        let input_data = InputData {
+            open: None,
+            high: Some(high.clone()),
+            low: Some(low.clone()),
+            close: Some(close.clone()),
+            volume: None,
+        };
+        let batch = ATR::new().calculate(&input_data, json!({ "period": 3 })).unwrap();
+
+        let mut stream = AverageTrueRange::new(3).unwrap();
+        let live: Vec<Option<f64>> = (0..high.len()).map(|i| stream.push(high[i], low[i], close[i])).collect();
+
+        let batch = batch.series("real").unwrap();
+        assert_eq!(live[..2], [None, None]);
+        for i in 2..high.len() {
+            assert_eq!(live[i], Some(batch[i]), "Mismatch at index {}", i);
+        }
+        assert!(AverageTrueRange::<f64>::new(0).is_err());
+    }
 }
@@ -0,0 +1,218 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::atr::AverageTrueRange;
+use crate::indicators::utils::validate_parameter_within_data_length;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+/// A stop level and the side of the trade it protects.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrailingStop<T: Float = f64> {
+    pub stop: T,
+    pub is_long: bool,
+}
+
+impl<T: Float> TrailingStop<T> {
+    /// 1 while long, -1 while short, as reported in `direction` output series.
+    pub fn direction(&self) -> T {
+        if self.is_long { T::one() } else { -T::one() }
+    }
+}
+
+/// Stop `multiplier` ATRs from the close that only moves in the trade's favour, over bars that
+/// arrive one at a time. It starts long, and flips sides when the close crosses it.
+#[derive(Debug, Clone)]
+pub struct AtrTrailingStop<T: Float = f64> {
+    atr: AverageTrueRange<T>,
+    multiplier: T,
+    stop: Option<TrailingStop<T>>,
+}
+
+impl<T: Float> AtrTrailingStop<T> {
+    pub fn new(period: usize, multiplier: T) -> Result<Self, IndicatorError> {
+        if multiplier <= T::zero() {
+            return Err(IndicatorError::InvalidParameters("The ATR multiplier must be positive".to_string()));
+        }
+        Ok(AtrTrailingStop { atr: AverageTrueRange::new(period)?, multiplier, stop: None })
+    }
+
+    /// Adds a bar and returns the stop for the next one, once the ATR is available.
+    pub fn push(&mut self, high: T, low: T, close: T) -> Option<TrailingStop<T>> {
+        let offset = self.multiplier * self.atr.push(high, low, close)?;
+        let stop = match self.stop {
+            Some(previous) if previous.is_long && close >= previous.stop => TrailingStop { stop: previous.stop.max(close - offset), is_long: true },
+            Some(previous) if !previous.is_long && close <= previous.stop => TrailingStop { stop: previous.stop.min(close + offset), is_long: false },
+            Some(previous) if previous.is_long => TrailingStop { stop: close + offset, is_long: false },
+            _ => TrailingStop { stop: close - offset, is_long: true },
+        };
+        self.stop = Some(stop);
+        Some(stop)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ATRStopParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+    /// Distance of the stop from the close, in ATRs.
+    #[serde(default = "default_multiplier")]
+    pub multiplier: f64,
+}
+
+fn default_period() -> usize { 14 }
+fn default_multiplier() -> f64 { 3.0 }
+
+impl IParameter for ATRStopParams {}
+
+/// ATR trailing stop: the `stop` of `AtrTrailingStop` on every bar, with its `direction`.
+pub struct ATRStop {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::HIGH, BarField::LOW, BarField::CLOSE],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::Required("multiplier"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::PositiveNumber("multiplier"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::CLOSE))),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::TrendIdentification));
+    groups.insert(Group::UseCase(UseCase::ReversalDetection));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Averaging));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    groups.insert(Group::OutputFormat(OutputFormat::MultiLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Absolute));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Basic));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Volatile));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Exponential));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Differential));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Crossovers));
+    groups
+}
+
+impl ATRStop {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for ATRStop {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for ATRStop {
+    fn short_name(&self) -> &'static str {
+        "ATRSTOP"
+    }
+
+    fn name(&self) -> &'static str {
+        "ATR Trailing Stop"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: ATRStopParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let mut trailing = AtrTrailingStop::new(params.period, T::cast(params.multiplier))?;
+        let [stop, direction] = out.multi(["stop", "direction"], close.len());
+        for i in 0..close.len() {
+            let bar = trailing.push(high[i], low[i], close[i]);
+            stop[i] = bar.map_or(T::nan(), |bar| bar.stop);
+            direction[i] = bar.map_or(T::nan(), |bar| bar.direction());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_atrstop_trails_and_flips() {
+        // With a one-bar ATR the stop sits one true range from the close: 2, except 3 on the gap
+        // down of bar 4.
+        let input_data = InputData {
+            open: None,
+            high: Some(array![11.0, 12.0, 13.0, 12.0, 10.0, 9.0]),
+            low: Some(array![9.0, 10.0, 11.0, 10.0, 8.0, 7.0]),
+            close: Some(array![10.0, 11.0, 12.0, 11.0, 9.0, 8.0]),
+            volume: None,
+        };
+
+        let result = ATRStop::new().calculate(&input_data, json!({ "period": 1, "multiplier": 1.0 })).unwrap();
+
+        if let OutputData::MultiSeries(series) = result {
+            // Rises with the close, holds when it falls back, then flips short above it.
+            assert_eq!(series["stop"], array![8.0, 9.0, 10.0, 10.0, 12.0, 10.0]);
+            assert_eq!(series["direction"], array![1.0, 1.0, 1.0, 1.0, -1.0, -1.0]);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+
+    #[test]
+    fn test_atrstop_stream_matches_batch() {
+        let high = array![48.70, 48.72, 48.90, 48.87, 48.82, 49.05, 49.20, 48.10, 47.90, 48.60];
+        let low = array![47.79, 48.14, 48.39, 48.37, 48.24, 48.64, 48.94, 47.20, 47.10, 47.80];
+        let close = array![48.16, 48.61, 48.75, 48.63, 48.74, 49.03, 49.07, 47.35, 47.80, 48.55];
+        let input_data = InputData {
+            open: None,
+            high: Some(high.clone()),
+            low: Some(low.clone()),
+            close: Some(close.clone()),
+            volume: None,
+        };
+        let batch = ATRStop::new().calculate(&input_data, json!({ "period": 3, "multiplier": 1.5 })).unwrap();
+
+        let mut stream = AtrTrailingStop::new(3, 1.5).unwrap();
+        let live: Vec<Option<TrailingStop>> = (0..high.len()).map(|i| stream.push(high[i], low[i], close[i])).collect();
+
+        let (stop, direction) = (batch.series("stop").unwrap(), batch.series("direction").unwrap());
+        assert_eq!(live[..2], [None, None]);
+        for i in 2..high.len() {
+            let bar = live[i].unwrap();
+            assert_eq!((bar.stop, bar.direction()), (stop[i], direction[i]), "Mismatch at index {}", i);
+        }
+        assert!(direction.iter().any(|&side| side < 0.0));
+        assert!(AtrTrailingStop::new(3, 0.0).is_err());
+    }
+}
@@ -1,6 +1,8 @@
 use std::collections::HashSet;
 use serde_json::Value;
-use crate::models::data::{BarField, InputData, OutputData};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, GenericInputDataView};
+use crate::models::float::Float;
 use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
 use crate::models::indicator::{Indicator, IndicatorError};
 use crate::validation::validator::Validator;
@@ -49,7 +51,13 @@ impl AvgPrice {
     }
 }
 
-impl Indicator for AvgPrice {
+impl Default for AvgPrice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for AvgPrice {
     fn short_name(&self) -> &'static str {
         "AVGPRICE"
     }
@@ -62,16 +70,19 @@ impl Indicator for AvgPrice {
         &self.groups
     }
 
-    fn calculate(&self, data: &InputData, params: Value) -> Result<OutputData, IndicatorError> {
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, _params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
         self.validator.validate_data(data)?;
 
         let open = data.get_by_bar_field(&BarField::OPEN).unwrap();
         let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
         let low = data.get_by_bar_field(&BarField::LOW).unwrap();
         let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
-        let sum = open + high + low + close;
-        let avg_price = sum / 4.0;
-        Ok(OutputData::SingleSeries(avg_price))
+        let avg_price = out.single(open.len());
+        for i in 0..open.len() {
+            let sum = open[i] + high[i] + low[i] + close[i];
+            avg_price[i] = sum / T::cast(4.0);
+        }
+        Ok(())
     }
 }
 
@@ -79,6 +90,7 @@ impl Indicator for AvgPrice {
 mod test {
     use ndarray::array;
     use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
     use super::*;
 
     #[test]
@@ -1,10 +1,10 @@
-use std::collections::{HashMap, HashSet};
-use ndarray::Array1;
-use ndarray::prelude::*;
+use std::collections::HashSet;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use crate::indicators::utils::cumulative_sum;
-use crate::models::data::{BarField, InputData, OutputData};
+use crate::indicators::utils::{rolling_mean_into, rolling_population_variance_into};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, GenericInputDataView};
+use crate::models::float::Float;
 use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
 use crate::models::indicator::{Indicator, IndicatorError};
 use crate::validation::validator::{IParameter, ParamRule, Validator};
@@ -72,7 +72,13 @@ impl BBands {
     }
 }
 
-impl Indicator for BBands {
+impl Default for BBands {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for BBands {
     fn short_name(&self) -> &'static str {
         "BBANDS"
     }
@@ -85,7 +91,7 @@ impl Indicator for BBands {
         &self.groups
     }
 
-    fn calculate(&self, data: &InputData, params: Value) -> Result<OutputData, IndicatorError> {
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
         let params: BBandsParams = serde_json::from_value(params)
             .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
 
@@ -93,58 +99,29 @@ impl Indicator for BBands {
 
         let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
         let period = params.period;
-        let std_dev_multiplier = params.std_dev_multiplier;
+        let std_dev_multiplier = T::cast(params.std_dev_multiplier);
         let length = close.len();
 
-        // Calculate moving average (MA) using vectorized operations
-        let mut ma = Array1::<f64>::from_elem(length, f64::NAN);
-
-        // Calculate standard deviation (SD)
-        let mut sd = Array1::<f64>::from_elem(length, f64::NAN);
-
-        // Precompute cumulative sums for mean and variance calculations
-        let cumsum = cumulative_sum(close);
-        let cumsum_sq = cumulative_sum(&(close * close));
-
-        for i in (period - 1)..length {
-            let start = i + 1 - period;
-            let sum = if start == 0 {
-                cumsum[i]
-            } else {
-                cumsum[i] - cumsum[start - 1]
-            };
-            let sum_sq = if start == 0 {
-                cumsum_sq[i]
-            } else {
-                cumsum_sq[i] - cumsum_sq[start - 1]
-            };
-
-            let mean = sum / period as f64;
-            ma[i] = mean;
-
-            let variance = (sum_sq - 2.0 * mean * sum + mean * mean * period as f64) / period as f64;
-            let std_dev = variance.sqrt();
-            sd[i] = std_dev;
-        }
-
-        // Calculate upper and lower bands
-        let upper_band = &ma + &(&sd * std_dev_multiplier);
-        let lower_band = &ma - &(&sd * std_dev_multiplier);
+        let [ma, upper_band, lower_band] = out.multi(["middle_band", "upper_band", "lower_band"], length);
+        rolling_mean_into(close, 0, period, ma);
+        // The variance goes into the lower band first; both bands are derived from it below.
+        rolling_population_variance_into(close, 0, period, lower_band);
 
-        // Prepare output data
-        let mut output = HashMap::new();
-        output.insert("middle_band", ma);
-        output.insert("upper_band", upper_band);
-        output.insert("lower_band", lower_band);
+        for i in 0..length {
+            let std_dev = lower_band[i].sqrt();
+            upper_band[i] = ma[i] + std_dev * std_dev_multiplier;
+            lower_band[i] = ma[i] - std_dev * std_dev_multiplier;
+        }
 
-        Ok(OutputData::MultiSeries(output))
+        Ok(())
     }
 }
 
-
 #[cfg(test)]
 mod test {
+    use ndarray::array;
     use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
     use super::*;
 
     #[test]
@@ -553,4 +530,41 @@ mod test {
             panic!("Unexpected output format");
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_bollinger_bands_calculate_into_matches_calculate() {
+        let close = array![
+        22.27, 22.19, 22.08, 22.17, 22.18,
+        22.13, 22.23, 22.43, 22.24, 22.29,
+        22.15, 22.39, 22.38, 22.61, 23.36,
+        24.05, 23.75, 23.83, 23.95, 23.63,
+        23.82, 23.87, 23.65, 23.19, 23.10,
+        23.33, 22.68, 23.10, 22.40, 22.17
+    ];
+
+        let input_data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(close.clone()),
+            volume: None,
+        };
+
+        let indicator = BBands::new();
+        let params = json!({ "period": 20, "std_dev_multiplier": 2.0 });
+        let mut out = OutputBuffers::new();
+        let mut scratch = Workspace::new();
+        indicator.calculate_into(&input_data, params.clone(), &mut out, &mut scratch).unwrap();
+
+        if let OutputData::MultiSeries(output) = indicator.calculate(&input_data, params).unwrap() {
+            for (name, values) in output {
+                let reused = out.get(name).unwrap();
+                for i in 19..close.len() {
+                    assert_eq!(reused[i], values[i], "Mismatch in {} at index {}", name, i);
+                }
+            }
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::HashSet;
+use serde_json::Value;
+use crate::indicators::bbands::{BBands, BBandsParams};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{ParamRule, Validator};
+
+/// Bollinger Bands plus where the close sits within them, `percent_b` (0 at the lower band, 1 at
+/// the upper, 0.5 when the bands meet), and their `bandwidth` relative to the middle band. Takes
+/// the same params as BBANDS.
+pub struct BBandsPercentB {
+    groups: HashSet<Group>,
+    validator: Validator,
+    bbands: BBands,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::CLOSE],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::Required("std_dev_multiplier"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::PositiveNumber("std_dev_multiplier"),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::VolatilityMeasurement));
+    groups.insert(Group::UseCase(UseCase::ReversalDetection));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Averaging));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::StatisticalMethods));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::RatioBased));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    groups.insert(Group::OutputFormat(OutputFormat::Band));
+    groups.insert(Group::OutputFormat(OutputFormat::MultiLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Percentage));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Intermediate));
+    groups.insert(Group::MarketSuitability(MarketSuitability::RangeBound));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Volatile));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::SimpleAverage));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Statistical));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Ratio));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::PeakThroughLevels));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::ThresholdLevels));
+    groups
+}
+
+impl BBandsPercentB {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator, bbands: BBands::new() }
+    }
+}
+
+impl Default for BBandsPercentB {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for BBandsPercentB {
+    fn short_name(&self) -> &'static str {
+        "BBANDSPB"
+    }
+
+    fn name(&self) -> &'static str {
+        "Bollinger %B and Bandwidth"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let parsed: BBandsParams = serde_json::from_value(params.clone())
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &parsed)?;
+
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let length = close.len();
+        self.bbands.calculate_view_into(data, params, out, scratch)?;
+        // BBANDS leaves its bands in the first three buffers, in this order.
+        let [middle_band, upper_band, lower_band, percent_b, bandwidth] = out.multi(["middle_band", "upper_band", "lower_band", "percent_b", "bandwidth"], length);
+        for i in 0..length {
+            let width = upper_band[i] - lower_band[i];
+            percent_b[i] = if width == T::zero() {
+                T::cast(0.5)
+            } else {
+                (close[i] - lower_band[i]) / width
+            };
+            bandwidth[i] = width / middle_band[i];
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_percent_b_and_bandwidth() {
+        let input_data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(array![10.0, 10.0, 12.0, 8.0, 10.0]),
+            volume: None,
+        };
+        let params = json!({ "period": 2, "std_dev_multiplier": 1.0 });
+
+        let bands = BBands::new().calculate(&input_data, params.clone()).unwrap();
+        let result = BBandsPercentB::new().calculate(&input_data, params).unwrap();
+
+        if let (OutputData::MultiSeries(bands), OutputData::MultiSeries(result)) = (bands, result) {
+            for name in ["middle_band", "upper_band", "lower_band"] {
+                assert_eq!(result[name].slice(ndarray::s![1..]), bands[name].slice(ndarray::s![1..]));
+            }
+            assert!(result["percent_b"][0].is_nan() && result["bandwidth"][0].is_nan());
+            // Flat window: the bands meet.
+            assert_eq!(result["percent_b"][1], 0.5);
+            assert_eq!(result["bandwidth"][1], 0.0);
+            // Window [10, 12]: bands at 10 and 12 around 11.
+            assert_eq!(result["percent_b"][2], 1.0);
+            assert_eq!(result["bandwidth"][2], 2.0 / 11.0);
+            assert_eq!(result["percent_b"][3], 0.0);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+use serde_json::Value;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::Validator;
+
+/// Balance of Power: `(close - open) / (high - low)`, 0 on bars without a range.
+pub struct BOP {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::MomentumDetection));
+    groups.insert(Group::UseCase(UseCase::MarketStrengthMeasurement));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::RatioBased));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Coincident));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Histogram));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Basic));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::MarketSuitability(MarketSuitability::RangeBound));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Scalping));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Raw));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Ratio));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::ThresholdLevels));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Divergence));
+    groups
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::OPEN, BarField::HIGH, BarField::LOW, BarField::CLOSE],
+        vec![]
+    )
+}
+
+impl BOP {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for BOP {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for BOP {
+    fn short_name(&self) -> &'static str {
+        "BOP"
+    }
+
+    fn name(&self) -> &'static str {
+        "Balance of Power"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, _params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        self.validator.validate_data(data)?;
+
+        let open = data.get_by_bar_field(&BarField::OPEN).unwrap();
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+        let bop = out.single(close.len());
+        for i in 0..close.len() {
+            let range = high[i] - low[i];
+            bop[i] = if range > T::zero() { (close[i] - open[i]) / range } else { T::zero() };
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_bop_expected_values() {
+        let input_data = InputData {
+            open: Some(array![10.0, 12.0, 11.0]),
+            high: Some(array![12.0, 12.5, 11.0]),
+            low: Some(array![9.0, 10.5, 11.0]),
+            close: Some(array![11.5, 11.0, 11.0]),
+            volume: None,
+        };
+
+        let result = BOP::new().calculate(&input_data, json!({})).unwrap();
+
+        if let OutputData::SingleSeries(bop) = result {
+            assert_eq!(bop, array![0.5, -0.5, 0.0]);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
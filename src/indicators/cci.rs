@@ -0,0 +1,159 @@
+use std::collections::HashSet;
+use ndarray::s;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::validate_parameter_within_data_length;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct CCIParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+}
+
+fn default_period() -> usize { 14 }
+
+impl IParameter for CCIParams {}
+
+/// Commodity Channel Index: distance of the typical price from its `period`-bar mean, in units of
+/// 0.015 mean absolute deviations. 0 when either is 0.
+pub struct CCI {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::HIGH, BarField::LOW, BarField::CLOSE],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::CLOSE))),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::MomentumDetection));
+    groups.insert(Group::UseCase(UseCase::ReversalDetection));
+    groups.insert(Group::UseCase(UseCase::CycleAnalysis));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::StatisticalMethods));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Oscillation));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Leading));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Absolute));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Intermediate));
+    groups.insert(Group::MarketSuitability(MarketSuitability::RangeBound));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::SimpleAverage));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Statistical));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::PeakThroughLevels));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Divergence));
+    groups
+}
+
+impl CCI {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for CCI {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for CCI {
+    fn short_name(&self) -> &'static str {
+        "CCI"
+    }
+
+    fn name(&self) -> &'static str {
+        "Commodity Channel Index"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: CCIParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let length = close.len();
+        let period = params.period;
+        let period_t = T::cast(period as f64);
+        let [typical] = scratch.arrays(length);
+        for i in 0..length {
+            typical[i] = (high[i] + low[i] + close[i]) / T::cast(3.0);
+        }
+
+        let cci = out.single(length);
+        cci.fill(T::nan());
+        for i in (period - 1)..length {
+            let window = typical.slice(s![i + 1 - period..=i]);
+            let average = window.sum() / period_t;
+            let deviation = window.fold(T::zero(), |total, &value| total + (value - average).abs());
+            let difference = typical[i] - average;
+            cci[i] = if difference != T::zero() && deviation != T::zero() {
+                difference / (T::cast(0.015) * (deviation / period_t))
+            } else {
+                T::zero()
+            };
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_cci_expected_values() {
+        let input_data = InputData {
+            open: None,
+            high: Some(array![11.0, 13.0, 12.0, 12.0]),
+            low: Some(array![9.0, 11.0, 10.0, 10.0]),
+            close: Some(array![10.0, 12.0, 11.0, 11.0]),
+            volume: None,
+        };
+
+        let result = CCI::new().calculate(&input_data, json!({ "period": 2 })).unwrap();
+
+        if let OutputData::SingleSeries(cci) = result {
+            assert!(cci[0].is_nan());
+            // Typical prices 10, 12: 1 above the mean, with a mean deviation of 1.
+            assert_eq!(cci[1], 1.0 / 0.015);
+            assert_eq!(cci[2], -0.5 / (0.015 * 0.5));
+            // No deviation at all gives 0.
+            assert_eq!(cci[3], 0.0);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::atr::AverageTrueRange;
+use crate::indicators::utils::{validate_parameter_within_data_length, RollingExtremum};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+/// Chandelier Exit over bars that arrive one at a time: the long stop hangs `multiplier` ATRs below
+/// the highest high of the last `period` bars, the short stop as far above the lowest low.
+#[derive(Debug, Clone)]
+pub struct ChandelierExit<T: Float = f64> {
+    atr: AverageTrueRange<T>,
+    multiplier: T,
+    highest: RollingExtremum<T>,
+    lowest: RollingExtremum<T>,
+}
+
+impl<T: Float> ChandelierExit<T> {
+    pub fn new(period: usize, multiplier: T) -> Result<Self, IndicatorError> {
+        if multiplier <= T::zero() {
+            return Err(IndicatorError::InvalidParameters("The ATR multiplier must be positive".to_string()));
+        }
+        Ok(ChandelierExit { atr: AverageTrueRange::new(period)?, multiplier, highest: RollingExtremum::max(period), lowest: RollingExtremum::min(period) })
+    }
+
+    /// Adds a bar and returns the long and short stops, once `period` bars have been added.
+    pub fn push(&mut self, high: T, low: T, close: T) -> Option<(T, T)> {
+        let highest = self.highest.push_value(high);
+        let lowest = self.lowest.push_value(low);
+        let offset = self.multiplier * self.atr.push(high, low, close)?;
+        Some((highest - offset, lowest + offset))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ChandelierParams {
+    /// Bars of the highest high, lowest low and ATR.
+    #[serde(default = "default_period")]
+    pub period: usize,
+    /// Distance of the stops from the extremes, in ATRs.
+    #[serde(default = "default_multiplier")]
+    pub multiplier: f64,
+}
+
+fn default_period() -> usize { 22 }
+fn default_multiplier() -> f64 { 3.0 }
+
+impl IParameter for ChandelierParams {}
+
+/// Chandelier Exit: the `long_stop` and `short_stop` of `ChandelierExit` on every bar.
+pub struct Chandelier {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::HIGH, BarField::LOW, BarField::CLOSE],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::Required("multiplier"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::PositiveNumber("multiplier"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::CLOSE))),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::TrendIdentification));
+    groups.insert(Group::UseCase(UseCase::VolatilityMeasurement));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::StatisticalMethods));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    groups.insert(Group::OutputFormat(OutputFormat::MultiLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Absolute));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Long));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Intermediate));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Volatile));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Positional));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Exponential));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Statistical));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Crossovers));
+    groups
+}
+
+impl Chandelier {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for Chandelier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for Chandelier {
+    fn short_name(&self) -> &'static str {
+        "CHANDELIER"
+    }
+
+    fn name(&self) -> &'static str {
+        "Chandelier Exit"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: ChandelierParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let mut exit = ChandelierExit::new(params.period, T::cast(params.multiplier))?;
+        let [long_stop, short_stop] = out.multi(["long_stop", "short_stop"], close.len());
+        for i in 0..close.len() {
+            (long_stop[i], short_stop[i]) = exit.push(high[i], low[i], close[i]).unwrap_or((T::nan(), T::nan()));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_chandelier_expected_values() {
+        let input_data = InputData {
+            open: None,
+            high: Some(array![11.0, 12.0, 13.0, 12.0]),
+            low: Some(array![9.0, 10.0, 11.0, 10.0]),
+            close: Some(array![10.0, 11.0, 12.0, 11.0]),
+            volume: None,
+        };
+
+        let result = Chandelier::new().calculate(&input_data, json!({ "period": 2, "multiplier": 1.5 })).unwrap();
+
+        if let OutputData::MultiSeries(series) = result {
+            // Every true range is 2, so the stops are 3 from the two-bar extremes.
+            assert!(series["long_stop"][0].is_nan() && series["short_stop"][0].is_nan());
+            assert_eq!(series["long_stop"].slice(ndarray::s![1..]), array![9.0, 10.0, 10.0]);
+            assert_eq!(series["short_stop"].slice(ndarray::s![1..]), array![12.0, 13.0, 13.0]);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+
+    #[test]
+    fn test_chandelier_stream_matches_batch() {
+        let high = array![48.70, 48.72, 48.90, 48.87, 48.82, 49.05, 49.20, 48.10, 47.90, 48.60];
+        let low = array![47.79, 48.14, 48.39, 48.37, 48.24, 48.64, 48.94, 47.20, 47.10, 47.80];
+        let close = array![48.16, 48.61, 48.75, 48.63, 48.74, 49.03, 49.07, 47.35, 47.80, 48.55];
+        let input_data = InputData {
+            open: None,
+            high: Some(high.clone()),
+            low: Some(low.clone()),
+            close: Some(close.clone()),
+            volume: None,
+        };
+        let batch = Chandelier::new().calculate(&input_data, json!({ "period": 3 })).unwrap();
+
+        let mut stream = ChandelierExit::new(3, 3.0).unwrap();
+        let live: Vec<Option<(f64, f64)>> = (0..high.len()).map(|i| stream.push(high[i], low[i], close[i])).collect();
+
+        let (long_stop, short_stop) = (batch.series("long_stop").unwrap(), batch.series("short_stop").unwrap());
+        assert_eq!(live[..2], [None, None]);
+        for i in 2..high.len() {
+            assert_eq!(live[i], Some((long_stop[i], short_stop[i])), "Mismatch at index {}", i);
+        }
+    }
+}
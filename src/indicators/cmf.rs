@@ -0,0 +1,154 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::{money_flow_multiplier, validate_parameter_within_data_length};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct CMFParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+}
+
+fn default_period() -> usize { 20 }
+
+impl IParameter for CMFParams {}
+
+pub struct CMF {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::HIGH, BarField::LOW, BarField::CLOSE, BarField::VOLUME],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::HIGH))),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::VolumeConfirmation));
+    groups.insert(Group::UseCase(UseCase::MarketStrengthMeasurement));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::VolumeWeighted));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::RatioBased));
+    groups.insert(Group::DataInputType(DataInputType::PriceVolumeCombined));
+    groups.insert(Group::SignalType(SignalType::Coincident));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Histogram));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Intermediate));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::MarketSuitability(MarketSuitability::RangeBound));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::SimpleAverage));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Ratio));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::ThresholdLevels));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Divergence));
+    groups
+}
+
+impl CMF {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for CMF {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for CMF {
+    fn short_name(&self) -> &'static str {
+        "CMF"
+    }
+
+    fn name(&self) -> &'static str {
+        "Chaikin Money Flow"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: CMFParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+        let volume = data.get_by_bar_field(&BarField::VOLUME).unwrap();
+
+        let length = high.len();
+        let period = params.period;
+        let [money_flow_volume] = scratch.arrays(length);
+        for i in 0..length {
+            money_flow_volume[i] = money_flow_multiplier(high[i], low[i], close[i]) * volume[i];
+        }
+
+        let cmf = out.single(length);
+        cmf.fill(T::nan());
+        let (mut flow_sum, mut volume_sum) = (T::zero(), T::zero());
+        for i in 0..length {
+            flow_sum += money_flow_volume[i];
+            volume_sum += volume[i];
+            if i >= period {
+                flow_sum -= money_flow_volume[i - period];
+                volume_sum -= volume[i - period];
+            }
+            if i + 1 >= period {
+                cmf[i] = if volume_sum == T::zero() { T::zero() } else { flow_sum / volume_sum };
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_cmf_expected_value() {
+        let input_data = InputData {
+            open: None,
+            high: Some(array![12.0, 11.0, 12.0]),
+            low: Some(array![10.0, 11.0, 10.0]),
+            close: Some(array![12.0, 11.0, 10.5]),
+            volume: Some(array![100.0, 300.0, 200.0]),
+        };
+
+        let result = CMF::new().calculate(&input_data, json!({ "period": 2 })).unwrap();
+
+        if let OutputData::SingleSeries(cmf_values) = result {
+            // Multipliers 1, 0 (high == low) and -0.5.
+            assert!(cmf_values[0].is_nan());
+            assert_eq!(cmf_values[1], 100.0 / 400.0);
+            assert_eq!(cmf_values[2], -100.0 / 500.0);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
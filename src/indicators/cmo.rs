@@ -0,0 +1,160 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::validate_parameter_within_data_length;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct CMOParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+}
+
+fn default_period() -> usize { 14 }
+
+impl IParameter for CMOParams {}
+
+/// Chande Momentum Oscillator: `100 * (gains - losses) / (gains + losses)`, with gains and losses
+/// of the close Wilder-smoothed over `period` bars as TA-Lib does. 0 when the close has not moved.
+pub struct CMO {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::CLOSE],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::CLOSE))),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::MomentumDetection));
+    groups.insert(Group::UseCase(UseCase::ReversalDetection));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Oscillation));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::RatioBased));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Leading));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Percentage));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Intermediate));
+    groups.insert(Group::MarketSuitability(MarketSuitability::RangeBound));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Exponential));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Ratio));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::PeakThroughLevels));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Divergence));
+    groups
+}
+
+impl CMO {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for CMO {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for CMO {
+    fn short_name(&self) -> &'static str {
+        "CMO"
+    }
+
+    fn name(&self) -> &'static str {
+        "Chande Momentum Oscillator"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: CMOParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let period = params.period;
+        let period_t = T::cast(period as f64);
+        let cmo = out.single(close.len());
+        cmo.fill(T::nan());
+
+        let (mut gain, mut loss) = (T::zero(), T::zero());
+        for i in 1..close.len() {
+            if i > period {
+                gain *= period_t - T::one();
+                loss *= period_t - T::one();
+            }
+            let change = close[i] - close[i - 1];
+            if change < T::zero() {
+                loss -= change;
+            } else {
+                gain += change;
+            }
+            if i < period {
+                continue;
+            }
+            gain /= period_t;
+            loss /= period_t;
+
+            let total = gain + loss;
+            cmo[i] = if total != T::zero() { T::cast(100.0) * ((gain - loss) / total) } else { T::zero() };
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_cmo_expected_values() {
+        let input_data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(array![10.0, 13.0, 12.0, 12.0, 14.0]),
+            volume: None,
+        };
+
+        let result = CMO::new().calculate(&input_data, json!({ "period": 2 })).unwrap();
+
+        if let OutputData::SingleSeries(cmo) = result {
+            assert!(cmo[0].is_nan() && cmo[1].is_nan());
+            // Gains 1.5 and losses 0.5 after two bars: 100 * 1 / 2.
+            assert_eq!(cmo[2], 50.0);
+            // Smoothed: gains 0.75 / losses 0.25, then gains 1.375 / losses 0.125.
+            assert_eq!(cmo[3], 50.0);
+            assert_eq!(cmo[4], 100.0 * (1.25 / 1.5));
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::{rolling_extremes_into, validate_parameter_within_data_length};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct DonchianParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+}
+
+fn default_period() -> usize { 20 }
+
+impl IParameter for DonchianParams {}
+
+/// Highest high and lowest low of the last `period` bars, with their midpoint as the middle band.
+pub struct Donchian {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::HIGH, BarField::LOW],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::HIGH))),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::TrendIdentification));
+    groups.insert(Group::UseCase(UseCase::SupportResistanceLevels));
+    groups.insert(Group::UseCase(UseCase::VolatilityMeasurement));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::StatisticalMethods));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    groups.insert(Group::OutputFormat(OutputFormat::Band));
+    groups.insert(Group::OutputFormat(OutputFormat::Absolute));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Long));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Basic));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Positional));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Raw));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Statistical));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::PeakThroughLevels));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Crossovers));
+    groups
+}
+
+impl Donchian {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for Donchian {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for Donchian {
+    fn short_name(&self) -> &'static str {
+        "DONCHIAN"
+    }
+
+    fn name(&self) -> &'static str {
+        "Donchian Channels"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: DonchianParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+
+        let length = high.len();
+        let [middle_band, upper_band, lower_band] = out.multi(["middle_band", "upper_band", "lower_band"], length);
+        rolling_extremes_into(high, low, params.period, upper_band, lower_band);
+        for i in 0..length {
+            middle_band[i] = (upper_band[i] + lower_band[i]) / T::cast(2.0);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_donchian_expected_values() {
+        let input_data = InputData {
+            open: None,
+            high: Some(array![10.0, 12.0, 11.0, 9.0, 10.5]),
+            low: Some(array![8.0, 9.0, 7.0, 8.5, 9.5]),
+            close: None,
+            volume: None,
+        };
+
+        let result = Donchian::new().calculate(&input_data, json!({ "period": 3 })).unwrap();
+
+        if let OutputData::MultiSeries(bands) = result {
+            assert!(bands["upper_band"][1].is_nan() && bands["middle_band"][1].is_nan());
+            assert_eq!(bands["upper_band"].slice(ndarray::s![2..]), array![12.0, 12.0, 11.0]);
+            assert_eq!(bands["lower_band"].slice(ndarray::s![2..]), array![7.0, 7.0, 7.0]);
+            assert_eq!(bands["middle_band"][4], 9.0);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
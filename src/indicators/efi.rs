@@ -0,0 +1,148 @@
+use std::collections::HashSet;
+use ndarray::s;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::{exponential_moving_average_into, validate_parameter_within_data_length};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct EFIParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+}
+
+fn default_period() -> usize { 13 }
+
+impl IParameter for EFIParams {}
+
+pub struct EFI {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::CLOSE, BarField::VOLUME],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::CLOSE))),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::MomentumDetection));
+    groups.insert(Group::UseCase(UseCase::VolumeConfirmation));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Differentiation));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::VolumeWeighted));
+    groups.insert(Group::DataInputType(DataInputType::PriceVolumeCombined));
+    groups.insert(Group::SignalType(SignalType::Leading));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Histogram));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Intermediate));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::MarketSuitability(MarketSuitability::RangeBound));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Exponential));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Differential));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Crossovers));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Divergence));
+    groups
+}
+
+impl EFI {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for EFI {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for EFI {
+    fn short_name(&self) -> &'static str {
+        "EFI"
+    }
+
+    fn name(&self) -> &'static str {
+        "Elder Force Index"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: EFIParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+        let volume = data.get_by_bar_field(&BarField::VOLUME).unwrap();
+
+        let length = close.len();
+        if params.period >= length {
+            return Err(IndicatorError::InvalidParameters(format!("Parameter 'period' must be less than data length ({} >= {})", params.period, length)));
+        }
+
+        // Raw force of each price change, then its EMA.
+        let [raw_force, smoothed] = scratch.arrays(length - 1);
+        for i in 1..length {
+            raw_force[i - 1] = (close[i] - close[i - 1]) * volume[i];
+        }
+        exponential_moving_average_into(raw_force.view(), params.period, smoothed);
+
+        let force_index = out.single(length);
+        force_index[0] = T::nan();
+        force_index.slice_mut(s![1..]).assign(smoothed);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_efi_expected_value() {
+        let input_data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(array![10.0, 11.0, 10.5, 11.5]),
+            volume: Some(array![100.0, 100.0, 200.0, 300.0]),
+        };
+
+        let result = EFI::new().calculate(&input_data, json!({ "period": 2 })).unwrap();
+
+        if let OutputData::SingleSeries(force_values) = result {
+            // Raw force 100, -100, 300; seeded with their first two values' mean.
+            assert!(force_values[0].is_nan() && force_values[1].is_nan());
+            assert_eq!(force_values[2], 0.0);
+            assert_eq!(force_values[3], 200.0);
+            assert!(EFI::new().calculate(&input_data, json!({ "period": 4 })).is_err());
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::validate_parameter_within_data_length;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct EMVParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+    /// Scales volume so values are readable; 100,000,000 by default.
+    #[serde(default = "default_volume_divisor")]
+    pub volume_divisor: f64,
+}
+
+fn default_period() -> usize { 14 }
+fn default_volume_divisor() -> f64 { 100_000_000.0 }
+
+impl IParameter for EMVParams {}
+
+pub struct EMV {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::HIGH, BarField::LOW, BarField::VOLUME],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::HIGH))),
+            ParamRule::PositiveNumber("volume_divisor"),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::MomentumDetection));
+    groups.insert(Group::UseCase(UseCase::VolumeConfirmation));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::RatioBased));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::VolumeWeighted));
+    groups.insert(Group::DataInputType(DataInputType::PriceVolumeCombined));
+    groups.insert(Group::SignalType(SignalType::Leading));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Histogram));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Intermediate));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::MarketSuitability(MarketSuitability::RangeBound));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::SimpleAverage));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Ratio));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Crossovers));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::ThresholdLevels));
+    groups
+}
+
+impl EMV {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for EMV {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for EMV {
+    fn short_name(&self) -> &'static str {
+        "EMV"
+    }
+
+    fn name(&self) -> &'static str {
+        "Ease of Movement"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: EMVParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+        let volume = data.get_by_bar_field(&BarField::VOLUME).unwrap();
+
+        let length = high.len();
+        if params.period >= length {
+            return Err(IndicatorError::InvalidParameters(format!("Parameter 'period' must be less than data length ({} >= {})", params.period, length)));
+        }
+
+        // Midpoint move divided by the box ratio (volume / divisor per unit of range), zero when
+        // there is no range or no volume.
+        let [raw_emv] = scratch.arrays(length);
+        let divisor = T::cast(params.volume_divisor);
+        raw_emv[0] = T::zero();
+        for i in 1..length {
+            let distance = ((high[i] + low[i]) - (high[i - 1] + low[i - 1])) / T::cast(2.0);
+            let high_low_range = high[i] - low[i];
+            raw_emv[i] = if high_low_range == T::zero() || volume[i] == T::zero() {
+                T::zero()
+            } else {
+                distance * high_low_range * divisor / volume[i]
+            };
+        }
+
+        let emv = out.single(length);
+        emv.fill(T::nan());
+        let period = params.period;
+        let mut sum = T::zero();
+        for i in 1..length {
+            sum += raw_emv[i];
+            if i > period {
+                sum -= raw_emv[i - period];
+            }
+            if i >= period {
+                emv[i] = sum / T::cast(period as f64);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_emv_expected_value() {
+        let input_data = InputData {
+            open: None,
+            high: Some(array![11.0, 12.0, 12.0, 13.0]),
+            low: Some(array![9.0, 10.0, 12.0, 11.0]),
+            close: None,
+            volume: Some(array![100.0, 200.0, 100.0, 400.0]),
+        };
+
+        let result = EMV::new().calculate(&input_data, json!({ "period": 2, "volume_divisor": 100.0 })).unwrap();
+
+        if let OutputData::SingleSeries(emv_values) = result {
+            // Raw values 1 * 2 * 100 / 200 = 1, 0 (high == low) and 0 * 2 * 100 / 400 = 0.
+            assert!(emv_values[0].is_nan() && emv_values[1].is_nan());
+            assert_eq!(emv_values[2], 0.5);
+            assert_eq!(emv_values[3], 0.0);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::{exponential_moving_average_into, rolling_mean_into, validate_parameter_within_data_length};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+/// Moving average the envelope is built around.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnvelopeAverage {
+    #[default]
+    Simple,
+    Exponential,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EnvelopeParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+    /// Distance of the bands from the average, in percent of it.
+    #[serde(default = "default_percentage")]
+    pub percentage: f64,
+    #[serde(default)]
+    pub average: EnvelopeAverage,
+}
+
+fn default_period() -> usize { 20 }
+fn default_percentage() -> f64 { 2.5 }
+
+impl IParameter for EnvelopeParams {}
+
+/// Moving average of the close with bands a fixed percentage above and below.
+pub struct Envelope {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::CLOSE],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::PositiveNumber("percentage"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::CLOSE))),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::TrendIdentification));
+    groups.insert(Group::UseCase(UseCase::SupportResistanceLevels));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Averaging));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::RatioBased));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    groups.insert(Group::OutputFormat(OutputFormat::Band));
+    groups.insert(Group::OutputFormat(OutputFormat::Absolute));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Basic));
+    groups.insert(Group::MarketSuitability(MarketSuitability::RangeBound));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::SimpleAverage));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Exponential));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Averaging));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::ThresholdLevels));
+    groups
+}
+
+impl Envelope {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for Envelope {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for Envelope {
+    fn short_name(&self) -> &'static str {
+        "ENVELOPE"
+    }
+
+    fn name(&self) -> &'static str {
+        "Moving Average Envelope"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: EnvelopeParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let length = close.len();
+        let [middle_band, upper_band, lower_band] = out.multi(["middle_band", "upper_band", "lower_band"], length);
+        match params.average {
+            EnvelopeAverage::Simple => rolling_mean_into(close, 0, params.period, middle_band),
+            EnvelopeAverage::Exponential => exponential_moving_average_into(close, params.period, middle_band),
+        }
+        let offset = T::cast(params.percentage / 100.0);
+        for i in 0..length {
+            upper_band[i] = middle_band[i] * (T::one() + offset);
+            lower_band[i] = middle_band[i] * (T::one() - offset);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_envelope_expected_values() {
+        let input_data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(array![100.0, 102.0, 104.0, 106.0]),
+            volume: None,
+        };
+
+        let simple = Envelope::new().calculate(&input_data, json!({ "period": 2, "percentage": 10.0 })).unwrap();
+        let exponential = Envelope::new().calculate(&input_data, json!({ "period": 2, "percentage": 10.0, "average": "exponential" })).unwrap();
+
+        if let (OutputData::MultiSeries(simple), OutputData::MultiSeries(exponential)) = (simple, exponential) {
+            assert!(simple["middle_band"][0].is_nan() && simple["upper_band"][0].is_nan());
+            assert_eq!(simple["middle_band"][3], 105.0);
+            assert!((simple["upper_band"][3] - 115.5).abs() < 1e-12);
+            assert!((simple["lower_band"][3] - 94.5).abs() < 1e-12);
+            // EMA(2) seeded with 101: 101 + (104 - 101) * 2 / 3 = 103.
+            assert!((exponential["middle_band"][2] - 103.0).abs() < 1e-12);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashSet;
+use std::ops::Bound;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct EWMAParams {
+    /// Decay of the previous variance; RiskMetrics uses 0.94 for daily returns.
+    #[serde(default = "default_lambda")]
+    pub lambda: f64,
+}
+
+fn default_lambda() -> f64 { 0.94 }
+
+impl IParameter for EWMAParams {}
+
+/// RiskMetrics volatility: `σ²[t] = λ·σ²[t-1] + (1 - λ)·r²[t-1]` over close-to-close log returns
+/// `r`, assumed to have zero mean, starting from the first squared return. Outputs the
+/// `volatility` of every bar's return and the `forecast` made at every bar for the next one, which
+/// is also the forecast for any later bar.
+pub struct EWMA {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::CLOSE],
+        vec![
+            ParamRule::Required("lambda"),
+            ParamRule::NumberInRange { name: "lambda", min: Bound::Excluded(0.0), max: Bound::Excluded(1.0) },
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::VolatilityMeasurement));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::ProbabilisticMethods));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::StatisticalMethods));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    groups.insert(Group::OutputFormat(OutputFormat::MultiLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Absolute));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Intermediate));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Volatile));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Stable));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Positional));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Exponential));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Statistical));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::ThresholdLevels));
+    groups
+}
+
+impl EWMA {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for EWMA {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for EWMA {
+    fn short_name(&self) -> &'static str {
+        "EWMA"
+    }
+
+    fn name(&self) -> &'static str {
+        "RiskMetrics EWMA Volatility"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: EWMAParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let length = close.len();
+        let lambda = T::cast(params.lambda);
+        let [volatility, forecast] = out.multi(["volatility", "forecast"], length);
+        volatility.fill(T::nan());
+        forecast.fill(T::nan());
+
+        let mut variance = T::nan();
+        for i in 1..length {
+            let squared_return = (close[i] / close[i - 1]).ln().powi(2);
+            if i > 1 {
+                volatility[i] = variance.sqrt();
+                variance = lambda * variance + (T::one() - lambda) * squared_return;
+            } else {
+                variance = squared_return;
+            }
+            forecast[i] = variance.sqrt();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_ewma_expected_values() {
+        let (up, down) = (0.02_f64.exp(), (-0.01_f64).exp());
+        let input_data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(array![100.0, 100.0 * up, 100.0 * up * down, 100.0 * up * down * down]),
+            volume: None,
+        };
+
+        let result = EWMA::new().calculate(&input_data, json!({ "lambda": 0.5 })).unwrap();
+
+        if let OutputData::MultiSeries(series) = result {
+            let (volatility, forecast) = (&series["volatility"], &series["forecast"]);
+            assert!(volatility[0].is_nan() && volatility[1].is_nan() && forecast[0].is_nan());
+            assert!((forecast[1] - 0.02).abs() < 1e-12);
+            assert!((volatility[2] - 0.02).abs() < 1e-12);
+            // 0.5 * 0.02^2 + 0.5 * 0.01^2
+            assert!((volatility[3] - 0.00025_f64.sqrt()).abs() < 1e-12);
+            assert!((forecast[2] - volatility[3]).abs() < 1e-15);
+            assert!(EWMA::new().calculate(&input_data, json!({ "lambda": 1.0 })).is_err());
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,436 @@
+use std::collections::HashSet;
+use std::f64::consts::PI;
+use ndarray::{s, Array1, ArrayView1};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+/// Fewest returns a model is fitted on.
+const MIN_RETURNS: usize = 20;
+
+#[derive(Serialize, Deserialize)]
+pub struct GARCHParams {
+    /// Bars ahead of every bar the `forecast` series looks.
+    #[serde(default = "default_horizon")]
+    pub horizon: usize,
+}
+
+fn default_horizon() -> usize { 10 }
+
+impl IParameter for GARCHParams {}
+
+/// Maximum-likelihood fit of `σ²[t] = ω + (α + γ·[ε[t-1] < 0])·ε²[t-1] + β·σ²[t-1]` on the
+/// demeaned log returns `ε` of a close series, with Gaussian innovations. The recursion starts from
+/// the sample variance.
+///
+/// `gamma` is zero for the symmetric GARCH(1,1) and weighs negative shocks extra in GJR-GARCH.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GarchFit {
+    pub mean: f64,
+    pub omega: f64,
+    pub alpha: f64,
+    pub beta: f64,
+    pub gamma: f64,
+    pub log_likelihood: f64,
+}
+
+impl GarchFit {
+    /// Fits GARCH(1,1), or GJR-GARCH(1,1,1) when `asymmetric`, on `returns`.
+    pub fn fit(returns: &[f64], asymmetric: bool) -> Result<Self, IndicatorError> {
+        if returns.len() < MIN_RETURNS {
+            return Err(IndicatorError::InvalidInput(format!("GARCH fitting needs at least {} returns, got {}.", MIN_RETURNS, returns.len())));
+        }
+        let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+        let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / returns.len() as f64;
+        if !(variance > 0.0 && variance.is_finite()) {
+            return Err(IndicatorError::CalculationError("GARCH fitting needs returns that vary.".to_string()));
+        }
+
+        // The likelihood is fitted on returns scaled to unit variance, where omega is of the same
+        // order as alpha and beta; only omega depends on the scale.
+        let scale = variance.sqrt();
+        let shocks: Vec<f64> = returns.iter().map(|r| (r - mean) / scale).collect();
+        let negative_log_likelihood = |x: &[f64]| {
+            let (omega, alpha, beta, gamma) = (x[0], x[1], x[2], if asymmetric { x[3] } else { 0.0 });
+            if omega <= 0.0 || alpha < 0.0 || beta < 0.0 || alpha + gamma < 0.0 || alpha + gamma / 2.0 + beta >= 1.0 {
+                return f64::INFINITY;
+            }
+            let model = GarchFit { mean: 0.0, omega, alpha, beta, gamma, log_likelihood: f64::NAN };
+            let mut total = 0.0;
+            model.conditional_variance(&shocks, 1.0, |shock, variance| total += variance.ln() + shock * shock / variance);
+            total / 2.0
+        };
+        let start: &[f64] = if asymmetric { &[0.045, 0.03, 0.9, 0.05] } else { &[0.05, 0.05, 0.9] };
+        // A restart from the first optimum guards against a collapsed simplex.
+        let optimum = nelder_mead(&negative_log_likelihood, &nelder_mead(&negative_log_likelihood, start));
+
+        let mut fit = GarchFit {
+            mean,
+            omega: optimum[0] * variance,
+            alpha: optimum[1],
+            beta: optimum[2],
+            gamma: if asymmetric { optimum[3] } else { 0.0 },
+            log_likelihood: 0.0,
+        };
+        let mut total = 0.0;
+        fit.conditional_variance(&returns.iter().map(|r| r - mean).collect::<Vec<_>>(), variance, |shock, variance| {
+            total += (2.0 * PI).ln() + variance.ln() + shock * shock / variance;
+        });
+        fit.log_likelihood = -total / 2.0;
+        Ok(fit)
+    }
+
+    /// `α + γ/2 + β`: how much of a variance shock carries over to the next bar, on average.
+    pub fn persistence(&self) -> f64 {
+        self.alpha + self.gamma / 2.0 + self.beta
+    }
+
+    /// Variance the forecasts revert to.
+    pub fn unconditional_variance(&self) -> f64 {
+        self.omega / (1.0 - self.persistence())
+    }
+
+    /// Variance after a shock `shock` (a demeaned return) with conditional variance `variance`.
+    fn next_variance(&self, shock: f64, variance: f64) -> f64 {
+        let alpha = if shock < 0.0 { self.alpha + self.gamma } else { self.alpha };
+        self.omega + alpha * shock * shock + self.beta * variance
+    }
+
+    /// Calls `visit(shock, variance)` for every demeaned return and its conditional variance,
+    /// starting the recursion from `initial`.
+    fn conditional_variance(&self, shocks: &[f64], initial: f64, mut visit: impl FnMut(f64, f64)) {
+        let mut variance = initial;
+        for &shock in shocks {
+            visit(shock, variance);
+            variance = self.next_variance(shock, variance);
+        }
+    }
+
+    /// Variance forecast `horizon` steps ahead, given the one-step forecast `next`.
+    pub fn forecast(&self, next: f64, horizon: usize) -> f64 {
+        let long_run = self.unconditional_variance();
+        long_run + self.persistence().powi(horizon as i32 - 1) * (next - long_run)
+    }
+
+    /// Writes the in-sample conditional volatility of every bar's return into `volatility`, and the
+    /// volatility forecast made at every bar for `horizon` bars ahead into `forecast`. `returns`
+    /// are the returns of bars 1.., so the first bar is NaN in both.
+    ///
+    /// `path` gets the forecasts made at the last bar for 1 to `horizon` bars ahead, in its last
+    /// `horizon` values, and NaN before them. Displaced by `horizon` bars, the path lines up with
+    /// the bars after the input. `horizon` must not exceed the number of bars.
+    pub fn volatility_into<T: Float>(&self, returns: &[f64], horizon: usize, volatility: &mut Array1<T>, forecast: &mut Array1<T>, path: &mut Array1<T>) {
+        volatility[0] = T::nan();
+        forecast[0] = T::nan();
+        let initial = returns.iter().map(|r| (r - self.mean).powi(2)).sum::<f64>() / returns.len() as f64;
+        let shocks: Vec<f64> = returns.iter().map(|r| r - self.mean).collect();
+        let mut bar = 1;
+        let mut next = initial;
+        self.conditional_variance(&shocks, initial, |shock, variance| {
+            next = self.next_variance(shock, variance);
+            volatility[bar] = T::cast(variance.sqrt());
+            forecast[bar] = T::cast(self.forecast(next, horizon).sqrt());
+            bar += 1;
+        });
+
+        let start = path.len() - horizon;
+        path.slice_mut(s![..start]).fill(T::nan());
+        for (step, value) in path.slice_mut(s![start..]).iter_mut().enumerate() {
+            *value = T::cast(self.forecast(next, step + 1).sqrt());
+        }
+    }
+}
+
+/// Fails when the forecast path of `horizon` bars does not fit in an output of `length` bars.
+pub fn check_horizon(horizon: usize, length: usize) -> Result<(), IndicatorError> {
+    if horizon > length {
+        return Err(IndicatorError::InvalidInput(format!("Forecast horizon {} exceeds the {} input bars.", horizon, length)));
+    }
+    Ok(())
+}
+
+/// Log returns of `close`, one per bar after the first.
+pub fn log_returns<T: Float>(close: ArrayView1<T>) -> Vec<f64> {
+    close.windows(2).into_iter()
+        .map(|pair| (pair[1] / pair[0]).ln().to_f64().unwrap())
+        .collect()
+}
+
+/// Nelder-Mead simplex minimization of `f` from `start`.
+fn nelder_mead(f: &dyn Fn(&[f64]) -> f64, start: &[f64]) -> Vec<f64> {
+    const STEP: f64 = 0.02;
+    const TOLERANCE: f64 = 1e-10;
+    const MAX_ITERATIONS: usize = 5000;
+
+    let dimension = start.len();
+    let mut simplex: Vec<(Vec<f64>, f64)> = (0..=dimension)
+        .map(|i| {
+            let mut point = start.to_vec();
+            if i > 0 {
+                point[i - 1] += STEP;
+            }
+            let value = f(&point);
+            (point, value)
+        })
+        .collect();
+
+    for _ in 0..MAX_ITERATIONS {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let (best, worst) = (simplex[0].1, simplex[dimension].1);
+        if worst - best <= TOLERANCE * (1.0 + best.abs()) {
+            break;
+        }
+
+        let centroid: Vec<f64> = (0..dimension)
+            .map(|j| simplex[..dimension].iter().map(|(point, _)| point[j]).sum::<f64>() / dimension as f64)
+            .collect();
+        // Points on the line from the centroid through the worst point: -1 reflects it, -2 expands
+        // the reflection, -0.5 and 0.5 contract outside and inside.
+        let along = |t: f64| -> Vec<f64> {
+            centroid.iter().zip(&simplex[dimension].0).map(|(c, w)| c + t * (w - c)).collect()
+        };
+
+        let reflected = along(-1.0);
+        let reflected_value = f(&reflected);
+        if reflected_value < best {
+            let expanded = along(-2.0);
+            let expanded_value = f(&expanded);
+            simplex[dimension] = if expanded_value < reflected_value { (expanded, expanded_value) } else { (reflected, reflected_value) };
+        } else if reflected_value < simplex[dimension - 1].1 {
+            simplex[dimension] = (reflected, reflected_value);
+        } else {
+            let contracted = along(if reflected_value < worst { -0.5 } else { 0.5 });
+            let contracted_value = f(&contracted);
+            if contracted_value < reflected_value.min(worst) {
+                simplex[dimension] = (contracted, contracted_value);
+            } else {
+                let best_point = simplex[0].0.clone();
+                for (point, value) in simplex.iter_mut().skip(1) {
+                    for (x, b) in point.iter_mut().zip(&best_point) {
+                        *x = b + (*x - b) / 2.0;
+                    }
+                    *value = f(point);
+                }
+            }
+        }
+    }
+
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    simplex.swap_remove(0).0
+}
+
+/// GARCH(1,1) conditional volatility of close-to-close log returns. Outputs the in-sample
+/// `volatility`, the `forecast` made at every bar for `horizon` bars ahead, the `forecast_path`
+/// made at the last bar for each of the next `horizon` bars, and the fitted `omega`, `alpha` and
+/// `beta`, repeated on every bar. `forecast_path` is displaced by `horizon` bars: its last
+/// `horizon` values belong to the bars after the input. The parameters are fitted on the whole
+/// input, so in-sample values use information from later bars.
+pub struct GARCH {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::CLOSE],
+        vec![
+            ParamRule::Required("horizon"),
+            ParamRule::PositiveInteger("horizon"),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::VolatilityMeasurement));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::ProbabilisticMethods));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::StatisticalMethods));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Leading));
+    groups.insert(Group::OutputFormat(OutputFormat::MultiLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Absolute));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Long));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Advanced));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Volatile));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Stable));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Positional));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Exponential));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Statistical));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::ThresholdLevels));
+    groups
+}
+
+impl GARCH {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for GARCH {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for GARCH {
+    fn short_name(&self) -> &'static str {
+        "GARCH"
+    }
+
+    fn name(&self) -> &'static str {
+        "GARCH(1,1) Volatility"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn displacement(&self, series: &str, params: &Value) -> Result<isize, IndicatorError> {
+        let params: GARCHParams = serde_json::from_value(params.clone())
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        match series {
+            "volatility" | "forecast" | "omega" | "alpha" | "beta" => Ok(0),
+            "forecast_path" => Ok(params.horizon as isize),
+            _ => Err(IndicatorError::InvalidInput(format!("Output series '{}' does not exist.", series))),
+        }
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: GARCHParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+        check_horizon(params.horizon, close.len())?;
+
+        let returns = log_returns(close);
+        let fit = GarchFit::fit(&returns, false)?;
+
+        let [volatility, forecast, forecast_path, omega, alpha, beta] = out.multi(["volatility", "forecast", "forecast_path", "omega", "alpha", "beta"], close.len());
+        fit.volatility_into(&returns, params.horizon, volatility, forecast, forecast_path);
+        omega.fill(T::cast(fit.omega));
+        alpha.fill(T::cast(fit.alpha));
+        beta.fill(T::cast(fit.beta));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    /// Closes whose log returns follow the given model with standard normal innovations, drawn
+    /// from a fixed seed.
+    pub(crate) fn simulate(model: &GarchFit, length: usize) -> InputData {
+        let mut state: u64 = 42;
+        let mut uniform = || {
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^= z >> 31;
+            ((z >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+        };
+        let mut close = vec![100.0];
+        let mut variance = model.unconditional_variance();
+        for _ in 1..length {
+            // Box-Muller.
+            let normal = (-2.0 * uniform().ln()).sqrt() * (2.0 * PI * uniform()).cos();
+            let shock = variance.sqrt() * normal;
+            close.push(close.last().unwrap() * (model.mean + shock).exp());
+            variance = model.next_variance(shock, variance);
+        }
+        InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(Array1::from(close)),
+            volume: None,
+        }
+    }
+
+    #[test]
+    fn test_garch_recovers_parameters() {
+        let model = GarchFit { mean: 0.0, omega: 2e-6, alpha: 0.1, beta: 0.85, gamma: 0.0, log_likelihood: f64::NAN };
+        let input_data = simulate(&model, 5000);
+
+        let fit = GarchFit::fit(&log_returns(input_data.close.as_ref().unwrap().view()), false).unwrap();
+        assert!((fit.alpha - 0.1).abs() < 0.03, "alpha = {}", fit.alpha);
+        assert!((fit.beta - 0.85).abs() < 0.05, "beta = {}", fit.beta);
+        assert!((fit.unconditional_variance() / model.unconditional_variance() - 1.0).abs() < 0.2);
+
+        let result = GARCH::new().calculate(&input_data, json!({ "horizon": 5 })).unwrap();
+        if let OutputData::MultiSeries(series) = result {
+            assert!(series["volatility"][0].is_nan() && series["forecast"][0].is_nan());
+            assert!(series["alpha"].iter().all(|&alpha| alpha == fit.alpha));
+            // The forecast for the next bar is that bar's in-sample volatility.
+            let one_step = GARCH::new().calculate(&input_data, json!({ "horizon": 1 })).unwrap();
+            assert!((one_step.series("forecast").unwrap()[100] - series["volatility"][101]).abs() < 1e-15);
+            // Far enough ahead, forecasts revert to the unconditional volatility.
+            let long_run = GARCH::new().calculate(&input_data, json!({ "horizon": 2000 })).unwrap();
+            let unconditional = fit.unconditional_variance().sqrt();
+            assert!(long_run.series("forecast").unwrap().iter().skip(1).all(|&x| (x - unconditional).abs() < 1e-9));
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+
+    #[test]
+    fn test_garch_forecast_path_after_last_bar() {
+        let model = GarchFit { mean: 0.0, omega: 2e-6, alpha: 0.1, beta: 0.85, gamma: 0.0, log_likelihood: f64::NAN };
+        let input_data = simulate(&model, 1000);
+        let params = json!({ "horizon": 300 });
+        let garch = GARCH::new();
+
+        let result = garch.calculate(&input_data, params.clone()).unwrap();
+        let displacement = Indicator::<f64>::displacement(&garch, "forecast_path", &params).unwrap();
+        assert_eq!(displacement, 300);
+        assert_eq!(Indicator::<f64>::displacement(&garch, "forecast", &params).unwrap(), 0);
+        assert!(Indicator::<f64>::displacement(&garch, "path", &params).is_err());
+
+        let path = result.displaced_series("forecast_path", displacement).unwrap();
+        assert_eq!(path.len(), 1300);
+        assert!(path.iter().take(1000).all(|x| x.is_nan()));
+        // Step h of the path is the h-step forecast made at the last bar.
+        for horizon in [1, 2, 50] {
+            let forecast = garch.calculate(&input_data, json!({ "horizon": horizon })).unwrap();
+            assert!((path[999 + horizon] - forecast.series("forecast").unwrap()[999]).abs() < 1e-15);
+        }
+        // The path reverts monotonically to the unconditional volatility.
+        let fit = GarchFit::fit(&log_returns(input_data.close.as_ref().unwrap().view()), false).unwrap();
+        let gaps: Vec<f64> = path.iter().skip(1000).map(|x| (x - fit.unconditional_variance().sqrt()).abs()).collect();
+        assert!(gaps.windows(2).all(|pair| pair[1] <= pair[0]));
+        assert!(gaps[299] < 1e-3 * gaps[0]);
+
+        assert!(matches!(garch.calculate(&input_data, json!({ "horizon": 1001 })), Err(IndicatorError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_garch_rejects_short_or_flat_input() {
+        let mut input_data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(Array1::from_elem(50, 100.0)),
+            volume: None,
+        };
+        assert!(matches!(GARCH::new().calculate(&input_data, json!({})), Err(IndicatorError::CalculationError(_))));
+        input_data.close = Some(Array1::linspace(100.0, 110.0, 10));
+        assert!(matches!(GARCH::new().calculate(&input_data, json!({})), Err(IndicatorError::InvalidInput(_))));
+        assert!(GARCH::new().calculate(&input_data, json!({ "horizon": 0 })).is_err());
+    }
+}
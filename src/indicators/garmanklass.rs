@@ -0,0 +1,153 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::{rolling_mean_into, validate_parameter_within_data_length};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct GarmanKlassParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+    /// Bars per year; the variance is multiplied by it before taking the square root.
+    #[serde(default = "default_annualization")]
+    pub annualization: f64,
+}
+
+fn default_period() -> usize { 20 }
+fn default_annualization() -> f64 { 252.0 }
+
+impl IParameter for GarmanKlassParams {}
+
+/// Garman-Klass (1980) volatility: `0.5 ln(H / L)^2 - (2 ln 2 - 1) ln(C / O)^2` averaged over
+/// `period` bars, annualized. Assumes no opening gaps.
+pub struct GarmanKlass {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::OPEN, BarField::HIGH, BarField::LOW, BarField::CLOSE],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::HIGH))),
+            ParamRule::PositiveNumber("annualization"),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::VolatilityMeasurement));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::StatisticalMethods));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Percentage));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Long));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Advanced));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Volatile));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Stable));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Positional));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::SimpleAverage));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Statistical));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::ThresholdLevels));
+    groups
+}
+
+impl GarmanKlass {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for GarmanKlass {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for GarmanKlass {
+    fn short_name(&self) -> &'static str {
+        "GARMANKLASS"
+    }
+
+    fn name(&self) -> &'static str {
+        "Garman-Klass Volatility"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: GarmanKlassParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let open = data.get_by_bar_field(&BarField::OPEN).unwrap();
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let length = high.len();
+        let annualization = T::cast(params.annualization);
+        let [terms, variance] = scratch.arrays(length);
+        let weight = T::cast(2.0 * std::f64::consts::LN_2 - 1.0);
+        for i in 0..length {
+            let range = (high[i] / low[i]).ln();
+            let body = (close[i] / open[i]).ln();
+            terms[i] = T::cast(0.5) * range * range - weight * body * body;
+        }
+        rolling_mean_into(terms.view(), 0, params.period, variance);
+
+        let volatility = out.single(length);
+        for i in 0..length {
+            volatility[i] = (variance[i] * annualization).sqrt();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::Array1;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_garman_klass_closed_form() {
+        // ln(H / L) = 0.02 and ln(C / O) = 0.01 on every bar.
+        let open = Array1::from_elem(4, 100.0);
+        let input_data = InputData {
+            high: Some(&open * 0.015_f64.exp()),
+            low: Some(&open * (-0.005_f64).exp()),
+            close: Some(&open * 0.01_f64.exp()),
+            open: Some(open),
+            volume: None,
+        };
+
+        let result = GarmanKlass::new().calculate(&input_data, json!({ "period": 2, "annualization": 252.0 })).unwrap();
+
+        if let OutputData::SingleSeries(values) = result {
+            let variance = 0.5 * 0.0004 - (2.0 * std::f64::consts::LN_2 - 1.0) * 0.0001;
+            assert!(values[0].is_nan());
+            assert!((values[3] - (variance * 252.0_f64).sqrt()).abs() < 1e-12);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::HashSet;
+use serde_json::Value;
+use crate::indicators::garch::{check_horizon, log_returns, GARCHParams, GarchFit};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{ParamRule, Validator};
+
+/// GJR-GARCH(1,1,1): GARCH(1,1) with an extra `gamma` weight on negative returns, for the leverage
+/// effect. Outputs the same series as GARCH plus the fitted `gamma`.
+pub struct GJRGARCH {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::CLOSE],
+        vec![
+            ParamRule::Required("horizon"),
+            ParamRule::PositiveInteger("horizon"),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::VolatilityMeasurement));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::ProbabilisticMethods));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::StatisticalMethods));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Leading));
+    groups.insert(Group::OutputFormat(OutputFormat::MultiLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Absolute));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Long));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Advanced));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Volatile));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Stable));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Positional));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Exponential));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Statistical));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::ThresholdLevels));
+    groups
+}
+
+impl GJRGARCH {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for GJRGARCH {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for GJRGARCH {
+    fn short_name(&self) -> &'static str {
+        "GJRGARCH"
+    }
+
+    fn name(&self) -> &'static str {
+        "GJR-GARCH Volatility"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn displacement(&self, series: &str, params: &Value) -> Result<isize, IndicatorError> {
+        let params: GARCHParams = serde_json::from_value(params.clone())
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        match series {
+            "volatility" | "forecast" | "omega" | "alpha" | "beta" | "gamma" => Ok(0),
+            "forecast_path" => Ok(params.horizon as isize),
+            _ => Err(IndicatorError::InvalidInput(format!("Output series '{}' does not exist.", series))),
+        }
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: GARCHParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+        check_horizon(params.horizon, close.len())?;
+
+        let returns = log_returns(close);
+        let fit = GarchFit::fit(&returns, true)?;
+
+        let [volatility, forecast, forecast_path, omega, alpha, beta, gamma] = out.multi(["volatility", "forecast", "forecast_path", "omega", "alpha", "beta", "gamma"], close.len());
+        fit.volatility_into(&returns, params.horizon, volatility, forecast, forecast_path);
+        omega.fill(T::cast(fit.omega));
+        alpha.fill(T::cast(fit.alpha));
+        beta.fill(T::cast(fit.beta));
+        gamma.fill(T::cast(fit.gamma));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+    use crate::indicators::garch::test::simulate;
+    use crate::models::data::OutputData;
+    use super::*;
+
+    #[test]
+    fn test_gjrgarch_recovers_leverage() {
+        let model = GarchFit { mean: 0.0, omega: 2e-6, alpha: 0.03, beta: 0.85, gamma: 0.15, log_likelihood: f64::NAN };
+        let input_data = simulate(&model, 5000);
+        let returns = log_returns(input_data.close.as_ref().unwrap().view());
+
+        let fit = GarchFit::fit(&returns, true).unwrap();
+        assert!((fit.gamma - 0.15).abs() < 0.06, "gamma = {}", fit.gamma);
+        assert!((fit.beta - 0.85).abs() < 0.05, "beta = {}", fit.beta);
+        // The symmetric model is nested in the asymmetric one.
+        assert!(fit.log_likelihood > GarchFit::fit(&returns, false).unwrap().log_likelihood);
+
+        let result = GJRGARCH::new().calculate(&input_data, json!({})).unwrap();
+        if let OutputData::MultiSeries(series) = result {
+            assert_eq!(series.len(), 7);
+            assert!(series["gamma"].iter().all(|&gamma| gamma == fit.gamma));
+            assert!(series["volatility"].iter().skip(1).all(|&volatility| volatility > 0.0));
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
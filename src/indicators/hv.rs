@@ -0,0 +1,152 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::{rolling_sample_variance_into, validate_parameter_at_least, validate_parameter_within_data_length};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct HVParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+    /// Bars per year; the variance is multiplied by it before taking the square root.
+    #[serde(default = "default_annualization")]
+    pub annualization: f64,
+}
+
+fn default_period() -> usize { 20 }
+fn default_annualization() -> f64 { 252.0 }
+
+impl IParameter for HVParams {}
+
+/// Close-to-close volatility: the sample standard deviation of log returns over `period` returns,
+/// annualized.
+pub struct HV {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::CLOSE],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::CLOSE))),
+            ParamRule::PositiveNumber("annualization"),
+            ParamRule::Custom(Box::new(|value: &Value, _: &dyn Candles| validate_parameter_at_least(value, "period", 2))),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::VolatilityMeasurement));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::StatisticalMethods));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Percentage));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Long));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Intermediate));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Volatile));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Stable));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Positional));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::SimpleAverage));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Statistical));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::ThresholdLevels));
+    groups
+}
+
+impl HV {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for HV {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for HV {
+    fn short_name(&self) -> &'static str {
+        "HV"
+    }
+
+    fn name(&self) -> &'static str {
+        "Historical Volatility"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: HVParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let length = close.len();
+        let annualization = T::cast(params.annualization);
+        let [returns, variance] = scratch.arrays(length);
+        returns[0] = T::nan();
+        for i in 1..length {
+            returns[i] = (close[i] / close[i - 1]).ln();
+        }
+        rolling_sample_variance_into(returns.view(), 1, params.period, variance);
+
+        let volatility = out.single(length);
+        for i in 0..length {
+            volatility[i] = (variance[i] * annualization).sqrt();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::Array1;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_hv_alternating_returns() {
+        // Log returns alternate between +1% and -1%: the sample variance over 4 returns is 4 * 0.01^2 / 3.
+        let close = Array1::from_shape_fn(9, |i| 100.0 * if i % 2 == 0 { 1.0 } else { 0.01_f64.exp() });
+        let input_data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(close),
+            volume: None,
+        };
+
+        let result = HV::new().calculate(&input_data, json!({ "period": 4, "annualization": 252.0 })).unwrap();
+
+        if let OutputData::SingleSeries(hv_values) = result {
+            assert!(hv_values.iter().take(4).all(|value| value.is_nan()));
+            let expected = (4.0 * 0.0001 / 3.0 * 252.0_f64).sqrt();
+            for i in 4..9 {
+                assert!((hv_values[i] - expected).abs() < 1e-12, "HV mismatch at index {}", i);
+            }
+            assert!(HV::new().calculate(&input_data, json!({ "period": 1 })).is_err());
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::{rolling_midpoint_into, validate_parameter_within_data_length};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct IchimokuParams {
+    #[serde(default = "default_tenkan_period")]
+    pub tenkan_period: usize,
+    #[serde(default = "default_kijun_period")]
+    pub kijun_period: usize,
+    #[serde(default = "default_senkou_b_period")]
+    pub senkou_b_period: usize,
+    /// Bars the senkou spans are plotted ahead and the chikou span behind.
+    #[serde(default = "default_displacement")]
+    pub displacement: usize,
+}
+
+fn default_tenkan_period() -> usize { 9 }
+fn default_kijun_period() -> usize { 26 }
+fn default_senkou_b_period() -> usize { 52 }
+fn default_displacement() -> usize { 26 }
+
+impl IParameter for IchimokuParams {}
+
+/// Ichimoku Kinko Hyo. `tenkan`, `kijun` and `senkou_b` are the high/low midpoints of their periods,
+/// `senkou_a` is the mean of `tenkan` and `kijun`, and `chikou` is the close. Every series is aligned
+/// to the bar it was calculated on; `Indicator::displacement` reports that the senkou spans belong
+/// `displacement` bars later and the chikou span `displacement` bars earlier.
+pub struct Ichimoku {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::HIGH, BarField::LOW, BarField::CLOSE],
+        vec![
+            ParamRule::Required("tenkan_period"),
+            ParamRule::Required("kijun_period"),
+            ParamRule::Required("senkou_b_period"),
+            ParamRule::Required("displacement"),
+            ParamRule::PositiveInteger("tenkan_period"),
+            ParamRule::PositiveInteger("kijun_period"),
+            ParamRule::PositiveInteger("senkou_b_period"),
+            ParamRule::PositiveInteger("displacement"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "tenkan_period", BarField::HIGH))),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "kijun_period", BarField::HIGH))),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "senkou_b_period", BarField::HIGH))),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::TrendIdentification));
+    groups.insert(Group::UseCase(UseCase::SupportResistanceLevels));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::StatisticalMethods));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Leading));
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    groups.insert(Group::OutputFormat(OutputFormat::MultiLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Absolute));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Long));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Advanced));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Positional));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Raw));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Statistical));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Crossovers));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::PeakThroughLevels));
+    groups
+}
+
+impl Ichimoku {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for Ichimoku {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for Ichimoku {
+    fn short_name(&self) -> &'static str {
+        "ICHIMOKU"
+    }
+
+    fn name(&self) -> &'static str {
+        "Ichimoku Kinko Hyo"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn displacement(&self, series: &str, params: &Value) -> Result<isize, IndicatorError> {
+        let params: IchimokuParams = serde_json::from_value(params.clone())
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        let displacement = params.displacement as isize;
+        match series {
+            "tenkan" | "kijun" => Ok(0),
+            "senkou_a" | "senkou_b" => Ok(displacement),
+            "chikou" => Ok(-displacement),
+            _ => Err(IndicatorError::InvalidInput(format!("Output series '{}' does not exist.", series))),
+        }
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: IchimokuParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let length = high.len();
+        let [tenkan, kijun, senkou_a, senkou_b, chikou] = out.multi(["tenkan", "kijun", "senkou_a", "senkou_b", "chikou"], length);
+        rolling_midpoint_into(high, low, params.tenkan_period, tenkan);
+        rolling_midpoint_into(high, low, params.kijun_period, kijun);
+        rolling_midpoint_into(high, low, params.senkou_b_period, senkou_b);
+        for i in 0..length {
+            senkou_a[i] = (tenkan[i] + kijun[i]) / T::cast(2.0);
+        }
+        chikou.assign(&close);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::{array, s};
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_ichimoku_expected_values() {
+        let input_data = InputData {
+            open: None,
+            high: Some(array![10.0, 12.0, 11.0, 13.0, 14.0]),
+            low: Some(array![8.0, 9.0, 7.0, 10.0, 12.0]),
+            close: Some(array![9.0, 11.0, 8.0, 12.0, 13.0]),
+            volume: None,
+        };
+        let params = json!({ "tenkan_period": 2, "kijun_period": 3, "senkou_b_period": 4, "displacement": 2 });
+
+        let result = Ichimoku::new().calculate(&input_data, params.clone()).unwrap();
+
+        if let OutputData::MultiSeries(ref series) = result {
+            assert!(series["tenkan"][0].is_nan() && series["kijun"][1].is_nan() && series["senkou_b"][2].is_nan());
+            assert_eq!(series["tenkan"].slice(s![1..]), array![10.0, 9.5, 10.0, 12.0]);
+            assert_eq!(series["kijun"].slice(s![2..]), array![9.5, 10.0, 10.5]);
+            assert_eq!(series["senkou_a"].slice(s![2..]), array![9.5, 10.0, 11.25]);
+            assert_eq!(series["senkou_b"].slice(s![3..]), array![10.0, 10.5]);
+            assert_eq!(series["chikou"], array![9.0, 11.0, 8.0, 12.0, 13.0]);
+        } else {
+            panic!("Unexpected output format");
+        }
+
+        let ichimoku = Ichimoku::new();
+        let displacement = |name| Indicator::<f64>::displacement(&ichimoku, name, &params).unwrap();
+        assert_eq!(displacement("kijun"), 0);
+        assert_eq!(displacement("senkou_b"), 2);
+        assert_eq!(displacement("chikou"), -2);
+        assert!(Indicator::<f64>::displacement(&ichimoku, "cloud", &params).is_err());
+
+        // The senkou spans project past the last bar and the chikou span is plotted in the past.
+        let senkou_b = result.displaced_series("senkou_b", displacement("senkou_b")).unwrap();
+        assert_eq!(senkou_b.len(), 7);
+        assert_eq!(senkou_b.slice(s![5..]), array![10.0, 10.5]);
+        let chikou = result.displaced_series("chikou", displacement("chikou")).unwrap();
+        assert_eq!(chikou.slice(s![..3]), array![8.0, 12.0, 13.0]);
+        assert!(chikou[3].is_nan() && chikou[4].is_nan());
+    }
+}
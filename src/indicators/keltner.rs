@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use crate::indicators::atr::ATR;
+use crate::indicators::utils::{exponential_moving_average_into, validate_parameter_within_data_length};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct KeltnerParams {
+    /// Period of the EMA of the close that forms the middle band.
+    #[serde(default = "default_period")]
+    pub period: usize,
+    #[serde(default = "default_atr_period")]
+    pub atr_period: usize,
+    /// Distance of the outer bands from the middle, in ATRs.
+    #[serde(default = "default_multiplier")]
+    pub multiplier: f64,
+}
+
+fn default_period() -> usize { 20 }
+fn default_atr_period() -> usize { 10 }
+fn default_multiplier() -> f64 { 2.0 }
+
+impl IParameter for KeltnerParams {}
+
+/// EMA of the close with bands `multiplier` ATRs above and below.
+pub struct Keltner {
+    groups: HashSet<Group>,
+    validator: Validator,
+    atr: ATR,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::HIGH, BarField::LOW, BarField::CLOSE],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::Required("atr_period"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::PositiveInteger("atr_period"),
+            ParamRule::PositiveNumber("multiplier"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::CLOSE))),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "atr_period", BarField::CLOSE))),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::VolatilityMeasurement));
+    groups.insert(Group::UseCase(UseCase::TrendIdentification));
+    groups.insert(Group::UseCase(UseCase::SupportResistanceLevels));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Averaging));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    groups.insert(Group::OutputFormat(OutputFormat::Band));
+    groups.insert(Group::OutputFormat(OutputFormat::Absolute));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Intermediate));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Volatile));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Exponential));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Averaging));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::ThresholdLevels));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Crossovers));
+    groups
+}
+
+impl Keltner {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator, atr: ATR::new() }
+    }
+}
+
+impl Default for Keltner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for Keltner {
+    fn short_name(&self) -> &'static str {
+        "KELTNER"
+    }
+
+    fn name(&self) -> &'static str {
+        "Keltner Channels"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: KeltnerParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let length = close.len();
+        self.atr.calculate_view_into(data, json!({ "period": params.atr_period }), out, scratch)?;
+        let multiplier = T::cast(params.multiplier);
+        // The first buffer still holds the ATR: move the band offsets out of it before the EMA
+        // overwrites it.
+        let [middle_band, upper_band, lower_band] = out.multi(["middle_band", "upper_band", "lower_band"], length);
+        for i in 0..length {
+            upper_band[i] = middle_band[i] * multiplier;
+        }
+        exponential_moving_average_into(close, params.period, middle_band);
+        for i in 0..length {
+            lower_band[i] = middle_band[i] - upper_band[i];
+            upper_band[i] = middle_band[i] + upper_band[i];
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::indicators::utils::exponential_moving_average;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_keltner_is_ema_plus_minus_atr() {
+        let input_data = InputData {
+            open: None,
+            high: Some(array![48.70, 48.72, 48.90, 48.87, 48.82, 49.05, 49.20, 49.35]),
+            low: Some(array![47.79, 48.14, 48.39, 48.37, 48.24, 48.64, 48.94, 48.86]),
+            close: Some(array![48.16, 48.61, 48.75, 48.63, 48.74, 49.03, 49.07, 49.32]),
+            volume: None,
+        };
+
+        let atr = ATR::new().calculate(&input_data, json!({ "period": 3 })).unwrap();
+        let result = Keltner::new().calculate(&input_data, json!({ "period": 4, "atr_period": 3, "multiplier": 1.5 })).unwrap();
+
+        if let (OutputData::MultiSeries(bands), OutputData::SingleSeries(atr)) = (result, atr) {
+            let ema = exponential_moving_average(input_data.close.as_ref().unwrap().view(), 4);
+            assert!(bands["middle_band"][2].is_nan() && bands["upper_band"][2].is_nan());
+            assert!(bands["middle_band"][3] == ema[3] && bands["lower_band"][3] == ema[3] - 1.5 * atr[3]);
+            for i in 3..8 {
+                assert!((bands["upper_band"][i] - (ema[i] + 1.5 * atr[i])).abs() < 1e-12);
+                assert!((bands["lower_band"][i] - (ema[i] - 1.5 * atr[i])).abs() < 1e-12);
+            }
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
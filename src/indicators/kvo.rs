@@ -0,0 +1,219 @@
+use std::collections::HashSet;
+use ndarray::s;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::exponential_moving_average_into;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct KVOParams {
+    #[serde(default = "default_short_period")]
+    pub short_period: usize,
+    #[serde(default = "default_long_period")]
+    pub long_period: usize,
+    #[serde(default = "default_signal_period")]
+    pub signal_period: usize,
+}
+
+fn default_short_period() -> usize { 34 }
+fn default_long_period() -> usize { 55 }
+fn default_signal_period() -> usize { 13 }
+
+impl IParameter for KVOParams {}
+
+pub struct KVO {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::HIGH, BarField::LOW, BarField::CLOSE, BarField::VOLUME],
+        vec![
+            ParamRule::Required("short_period"),
+            ParamRule::Required("long_period"),
+            ParamRule::Required("signal_period"),
+            ParamRule::PositiveInteger("short_period"),
+            ParamRule::PositiveInteger("long_period"),
+            ParamRule::PositiveInteger("signal_period"),
+            ParamRule::CorrectPeriod { left: "short_period", right: "long_period" },
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::VolumeConfirmation));
+    groups.insert(Group::UseCase(UseCase::TrendIdentification));
+    groups.insert(Group::UseCase(UseCase::ReversalDetection));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::VolumeWeighted));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Oscillation));
+    groups.insert(Group::DataInputType(DataInputType::PriceVolumeCombined));
+    groups.insert(Group::SignalType(SignalType::Leading));
+    groups.insert(Group::OutputFormat(OutputFormat::MultiLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Histogram));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Long));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Advanced));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::MarketSuitability(MarketSuitability::RangeBound));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Positional));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Exponential));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Differential));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Crossovers));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Divergence));
+    groups
+}
+
+impl KVO {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for KVO {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for KVO {
+    fn short_name(&self) -> &'static str {
+        "KVO"
+    }
+
+    fn name(&self) -> &'static str {
+        "Klinger Volume Oscillator"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: KVOParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+        let volume = data.get_by_bar_field(&BarField::VOLUME).unwrap();
+
+        let length = high.len();
+        let (short_period, long_period, signal_period) = (params.short_period, params.long_period, params.signal_period);
+        if long_period + signal_period > length {
+            return Err(IndicatorError::InvalidParameters(format!(
+                "Parameters 'long_period' + 'signal_period' exceed data length ({} > {})", long_period + signal_period, length
+            )));
+        }
+
+        // Volume force from the second bar on. The trend follows H + L + C; the cumulative measure
+        // restarts from the previous bar's range whenever the trend flips.
+        let [volume_force, short_ema, long_ema] = scratch.arrays(length - 1);
+        let (mut trend, mut cumulative_measure) = (T::zero(), T::zero());
+        for i in 1..length {
+            let new_trend = if high[i] + low[i] + close[i] > high[i - 1] + low[i - 1] + close[i - 1] { T::one() } else { -T::one() };
+            let daily_measure = high[i] - low[i];
+            cumulative_measure = if new_trend == trend {
+                cumulative_measure + daily_measure
+            } else {
+                high[i - 1] - low[i - 1] + daily_measure
+            };
+            trend = new_trend;
+            volume_force[i - 1] = if cumulative_measure == T::zero() {
+                T::zero()
+            } else {
+                volume[i] * (T::cast(2.0) * (daily_measure / cumulative_measure - T::one())).abs() * trend * T::cast(100.0)
+            };
+        }
+        exponential_moving_average_into(volume_force.view(), short_period, short_ema);
+        exponential_moving_average_into(volume_force.view(), long_period, long_ema);
+
+        let [kvo, signal] = out.multi(["kvo", "signal"], length);
+        kvo.fill(T::nan());
+        signal.fill(T::nan());
+        for i in long_period..length {
+            kvo[i] = short_ema[i - 1] - long_ema[i - 1];
+        }
+
+        // Signal line: EMA of the oscillator, seeded with the mean of its first `signal_period` values.
+        let first_signal = long_period + signal_period - 1;
+        let alpha = T::cast(2.0 / (signal_period as f64 + 1.0));
+        signal[first_signal] = kvo.slice(s![long_period..=first_signal]).mean().unwrap();
+
+        for i in (first_signal + 1)..length {
+            signal[i] = (kvo[i] - signal[i - 1]) * alpha + signal[i - 1];
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::Array1;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    fn input_data(length: usize) -> InputData {
+        let close = Array1::from_shape_fn(length, |i| 100.0 + (i as f64 * 0.5).sin() * 3.0);
+        InputData {
+            open: None,
+            high: Some(&close + 1.0),
+            low: Some(&close - 1.0),
+            close: Some(close),
+            volume: Some(Array1::from_shape_fn(length, |i| 1000.0 + i as f64 * 10.0)),
+        }
+    }
+
+    #[test]
+    fn test_kvo_warm_up() {
+        let params = json!({ "short_period": 3, "long_period": 5, "signal_period": 2 });
+        let result = KVO::new().calculate(&input_data(20), params).unwrap();
+
+        if let OutputData::MultiSeries(values) = result {
+            let (kvo, signal) = (&values["kvo"], &values["signal"]);
+            assert!(kvo[4].is_nan() && !kvo[5].is_nan());
+            assert!(signal[5].is_nan() && !signal[6].is_nan());
+            assert!((signal[6] - (kvo[5] + kvo[6]) / 2.0).abs() < 1e-9);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+
+    #[test]
+    fn test_kvo_is_positive_while_force_builds() {
+            let close = Array1::from_shape_fn(12, |i| 100.0 + i as f64);
+        let data = InputData {
+            open: None,
+            high: Some(&close + 1.0),
+            low: Some(&close - 1.0),
+            close: Some(close),
+            volume: Some(Array1::from_elem(12, 1000.0)),
+        };
+
+        let params = json!({ "short_period": 2, "long_period": 4, "signal_period": 2 });
+        let result = KVO::new().calculate(&data, params.clone()).unwrap();
+
+        assert!(KVO::new().calculate(&input_data(5), params).is_err());
+
+        if let OutputData::MultiSeries(values) = result {
+            // With a constant range the force on the k-th bar of the trend is 100 * V * |2 * (1 / k - 1)|,
+            // so it keeps growing and the short EMA stays above the long one.
+            assert!(values["kvo"].iter().skip(4).all(|&value| value > 0.0));
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
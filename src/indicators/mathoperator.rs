@@ -0,0 +1,162 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::GenericInputDataView;
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MathematicalBasis, OutputFormat, SignalType, SmoothingTechnique, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::models::price_source::PriceSource;
+use crate::validation::validator::{IParameter, Validator};
+
+/// Element-wise arithmetic between two input series.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mult,
+    Div,
+}
+
+impl Operator {
+    pub const ALL: [Operator; 4] = [Operator::Add, Operator::Sub, Operator::Mult, Operator::Div];
+
+    pub fn apply<T: Float>(&self, left: T, right: T) -> T {
+        match self {
+            Operator::Add => left + right,
+            Operator::Sub => left - right,
+            Operator::Mult => left * right,
+            Operator::Div => left / right,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MathOperatorParams {
+    #[serde(default = "default_left_source")]
+    pub left_source: PriceSource,
+    #[serde(default = "default_right_source")]
+    pub right_source: PriceSource,
+}
+
+// TA-Lib's defaults for its two-input math operators.
+fn default_left_source() -> PriceSource { PriceSource::High }
+fn default_right_source() -> PriceSource { PriceSource::Low }
+
+impl IParameter for MathOperatorParams {}
+
+/// TA-Lib's ADD, SUB, MULT and DIV: `left_source` combined bar by bar with `right_source`.
+/// Division by zero follows IEEE rules, giving infinities or NaN.
+pub struct MathOperator {
+    operator: Operator,
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(vec![], vec![])
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::PriceTransformation));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::TransformAnalysis));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Coincident));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Absolute));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Basic));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Raw));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Differential));
+    groups
+}
+
+impl MathOperator {
+    pub fn new(operator: Operator) -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { operator, groups, validator }
+    }
+}
+
+impl<T: Float> Indicator<T> for MathOperator {
+    fn short_name(&self) -> &'static str {
+        match self.operator {
+            Operator::Add => "ADD",
+            Operator::Sub => "SUB",
+            Operator::Mult => "MULT",
+            Operator::Div => "DIV",
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self.operator {
+            Operator::Add => "Vector Arithmetic Add",
+            Operator::Sub => "Vector Arithmetic Subtraction",
+            Operator::Mult => "Vector Arithmetic Mult",
+            Operator::Div => "Vector Arithmetic Div",
+        }
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: MathOperatorParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate_params(data, &params)?;
+
+        let left = params.left_source.series(data)?;
+        let right = params.right_source.series(data)?;
+        if left.len() != right.len() {
+            return Err(IndicatorError::InvalidInput("Input data series of the bars must have the same length.".to_string()));
+        }
+
+        let result = out.single(left.len());
+        for i in 0..left.len() {
+            result[i] = self.operator.apply(left[i], right[i]);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_math_operator_expected_values() {
+        let input_data = InputData {
+            open: None,
+            high: Some(array![12.0, 10.0, 9.0]),
+            low: Some(array![8.0, 10.0, 0.0]),
+            close: Some(array![10.0, 10.0, 4.0]),
+            volume: None,
+        };
+
+        let expected = [
+            (Operator::Add, array![20.0, 20.0, 9.0]),
+            (Operator::Sub, array![4.0, 0.0, 9.0]),
+            (Operator::Mult, array![96.0, 100.0, 0.0]),
+            (Operator::Div, array![1.5, 1.0, f64::INFINITY]),
+        ];
+        for (operator, expected) in expected {
+            if let OutputData::SingleSeries(values) = MathOperator::new(operator).calculate(&input_data, json!({})).unwrap() {
+                assert_eq!(values, expected);
+            } else {
+                panic!("Unexpected output format");
+            }
+        }
+
+        let params = json!({ "left_source": "close", "right_source": "median" });
+        let result = MathOperator::new(Operator::Sub).calculate(&input_data, params).unwrap();
+        assert_eq!(result.series("real").unwrap(), array![0.0, 0.0, -0.5]);
+        assert!(MathOperator::new(Operator::Add).calculate(&input_data, json!({ "left_source": "volume" })).is_err());
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::GenericInputDataView;
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MathematicalBasis, OutputFormat, SignalType, SmoothingTechnique, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::models::price_source::PriceSource;
+use crate::validation::validator::{IParameter, Validator};
+
+/// Element-wise function applied to an input series.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    Acos,
+    Asin,
+    Atan,
+    Ceil,
+    Cos,
+    Cosh,
+    Exp,
+    Floor,
+    Ln,
+    Log10,
+    Sin,
+    Sinh,
+    Sqrt,
+    Tan,
+    Tanh,
+}
+
+impl Function {
+    pub const ALL: [Function; 15] = [
+        Function::Acos, Function::Asin, Function::Atan, Function::Ceil, Function::Cos, Function::Cosh, Function::Exp,
+        Function::Floor, Function::Ln, Function::Log10, Function::Sin, Function::Sinh, Function::Sqrt, Function::Tan,
+        Function::Tanh,
+    ];
+
+    pub fn apply<T: Float>(&self, value: T) -> T {
+        match self {
+            Function::Acos => value.acos(),
+            Function::Asin => value.asin(),
+            Function::Atan => value.atan(),
+            Function::Ceil => value.ceil(),
+            Function::Cos => value.cos(),
+            Function::Cosh => value.cosh(),
+            Function::Exp => value.exp(),
+            Function::Floor => value.floor(),
+            Function::Ln => value.ln(),
+            Function::Log10 => value.log10(),
+            Function::Sin => value.sin(),
+            Function::Sinh => value.sinh(),
+            Function::Sqrt => value.sqrt(),
+            Function::Tan => value.tan(),
+            Function::Tanh => value.tanh(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MathTransformParams {
+    #[serde(default = "default_source")]
+    pub source: PriceSource,
+}
+
+fn default_source() -> PriceSource { PriceSource::Close }
+
+impl IParameter for MathTransformParams {}
+
+/// TA-Lib's math transforms (LN, SQRT, SIN, ...) of `source`, bar by bar. Values outside a
+/// function's domain give NaN.
+pub struct MathTransform {
+    function: Function,
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(vec![], vec![])
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::PriceTransformation));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::TransformAnalysis));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Coincident));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Absolute));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Basic));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Raw));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Differential));
+    groups
+}
+
+impl MathTransform {
+    pub fn new(function: Function) -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { function, groups, validator }
+    }
+}
+
+impl<T: Float> Indicator<T> for MathTransform {
+    fn short_name(&self) -> &'static str {
+        match self.function {
+            Function::Acos => "ACOS",
+            Function::Asin => "ASIN",
+            Function::Atan => "ATAN",
+            Function::Ceil => "CEIL",
+            Function::Cos => "COS",
+            Function::Cosh => "COSH",
+            Function::Exp => "EXP",
+            Function::Floor => "FLOOR",
+            Function::Ln => "LN",
+            Function::Log10 => "LOG10",
+            Function::Sin => "SIN",
+            Function::Sinh => "SINH",
+            Function::Sqrt => "SQRT",
+            Function::Tan => "TAN",
+            Function::Tanh => "TANH",
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self.function {
+            Function::Acos => "Vector Trigonometric ACos",
+            Function::Asin => "Vector Trigonometric ASin",
+            Function::Atan => "Vector Trigonometric ATan",
+            Function::Ceil => "Vector Ceil",
+            Function::Cos => "Vector Trigonometric Cos",
+            Function::Cosh => "Vector Trigonometric Cosh",
+            Function::Exp => "Vector Arithmetic Exp",
+            Function::Floor => "Vector Floor",
+            Function::Ln => "Vector Log Natural",
+            Function::Log10 => "Vector Log10",
+            Function::Sin => "Vector Trigonometric Sin",
+            Function::Sinh => "Vector Trigonometric Sinh",
+            Function::Sqrt => "Vector Square Root",
+            Function::Tan => "Vector Trigonometric Tan",
+            Function::Tanh => "Vector Trigonometric Tanh",
+        }
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: MathTransformParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate_params(data, &params)?;
+
+        let values = params.source.series(data)?;
+
+        let result = out.single(values.len());
+        for i in 0..values.len() {
+            result[i] = self.function.apply(values[i]);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_math_transform_expected_values() {
+        let input_data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(array![0.25, 1.0, 4.0]),
+            volume: Some(array![100.0, 1000.0, -1.0]),
+        };
+
+        let result = MathTransform::new(Function::Sqrt).calculate(&input_data, json!({})).unwrap();
+        if let OutputData::SingleSeries(values) = result {
+            assert_eq!(values, array![0.5, 1.0, 2.0]);
+        } else {
+            panic!("Unexpected output format");
+        }
+
+        let log10 = MathTransform::new(Function::Log10).calculate(&input_data, json!({ "source": "volume" })).unwrap();
+        let log10 = log10.series("real").unwrap();
+        assert_eq!(log10.slice(ndarray::s![..2]), array![2.0, 3.0]);
+        // Outside the domain.
+        assert!(log10[2].is_nan());
+        let acos = MathTransform::new(Function::Acos).calculate(&input_data, json!({})).unwrap();
+        assert_eq!(acos.series("real").unwrap()[1], 0.0);
+        assert!(acos.series("real").unwrap()[2].is_nan());
+        assert!(MathTransform::new(Function::Ln).calculate(&input_data, json!({ "source": "open" })).is_err());
+    }
+}
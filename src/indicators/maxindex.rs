@@ -0,0 +1,86 @@
+use std::collections::HashSet;
+use serde_json::Value;
+use crate::indicators::minmax::{create_groups, create_validator, MinMaxParams};
+use crate::indicators::utils::{rolling_extremum_index_into, RollingExtremum};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{Group, OutputFormat};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::Validator;
+
+/// Bar index (from the start of the input) of the highest close of the last `period` bars. Ties
+/// go to the newest bar.
+pub struct MAXINDEX {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+impl MAXINDEX {
+    pub fn new() -> Self {
+        let groups = create_groups(OutputFormat::SingleLine);
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for MAXINDEX {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for MAXINDEX {
+    fn short_name(&self) -> &'static str {
+        "MAXINDEX"
+    }
+
+    fn name(&self) -> &'static str {
+        "Index of Highest Value over a Period"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: MinMaxParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        rolling_extremum_index_into(close, RollingExtremum::max(params.period), out.single(close.len()));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_maxindex_expected_values() {
+        let input_data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(array![3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0]),
+            volume: None,
+        };
+
+        let result = MAXINDEX::new().calculate(&input_data, json!({ "period": 3 })).unwrap();
+
+        if let OutputData::SingleSeries(values) = result {
+            assert!(values[0].is_nan() && values[1].is_nan());
+            assert_eq!(values.slice(ndarray::s![2..]), array![2.0, 2.0, 4.0, 5.0, 5.0]);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::HashSet;
+use serde_json::Value;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::Validator;
+
+pub struct MedPrice {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::PriceTransformation));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Averaging));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Coincident));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Absolute));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Long));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Basic));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::MarketSuitability(MarketSuitability::RangeBound));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Positional));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Raw));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Averaging));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Patterns));
+
+    groups
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::HIGH, BarField::LOW],
+        vec![]
+    )
+}
+
+impl MedPrice {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for MedPrice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for MedPrice {
+    fn short_name(&self) -> &'static str {
+        "MEDPRICE"
+    }
+
+    fn name(&self) -> &'static str {
+        "Median Price"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, _params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        self.validator.validate_data(data)?;
+
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+        let med_price = out.single(high.len());
+        for i in 0..high.len() {
+            med_price[i] = (high[i] + low[i]) / T::cast(2.0);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_med_price_expected_value() {
+        let input_data = InputData {
+            open: None,
+            high: Some(array![1.5, 2.5, 3.5]),
+            low: Some(array![0.5, 1.5, 2.0]),
+            close: None,
+            volume: None,
+        };
+
+        let result = MedPrice::new().calculate(&input_data, json!({})).unwrap();
+
+        if let OutputData::SingleSeries(med_price_values) = result {
+            assert_eq!(med_price_values, array![1.0, 2.0, 2.75]);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+
+    #[test]
+    fn test_med_price_requires_low() {
+        let input_data = InputData {
+            open: None,
+            high: Some(array![1.5, 2.5, 3.5]),
+            low: None,
+            close: None,
+            volume: None,
+        };
+
+        assert!(MedPrice::new().calculate(&input_data, json!({})).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::validate_parameter_within_data_length;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct MFIParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+}
+
+fn default_period() -> usize { 14 }
+
+impl IParameter for MFIParams {}
+
+pub struct MFI {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::HIGH, BarField::LOW, BarField::CLOSE, BarField::VOLUME],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::HIGH))),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::MomentumDetection));
+    groups.insert(Group::UseCase(UseCase::VolumeConfirmation));
+    groups.insert(Group::UseCase(UseCase::ReversalDetection));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Oscillation));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::VolumeWeighted));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::RatioBased));
+    groups.insert(Group::DataInputType(DataInputType::PriceVolumeCombined));
+    groups.insert(Group::SignalType(SignalType::Leading));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Percentage));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Intermediate));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::MarketSuitability(MarketSuitability::RangeBound));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Raw));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Ratio));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::ThresholdLevels));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Divergence));
+    groups
+}
+
+impl MFI {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for MFI {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for MFI {
+    fn short_name(&self) -> &'static str {
+        "MFI"
+    }
+
+    fn name(&self) -> &'static str {
+        "Money Flow Index"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: MFIParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+        let volume = data.get_by_bar_field(&BarField::VOLUME).unwrap();
+
+        let length = high.len();
+        let period = params.period;
+        let [positive_flow, negative_flow] = scratch.arrays(length);
+        positive_flow.fill(T::zero());
+        negative_flow.fill(T::zero());
+        let three = T::cast(3.0);
+        for i in 1..length {
+            let typical_price = (high[i] + low[i] + close[i]) / three;
+            let previous_typical_price = (high[i - 1] + low[i - 1] + close[i - 1]) / three;
+            let money_flow = typical_price * volume[i];
+            if typical_price > previous_typical_price {
+                positive_flow[i] = money_flow;
+            } else if typical_price < previous_typical_price {
+                negative_flow[i] = money_flow;
+            }
+        }
+
+        // Each value covers the money flows of the last `period` price changes.
+        let mfi = out.single(length);
+        mfi.fill(T::nan());
+        let (mut positive_sum, mut negative_sum) = (T::zero(), T::zero());
+        for i in 1..length {
+            positive_sum += positive_flow[i];
+            negative_sum += negative_flow[i];
+            if i > period {
+                positive_sum -= positive_flow[i - period];
+                negative_sum -= negative_flow[i - period];
+            }
+            if i >= period {
+                let total = positive_sum + negative_sum;
+                mfi[i] = if total == T::zero() { T::zero() } else { T::cast(100.0) * positive_sum / total };
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_mfi_expected_value() {
+        let input_data = InputData {
+            open: None,
+            high: Some(array![11.0, 12.0, 11.5, 12.5]),
+            low: Some(array![9.0, 10.0, 9.5, 10.5]),
+            close: Some(array![10.0, 11.0, 10.5, 11.5]),
+            volume: Some(array![100.0, 100.0, 200.0, 100.0]),
+        };
+
+        let result = MFI::new().calculate(&input_data, json!({ "period": 2 })).unwrap();
+
+        if let OutputData::SingleSeries(mfi_values) = result {
+            // Typical prices 10, 11, 10.5, 11.5: flows +1100, -2100, +1150.
+            assert!(mfi_values[0].is_nan() && mfi_values[1].is_nan());
+            assert!((mfi_values[2] - 100.0 * 1100.0 / 3200.0).abs() < 1e-12);
+            assert!((mfi_values[3] - 100.0 * 1150.0 / 3250.0).abs() < 1e-12);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+
+    #[test]
+    fn test_mfi_is_zero_without_volume() {
+        let input_data = InputData {
+            open: None,
+            high: Some(array![11.0, 12.0, 11.5]),
+            low: Some(array![9.0, 10.0, 9.5]),
+            close: Some(array![10.0, 11.0, 10.5]),
+            volume: Some(array![0.0, 0.0, 0.0]),
+        };
+
+        let result = MFI::new().calculate(&input_data, json!({ "period": 2 })).unwrap();
+
+        if let OutputData::SingleSeries(mfi_values) = result {
+            assert_eq!(mfi_values[2], 0.0);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::{rolling_midpoint_into, validate_parameter_within_data_length};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct MidPointParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+}
+
+fn default_period() -> usize { 14 }
+
+impl IParameter for MidPointParams {}
+
+pub struct MidPoint {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::CLOSE],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::CLOSE))),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    // Use Case
+    groups.insert(Group::UseCase(UseCase::PriceTransformation));
+    groups.insert(Group::UseCase(UseCase::SupportResistanceLevels));
+    // Mathematical Basis
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Averaging));
+    // Data Input Type
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    // Signal Type
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    // Output Format
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Absolute));
+    // Timeframe Focus
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    // Complexity Level
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Basic));
+    // Market Suitability
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::MarketSuitability(MarketSuitability::RangeBound));
+    // Trading Strategy Suitability
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    // Smoothing Technique
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Raw));
+    // Calculation Methodology
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Averaging));
+    // Signal Interpretation
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Crossovers));
+    groups
+}
+
+impl MidPoint {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for MidPoint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for MidPoint {
+    fn short_name(&self) -> &'static str {
+        "MIDPOINT"
+    }
+
+    fn name(&self) -> &'static str {
+        "MidPoint over period"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: MidPointParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+        let mid_point = out.single(close.len());
+        rolling_midpoint_into(close, close, params.period, mid_point);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_mid_point_expected_value() {
+        let input_data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(array![10.0, 12.0, 8.0, 11.0, 13.0]),
+            volume: None,
+        };
+
+        let result = MidPoint::new().calculate(&input_data, json!({ "period": 3 })).unwrap();
+
+        if let OutputData::SingleSeries(mid_point_values) = result {
+            assert!(mid_point_values[0].is_nan() && mid_point_values[1].is_nan());
+            assert_eq!(mid_point_values.slice(ndarray::s![2..]), array![10.0, 10.0, 10.5]);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
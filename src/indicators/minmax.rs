@@ -0,0 +1,131 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::{rolling_extremes_into, validate_parameter_within_data_length};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct MinMaxParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+}
+
+fn default_period() -> usize { 30 }
+
+impl IParameter for MinMaxParams {}
+
+pub(crate) fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::CLOSE],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::CLOSE))),
+        ],
+    )
+}
+
+/// Groups of the rolling close extremes, which differ only in how many lines they return.
+pub(crate) fn create_groups(lines: OutputFormat) -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::SupportResistanceLevels));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::StatisticalMethods));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    groups.insert(Group::OutputFormat(lines));
+    groups.insert(Group::OutputFormat(OutputFormat::Absolute));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Basic));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::MarketSuitability(MarketSuitability::RangeBound));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Raw));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Statistical));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::PeakThroughLevels));
+    groups
+}
+
+/// Lowest (`min`) and highest (`max`) close of the last `period` bars.
+pub struct MINMAX {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+impl MINMAX {
+    pub fn new() -> Self {
+        let groups = create_groups(OutputFormat::MultiLine);
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for MINMAX {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for MINMAX {
+    fn short_name(&self) -> &'static str {
+        "MINMAX"
+    }
+
+    fn name(&self) -> &'static str {
+        "Lowest and Highest Values over a Period"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: MinMaxParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let [min, max] = out.multi(["min", "max"], close.len());
+        rolling_extremes_into(close, close, params.period, max, min);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_minmax_expected_values() {
+        let input_data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(array![3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0]),
+            volume: None,
+        };
+
+        let result = MINMAX::new().calculate(&input_data, json!({ "period": 3 })).unwrap();
+
+        if let OutputData::MultiSeries(output) = result {
+            let (min, max) = (output.get("min").unwrap(), output.get("max").unwrap());
+            assert!(min[0].is_nan() && min[1].is_nan() && max[0].is_nan() && max[1].is_nan());
+            assert_eq!(min.slice(ndarray::s![2..]), array![1.0, 1.0, 1.0, 1.0, 2.0]);
+            assert_eq!(max.slice(ndarray::s![2..]), array![4.0, 4.0, 5.0, 9.0, 9.0]);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::HashSet;
+use serde_json::Value;
+use crate::indicators::minmax::{create_groups, create_validator, MinMaxParams};
+use crate::indicators::utils::{rolling_extremum_index_into, RollingExtremum};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{Group, OutputFormat};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::Validator;
+
+/// Bar indices (from the start of the input) of the lowest (`min_index`) and highest (`max_index`)
+/// close of the last `period` bars. Ties go to the newest bar.
+pub struct MINMAXINDEX {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+impl MINMAXINDEX {
+    pub fn new() -> Self {
+        let groups = create_groups(OutputFormat::MultiLine);
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for MINMAXINDEX {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for MINMAXINDEX {
+    fn short_name(&self) -> &'static str {
+        "MINMAXINDEX"
+    }
+
+    fn name(&self) -> &'static str {
+        "Indexes of Lowest and Highest Values over a Period"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: MinMaxParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let [min_index, max_index] = out.multi(["min_index", "max_index"], close.len());
+        rolling_extremum_index_into(close, RollingExtremum::min(params.period), min_index);
+        rolling_extremum_index_into(close, RollingExtremum::max(params.period), max_index);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_minmaxindex_expected_values() {
+        let input_data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(array![3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0]),
+            volume: None,
+        };
+
+        let result = MINMAXINDEX::new().calculate(&input_data, json!({ "period": 3 })).unwrap();
+
+        if let OutputData::MultiSeries(output) = result {
+            let (min_index, max_index) = (output.get("min_index").unwrap(), output.get("max_index").unwrap());
+            assert!(min_index[1].is_nan() && max_index[1].is_nan());
+            assert_eq!(min_index.slice(ndarray::s![2..]), array![1.0, 3.0, 3.0, 3.0, 6.0]);
+            assert_eq!(max_index.slice(ndarray::s![2..]), array![2.0, 2.0, 4.0, 5.0, 5.0]);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
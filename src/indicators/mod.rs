@@ -1,10 +1,65 @@
 pub mod ad;
-mod adosc;
-mod utils;
-mod adx;
-mod adxr;
-mod apo;
-mod aroon;
-mod atr;
-mod avgprice;
-mod bbands;
\ No newline at end of file
+pub mod adosc;
+pub mod utils;
+pub mod adx;
+pub mod adxr;
+pub mod apo;
+pub mod aroon;
+pub mod atr;
+pub mod atrstop;
+pub mod avgprice;
+pub mod bbands;
+pub mod bbandspb;
+pub mod bop;
+pub mod cci;
+pub mod chandelier;
+pub mod cmf;
+pub mod cmo;
+pub mod donchian;
+pub mod efi;
+pub mod emv;
+pub mod envelope;
+pub mod ewma;
+pub mod garch;
+pub mod garmanklass;
+pub mod gjrgarch;
+pub mod hv;
+pub mod ichimoku;
+pub mod keltner;
+pub mod kvo;
+pub mod mathoperator;
+pub mod mathtransform;
+pub mod max;
+pub mod maxindex;
+pub mod medprice;
+pub mod mfi;
+pub mod midpoint;
+pub mod midprice;
+pub mod min;
+pub mod minindex;
+pub mod minmax;
+pub mod minmaxindex;
+pub mod mom;
+pub mod natr;
+pub mod nvi;
+pub mod obv;
+pub mod parkinson;
+pub mod pvi;
+pub mod roc;
+pub mod rocp;
+pub mod rocr;
+pub mod rocr100;
+pub mod rogerssatchell;
+pub mod sar;
+pub mod sarext;
+pub mod sum;
+pub mod supertrend;
+pub mod trix;
+pub mod typprice;
+pub mod ultosc;
+pub mod vosc;
+pub mod vwap;
+pub mod wclprice;
+pub mod yangzhang;
+#[cfg(test)]
+mod conformance;
@@ -0,0 +1,147 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use crate::indicators::atr::ATR;
+use crate::indicators::utils::validate_parameter_within_data_length;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct NATRParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+    /// Bars per year to annualize by (`sqrt(annualization)`). 1 keeps the per-bar, TA-Lib value.
+    #[serde(default = "default_annualization")]
+    pub annualization: f64,
+}
+
+fn default_period() -> usize { 14 }
+fn default_annualization() -> f64 { 1.0 }
+
+impl IParameter for NATRParams {}
+
+/// ATR as a percentage of the close.
+pub struct NATR {
+    groups: HashSet<Group>,
+    validator: Validator,
+    atr: ATR,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::CLOSE],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::CLOSE))),
+            ParamRule::PositiveNumber("annualization"),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::VolatilityMeasurement));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Averaging));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::RatioBased));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Percentage));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Basic));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::MarketSuitability(MarketSuitability::RangeBound));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Volatile));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Exponential));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Ratio));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::ThresholdLevels));
+    groups
+}
+
+impl NATR {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator, atr: ATR::new() }
+    }
+}
+
+impl Default for NATR {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for NATR {
+    fn short_name(&self) -> &'static str {
+        "NATR"
+    }
+
+    fn name(&self) -> &'static str {
+        "Normalized Average True Range"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: NATRParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let length = close.len();
+        self.atr.calculate_view_into(data, json!({ "period": params.period }), out, scratch)?;
+        let natr = out.single(length);
+        let scale = T::cast(100.0 * params.annualization.sqrt());
+        for i in 0..length {
+            natr[i] = natr[i] / close[i] * scale;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_natr_is_atr_over_close() {
+        let input_data = InputData {
+            open: None,
+            high: Some(array![48.70, 48.72, 48.90, 48.87, 48.82]),
+            low: Some(array![47.79, 48.14, 48.39, 48.37, 48.24]),
+            close: Some(array![48.16, 48.61, 48.75, 48.63, 48.74]),
+            volume: None,
+        };
+
+        let atr = ATR::new().calculate(&input_data, json!({ "period": 3 })).unwrap();
+        let natr = NATR::new().calculate(&input_data, json!({ "period": 3 })).unwrap();
+        let annualized = NATR::new().calculate(&input_data, json!({ "period": 3, "annualization": 4.0 })).unwrap();
+
+        if let (OutputData::SingleSeries(atr), OutputData::SingleSeries(natr), OutputData::SingleSeries(annualized)) = (atr, natr, annualized) {
+            assert!(natr[1].is_nan());
+            for i in 2..5 {
+                assert!((natr[i] - atr[i] / input_data.close.as_ref().unwrap()[i] * 100.0).abs() < 1e-12);
+                assert!((annualized[i] - 2.0 * natr[i]).abs() < 1e-12);
+            }
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::{rolling_mean_into, validate_parameter_within_data_length};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct ParkinsonParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+    /// Bars per year; the variance is multiplied by it before taking the square root.
+    #[serde(default = "default_annualization")]
+    pub annualization: f64,
+}
+
+fn default_period() -> usize { 20 }
+fn default_annualization() -> f64 { 252.0 }
+
+impl IParameter for ParkinsonParams {}
+
+/// Parkinson (1980) high-low volatility: `ln(H / L)^2 / (4 ln 2)` averaged over `period` bars,
+/// annualized.
+pub struct Parkinson {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::HIGH, BarField::LOW],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::HIGH))),
+            ParamRule::PositiveNumber("annualization"),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::VolatilityMeasurement));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::StatisticalMethods));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Percentage));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Long));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Intermediate));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Volatile));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Stable));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Positional));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::SimpleAverage));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Statistical));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::ThresholdLevels));
+    groups
+}
+
+impl Parkinson {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for Parkinson {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for Parkinson {
+    fn short_name(&self) -> &'static str {
+        "PARKINSON"
+    }
+
+    fn name(&self) -> &'static str {
+        "Parkinson Volatility"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: ParkinsonParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+
+        let length = high.len();
+        let annualization = T::cast(params.annualization);
+        let [terms, variance] = scratch.arrays(length);
+        let scale = T::cast(4.0 * std::f64::consts::LN_2);
+        for i in 0..length {
+            let range = (high[i] / low[i]).ln();
+            terms[i] = range * range / scale;
+        }
+        rolling_mean_into(terms.view(), 0, params.period, variance);
+
+        let volatility = out.single(length);
+        for i in 0..length {
+            volatility[i] = (variance[i] * annualization).sqrt();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::Array1;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_parkinson_constant_range() {
+        let low = Array1::from_elem(5, 100.0);
+        let input_data = InputData {
+            open: None,
+            high: Some(&low * 0.02_f64.exp()),
+            low: Some(low),
+            close: None,
+            volume: None,
+        };
+
+        let result = Parkinson::new().calculate(&input_data, json!({ "period": 3, "annualization": 1.0 })).unwrap();
+
+        if let OutputData::SingleSeries(values) = result {
+            assert!(values[1].is_nan());
+            let expected = (0.0004 / (4.0 * std::f64::consts::LN_2)).sqrt();
+            assert!((values[2] - expected).abs() < 1e-12);
+            assert!((values[4] - expected).abs() < 1e-12);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use std::collections::HashSet;
+use ndarray::{Array1, ArrayView1};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::validate_parameter_within_data_length;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct ROCParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+}
+
+fn default_period() -> usize { 10 }
+
+impl IParameter for ROCParams {}
+
+/// Applies `rate(close, previous)` to every close and the close `period` bars before it. Like
+/// TA-Lib, a previous close of 0 gives 0.
+pub(crate) fn rate_of_change_into<T: Float>(close: ArrayView1<T>, period: usize, out: &mut Array1<T>, rate: impl Fn(T, T) -> T) {
+    for i in 0..close.len() {
+        out[i] = if i < period {
+            T::nan()
+        } else if close[i - period] == T::zero() {
+            T::zero()
+        } else {
+            rate(close[i], close[i - period])
+        };
+    }
+}
+
+pub(crate) fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::CLOSE],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::CLOSE))),
+        ],
+    )
+}
+
+/// Groups of the rate of change variants, which differ only in how the change is expressed.
+pub(crate) fn create_groups(output: OutputFormat) -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::MomentumDetection));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::RatioBased));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Oscillation));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Leading));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(output));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Basic));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Raw));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Ratio));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Crossovers));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Divergence));
+    groups
+}
+
+/// Rate of change in percent: `(close / previous - 1) * 100`.
+pub struct ROC {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+impl ROC {
+    pub fn new() -> Self {
+        let groups = create_groups(OutputFormat::Percentage);
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for ROC {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for ROC {
+    fn short_name(&self) -> &'static str {
+        "ROC"
+    }
+
+    fn name(&self) -> &'static str {
+        "Rate of Change"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: ROCParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let hundred = T::cast(100.0);
+        rate_of_change_into(close, params.period, out.single(close.len()), |value, previous| ((value / previous) - T::one()) * hundred);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_roc_expected_values() {
+        let input_data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(array![10.0, 0.0, 12.5, 8.0, 25.0]),
+            volume: None,
+        };
+
+        let result = ROC::new().calculate(&input_data, json!({ "period": 2 })).unwrap();
+
+        if let OutputData::SingleSeries(roc) = result {
+            assert!(roc[0].is_nan() && roc[1].is_nan());
+            // A previous close of 0 gives 0 rather than infinity.
+            assert_eq!(roc.slice(ndarray::s![2..]), array![25.0, 0.0, 100.0]);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
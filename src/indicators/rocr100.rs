@@ -0,0 +1,85 @@
+use std::collections::HashSet;
+use serde_json::Value;
+use crate::indicators::roc::{create_groups, create_validator, rate_of_change_into, ROCParams};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{Group, OutputFormat};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::Validator;
+
+/// Rate of change as a ratio scaled to 100: `close / previous * 100`.
+pub struct ROCR100 {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+impl ROCR100 {
+    pub fn new() -> Self {
+        let groups = create_groups(OutputFormat::Percentage);
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for ROCR100 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for ROCR100 {
+    fn short_name(&self) -> &'static str {
+        "ROCR100"
+    }
+
+    fn name(&self) -> &'static str {
+        "Rate of Change Ratio 100 Scale"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: ROCParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let hundred = T::cast(100.0);
+        rate_of_change_into(close, params.period, out.single(close.len()), |value, previous| (value / previous) * hundred);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_rocr100_expected_values() {
+        let input_data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(array![10.0, 8.0, 12.5, 2.0, 25.0]),
+            volume: None,
+        };
+
+        let result = ROCR100::new().calculate(&input_data, json!({ "period": 2 })).unwrap();
+
+        if let OutputData::SingleSeries(values) = result {
+            assert!(values[0].is_nan() && values[1].is_nan());
+            assert_eq!(values.slice(ndarray::s![2..]), array![125.0, 25.0, 200.0]);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::{rogers_satchell_term, rolling_mean_into, validate_parameter_within_data_length};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct RogersSatchellParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+    /// Bars per year; the variance is multiplied by it before taking the square root.
+    #[serde(default = "default_annualization")]
+    pub annualization: f64,
+}
+
+fn default_period() -> usize { 20 }
+fn default_annualization() -> f64 { 252.0 }
+
+impl IParameter for RogersSatchellParams {}
+
+/// Rogers-Satchell (1991) volatility: `ln(H / C) ln(H / O) + ln(L / C) ln(L / O)` averaged over
+/// `period` bars, annualized. Unbiased under drift.
+pub struct RogersSatchell {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::OPEN, BarField::HIGH, BarField::LOW, BarField::CLOSE],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::HIGH))),
+            ParamRule::PositiveNumber("annualization"),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::VolatilityMeasurement));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::StatisticalMethods));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Percentage));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Long));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Advanced));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Volatile));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Stable));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Positional));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::SimpleAverage));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Statistical));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::ThresholdLevels));
+    groups
+}
+
+impl RogersSatchell {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for RogersSatchell {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for RogersSatchell {
+    fn short_name(&self) -> &'static str {
+        "ROGERSSATCHELL"
+    }
+
+    fn name(&self) -> &'static str {
+        "Rogers-Satchell Volatility"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: RogersSatchellParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let open = data.get_by_bar_field(&BarField::OPEN).unwrap();
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let length = high.len();
+        let annualization = T::cast(params.annualization);
+        let [terms, variance] = scratch.arrays(length);
+        for i in 0..length {
+            terms[i] = rogers_satchell_term(open[i], high[i], low[i], close[i]);
+        }
+        rolling_mean_into(terms.view(), 0, params.period, variance);
+
+        let volatility = out.single(length);
+        for i in 0..length {
+            volatility[i] = (variance[i] * annualization).sqrt();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::Array1;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_rogers_satchell_closed_form() {
+        // Opens and closes at the low, highs 2% above: each bar contributes 0.02^2.
+        let open = Array1::from_elem(3, 50.0);
+        let input_data = InputData {
+            high: Some(&open * 0.02_f64.exp()),
+            low: Some(open.clone()),
+            close: Some(open.clone()),
+            open: Some(open),
+            volume: None,
+        };
+
+        let result = RogersSatchell::new().calculate(&input_data, json!({ "period": 3, "annualization": 4.0 })).unwrap();
+
+        if let OutputData::SingleSeries(values) = result {
+            assert!((values[2] - (0.0004 * 4.0_f64).sqrt()).abs() < 1e-12);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,341 @@
+use std::collections::HashSet;
+use std::ops::Bound;
+use ndarray::{Array1, ArrayView1};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+/// Acceleration factor of one trend direction: it starts at `initial` and grows by `step` on
+/// every new extreme, up to `maximum`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Acceleration<T: Float = f64> {
+    pub initial: T,
+    pub step: T,
+    pub maximum: T,
+}
+
+/// Parabolic SAR settings, with TA-Lib SAREXT's meaning.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SarSettings<T: Float = f64> {
+    /// 0 picks the first trend from the first two bars. A positive value starts long with the
+    /// stop at that value, a negative one starts short with the stop at its absolute value.
+    pub start_value: T,
+    /// Fraction of the stop it is moved away from price by when the trend reverses.
+    pub offset_on_reverse: T,
+    pub long: Acceleration<T>,
+    pub short: Acceleration<T>,
+}
+
+impl<T: Float> SarSettings<T> {
+    /// Wilder's original SAR: the same acceleration for both directions, and no start value or
+    /// offset.
+    pub fn symmetric(acceleration: T, maximum: T) -> Self {
+        let acceleration = Acceleration { initial: acceleration, step: acceleration, maximum };
+        SarSettings { start_value: T::zero(), offset_on_reverse: T::zero(), long: acceleration, short: acceleration }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Trend<T: Float> {
+    is_long: bool,
+    sar: T,
+    extreme: T,
+    long_factor: T,
+    short_factor: T,
+}
+
+/// Parabolic stop-and-reverse over bars that arrive one at a time. SAR and SAREXT run it over
+/// whole series, so live and historical stops are the same.
+#[derive(Debug, Clone)]
+pub struct ParabolicSar<T: Float = f64> {
+    settings: SarSettings<T>,
+    previous: Option<(T, T)>,
+    trend: Option<Trend<T>>,
+}
+
+impl<T: Float> ParabolicSar<T> {
+    pub fn new(settings: SarSettings<T>) -> Result<Self, IndicatorError> {
+        for acceleration in [settings.long, settings.short] {
+            let valid = acceleration.initial > T::zero()
+                && acceleration.step >= T::zero()
+                && acceleration.initial <= acceleration.maximum
+                && acceleration.step <= acceleration.maximum;
+            if !valid {
+                return Err(IndicatorError::InvalidParameters("Accelerations must be positive and at most their maximum".to_string()));
+            }
+        }
+        if settings.offset_on_reverse < T::zero() {
+            return Err(IndicatorError::InvalidParameters("The offset on reverse must not be negative".to_string()));
+        }
+        Ok(ParabolicSar { settings, previous: None, trend: None })
+    }
+
+    /// Whether the current trend is long, once there is one.
+    pub fn is_long(&self) -> Option<bool> {
+        self.trend.map(|trend| trend.is_long)
+    }
+
+    fn start(&self, first_high: T, first_low: T, high: T, low: T) -> Trend<T> {
+        let start_value = self.settings.start_value;
+        let is_long = if start_value == T::zero() {
+            // Short only if the second bar has minus directional movement.
+            let down_move = first_low - low;
+            let up_move = high - first_high;
+            !(down_move > T::zero() && up_move < down_move)
+        } else {
+            start_value > T::zero()
+        };
+        let sar = match (start_value == T::zero(), is_long) {
+            (true, true) => first_low,
+            (true, false) => first_high,
+            (false, _) => start_value.abs(),
+        };
+        Trend {
+            is_long,
+            sar,
+            extreme: if is_long { high } else { low },
+            long_factor: self.settings.long.initial,
+            short_factor: self.settings.short.initial,
+        }
+    }
+
+    /// Adds a bar and returns its stop: positive while long, negative while short. The first bar
+    /// only seeds the trend and returns `None`.
+    pub fn push(&mut self, high: T, low: T) -> Option<T> {
+        let (mut previous_high, mut previous_low) = self.previous.replace((high, low))?;
+        if self.trend.is_none() {
+            self.trend = Some(self.start(previous_high, previous_low, high, low));
+            // The first stop is kept within the second bar's range only.
+            (previous_high, previous_low) = (high, low);
+        }
+        let settings = self.settings;
+        let trend = self.trend.as_mut().unwrap();
+
+        let stop = if trend.is_long {
+            if low <= trend.sar {
+                // Reverse at the extreme point, kept above the last two highs.
+                trend.is_long = false;
+                let mut sar = trend.extreme.max(previous_high).max(high);
+                sar += sar * settings.offset_on_reverse;
+                trend.short_factor = settings.short.initial;
+                trend.extreme = low;
+                trend.sar = (sar + trend.short_factor * (low - sar)).max(previous_high).max(high);
+                -sar
+            } else {
+                let sar = trend.sar;
+                if high > trend.extreme {
+                    trend.extreme = high;
+                    trend.long_factor = (trend.long_factor + settings.long.step).min(settings.long.maximum);
+                }
+                trend.sar = (sar + trend.long_factor * (trend.extreme - sar)).min(previous_low).min(low);
+                sar
+            }
+        } else if high >= trend.sar {
+            // Reverse at the extreme point, kept below the last two lows.
+            trend.is_long = true;
+            let mut sar = trend.extreme.min(previous_low).min(low);
+            sar -= sar * settings.offset_on_reverse;
+            trend.long_factor = settings.long.initial;
+            trend.extreme = high;
+            trend.sar = (sar + trend.long_factor * (high - sar)).min(previous_low).min(low);
+            sar
+        } else {
+            let sar = trend.sar;
+            if low < trend.extreme {
+                trend.extreme = low;
+                trend.short_factor = (trend.short_factor + settings.short.step).min(settings.short.maximum);
+            }
+            trend.sar = (sar + trend.short_factor * (trend.extreme - sar)).max(previous_high).max(high);
+            -sar
+        };
+        Some(stop)
+    }
+}
+
+/// Runs `sar` over all bars into `out`, NaN on the first bar.
+pub(crate) fn parabolic_sar_into<T: Float>(high: ArrayView1<T>, low: ArrayView1<T>, mut sar: ParabolicSar<T>, out: &mut Array1<T>) {
+    for i in 0..high.len() {
+        out[i] = sar.push(high[i], low[i]).unwrap_or(T::nan());
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SARParams {
+    #[serde(default = "default_acceleration")]
+    pub acceleration: f64,
+    #[serde(default = "default_maximum")]
+    pub maximum: f64,
+}
+
+fn default_acceleration() -> f64 { 0.02 }
+fn default_maximum() -> f64 { 0.2 }
+
+impl IParameter for SARParams {}
+
+impl SARParams {
+    pub fn settings<T: Float>(&self) -> SarSettings<T> {
+        SarSettings::symmetric(T::cast(self.acceleration), T::cast(self.maximum))
+    }
+}
+
+/// Parabolic SAR: a trailing stop below price in uptrends and above it in downtrends, which
+/// flips sides when price crosses it.
+pub struct SAR {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::HIGH, BarField::LOW],
+        vec![
+            ParamRule::Required("acceleration"),
+            ParamRule::Required("maximum"),
+            ParamRule::NumberInRange { name: "acceleration", min: Bound::Excluded(0.0), max: Bound::Unbounded },
+            ParamRule::NotGreaterThan { left: "acceleration", right: "maximum" },
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::TrendIdentification));
+    groups.insert(Group::UseCase(UseCase::ReversalDetection));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Averaging));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Absolute));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Intermediate));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Adaptive));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Differential));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Crossovers));
+    groups
+}
+
+impl SAR {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for SAR {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for SAR {
+    fn short_name(&self) -> &'static str {
+        "SAR"
+    }
+
+    fn name(&self) -> &'static str {
+        "Parabolic SAR"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: SARParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+
+        let sar = out.single(high.len());
+        parabolic_sar_into(high, low, ParabolicSar::new(params.settings())?, sar);
+        // SAR reports the stop without SAREXT's direction sign.
+        sar.mapv_inplace(|value| value.abs());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_sar_expected_values() {
+        let input_data = InputData {
+            open: None,
+            high: Some(array![10.0, 11.0, 12.0, 13.0, 12.5, 11.0]),
+            low: Some(array![9.0, 10.0, 11.0, 12.0, 11.5, 9.5]),
+            close: None,
+            volume: None,
+        };
+
+        let result = SAR::new().calculate(&input_data, json!({ "acceleration": 0.1, "maximum": 0.2 })).unwrap();
+
+        if let OutputData::SingleSeries(sar_values) = result {
+            // Long from the first low, 9, with the extreme at 11; then 9 + 0.1 * (11 - 9) = 9.2 capped
+            // by the lows, 9.2 + 0.2 * (12 - 9.2) = 9.76, ...
+            assert!(sar_values[0].is_nan());
+            assert_eq!(sar_values[1], 9.0);
+            assert!((sar_values[2] - 9.2).abs() < 1e-12);
+            assert!((sar_values[3] - 9.76).abs() < 1e-12);
+            assert!((sar_values[4] - (9.76 + 0.2 * (13.0 - 9.76))).abs() < 1e-12);
+            // The low of 9.5 breaks the stop: reverse to short at the extreme, 13.
+            assert_eq!(sar_values[5], 13.0);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+
+    #[test]
+    fn test_sar_stream_matches_batch() {
+        let high = array![10.0, 11.0, 12.0, 13.0, 12.5, 11.0, 10.5, 11.5];
+        let low = array![9.0, 10.0, 11.0, 12.0, 11.5, 9.5, 9.0, 10.0];
+        let input_data = InputData {
+            open: None,
+            high: Some(high.clone()),
+            low: Some(low.clone()),
+            close: None,
+            volume: None,
+        };
+        let batch = SAR::new().calculate(&input_data, json!({})).unwrap();
+
+        let mut stream = ParabolicSar::new(SarSettings::symmetric(0.02, 0.2)).unwrap();
+        let live: Vec<f64> = high.iter().zip(&low).map(|(&h, &l)| stream.push(h, l).map_or(f64::NAN, f64::abs)).collect();
+
+        let batch = batch.series("real").unwrap();
+        assert!(live[0].is_nan());
+        assert_eq!(&live[1..], &batch.as_slice().unwrap()[1..]);
+        assert_eq!(stream.is_long(), Some(false));
+    }
+
+    #[test]
+    fn test_sar_rejects_acceleration_out_of_range() {
+        let input_data = InputData {
+            open: None,
+            high: Some(array![2.0, 3.0]),
+            low: Some(array![1.0, 2.0]),
+            close: None,
+            volume: None,
+        };
+        assert!(SAR::new().calculate(&input_data, json!({ "acceleration": 0.0 })).is_err());
+        assert!(SAR::new().calculate(&input_data, json!({ "acceleration": 0.3, "maximum": 0.2 })).is_err());
+        assert!(SAR::new().calculate(&input_data, json!({ "acceleration": 0.2, "maximum": 0.2 })).is_ok());
+        assert!(ParabolicSar::new(SarSettings::symmetric(-0.1, 0.2)).is_err());
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::HashSet;
+use std::ops::Bound;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::sar::{parabolic_sar_into, Acceleration, ParabolicSar, SarSettings};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct SAREXTParams {
+    #[serde(default)]
+    pub start_value: f64,
+    #[serde(default)]
+    pub offset_on_reverse: f64,
+    #[serde(default = "default_acceleration")]
+    pub acceleration_init_long: f64,
+    #[serde(default = "default_acceleration")]
+    pub acceleration_long: f64,
+    #[serde(default = "default_maximum")]
+    pub acceleration_max_long: f64,
+    #[serde(default = "default_acceleration")]
+    pub acceleration_init_short: f64,
+    #[serde(default = "default_acceleration")]
+    pub acceleration_short: f64,
+    #[serde(default = "default_maximum")]
+    pub acceleration_max_short: f64,
+}
+
+fn default_acceleration() -> f64 { 0.02 }
+fn default_maximum() -> f64 { 0.2 }
+
+impl IParameter for SAREXTParams {}
+
+impl SAREXTParams {
+    pub fn settings<T: Float>(&self) -> SarSettings<T> {
+        SarSettings {
+            start_value: T::cast(self.start_value),
+            offset_on_reverse: T::cast(self.offset_on_reverse),
+            long: Acceleration {
+                initial: T::cast(self.acceleration_init_long),
+                step: T::cast(self.acceleration_long),
+                maximum: T::cast(self.acceleration_max_long),
+            },
+            short: Acceleration {
+                initial: T::cast(self.acceleration_init_short),
+                step: T::cast(self.acceleration_short),
+                maximum: T::cast(self.acceleration_max_short),
+            },
+        }
+    }
+}
+
+/// Parabolic SAR with separate long and short accelerations, a start value and an offset on
+/// reverse (see [`SarSettings`]). As in TA-Lib, the stop is negative while the trend is short.
+pub struct SAREXT {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    let positive = |name| ParamRule::NumberInRange { name, min: Bound::Excluded(0.0), max: Bound::Unbounded };
+    let not_negative = |name| ParamRule::NumberInRange { name, min: Bound::Included(0.0), max: Bound::Unbounded };
+    Validator::new(
+        vec![BarField::HIGH, BarField::LOW],
+        vec![
+            positive("acceleration_init_long"),
+            not_negative("acceleration_long"),
+            positive("acceleration_init_short"),
+            not_negative("acceleration_short"),
+            not_negative("offset_on_reverse"),
+            ParamRule::NotGreaterThan { left: "acceleration_init_long", right: "acceleration_max_long" },
+            ParamRule::NotGreaterThan { left: "acceleration_long", right: "acceleration_max_long" },
+            ParamRule::NotGreaterThan { left: "acceleration_init_short", right: "acceleration_max_short" },
+            ParamRule::NotGreaterThan { left: "acceleration_short", right: "acceleration_max_short" },
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::TrendIdentification));
+    groups.insert(Group::UseCase(UseCase::ReversalDetection));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Averaging));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Directional));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Advanced));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Adaptive));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Differential));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Crossovers));
+    groups
+}
+
+impl SAREXT {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for SAREXT {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for SAREXT {
+    fn short_name(&self) -> &'static str {
+        "SAREXT"
+    }
+
+    fn name(&self) -> &'static str {
+        "Parabolic SAR - Extended"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: SAREXTParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+
+        let sar = out.single(high.len());
+        parabolic_sar_into(high, low, ParabolicSar::new(params.settings())?, sar);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::indicators::sar::SAR;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    fn input_data() -> InputData {
+        InputData {
+            open: None,
+            high: Some(array![10.0, 11.0, 12.0, 13.0, 12.5, 11.0, 10.5]),
+            low: Some(array![9.0, 10.0, 11.0, 12.0, 11.5, 9.5, 9.0]),
+            close: None,
+            volume: None,
+        }
+    }
+
+    #[test]
+    fn test_sarext_signs_and_sar_equivalence() {
+        let sar = SAR::new().calculate(&input_data(), json!({})).unwrap();
+        let sarext = SAREXT::new().calculate(&input_data(), json!({})).unwrap();
+
+        if let (OutputData::SingleSeries(sar), OutputData::SingleSeries(sarext)) = (sar, sarext) {
+            assert!(sarext[0].is_nan());
+            assert!(sarext.iter().skip(1).take(4).all(|&value| value > 0.0));
+            assert!(sarext[5] < 0.0 && sarext[6] < 0.0);
+            for i in 1..7 {
+                assert_eq!(sarext[i].abs(), sar[i]);
+            }
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+
+    #[test]
+    fn test_sarext_start_value_and_offset() {
+        let params = json!({ "start_value": -12.0, "offset_on_reverse": 0.1, "acceleration_init_short": 0.1, "acceleration_max_short": 0.1 });
+        let result = SAREXT::new().calculate(&input_data(), params).unwrap();
+
+        if let OutputData::SingleSeries(sarext) = result {
+            // Starts short at 12; the next stop is 12 + 0.1 * (10 - 12) = 11.8.
+            assert_eq!(sarext[1], -12.0);
+            // The high of 12 reaches 11.8: reverse long at the extreme, 10, moved 10% lower.
+            assert!((sarext[2] - 9.0).abs() < 1e-12);
+        } else {
+            panic!("Unexpected output format");
+        }
+        let wrong = json!({ "acceleration_long": 0.5 });
+        assert!(SAREXT::new().calculate(&input_data(), wrong).is_err());
+    }
+}
@@ -0,0 +1,224 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::atr::AverageTrueRange;
+use crate::indicators::atrstop::TrailingStop;
+use crate::indicators::utils::validate_parameter_within_data_length;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Debug, Clone, Copy)]
+struct Bands<T: Float> {
+    upper: T,
+    lower: T,
+    is_long: bool,
+}
+
+/// SuperTrend over bars that arrive one at a time. Bands `multiplier` ATRs around the high-low
+/// midpoint only tighten until the close breaks them; the line is the lower band while long and
+/// the upper band while short. It starts short, like the common charting implementations.
+#[derive(Debug, Clone)]
+pub struct SuperTrendStop<T: Float = f64> {
+    atr: AverageTrueRange<T>,
+    multiplier: T,
+    previous_close: Option<T>,
+    bands: Option<Bands<T>>,
+}
+
+impl<T: Float> SuperTrendStop<T> {
+    pub fn new(period: usize, multiplier: T) -> Result<Self, IndicatorError> {
+        if multiplier <= T::zero() {
+            return Err(IndicatorError::InvalidParameters("The ATR multiplier must be positive".to_string()));
+        }
+        Ok(SuperTrendStop { atr: AverageTrueRange::new(period)?, multiplier, previous_close: None, bands: None })
+    }
+
+    /// Adds a bar and returns the SuperTrend line, once the ATR is available.
+    pub fn push(&mut self, high: T, low: T, close: T) -> Option<TrailingStop<T>> {
+        let previous_close = self.previous_close.replace(close);
+        let offset = self.multiplier * self.atr.push(high, low, close)?;
+        let middle = (high + low) / T::cast(2.0);
+        let (mut upper, mut lower) = (middle + offset, middle - offset);
+
+        let is_long = match (self.bands, previous_close) {
+            (Some(previous), Some(previous_close)) => {
+                // A band only moves away from price once the previous close has broken it.
+                if upper > previous.upper && previous_close <= previous.upper {
+                    upper = previous.upper;
+                }
+                if lower < previous.lower && previous_close >= previous.lower {
+                    lower = previous.lower;
+                }
+                if previous.is_long { close >= lower } else { close > upper }
+            }
+            _ => false,
+        };
+        self.bands = Some(Bands { upper, lower, is_long });
+        Some(TrailingStop { stop: if is_long { lower } else { upper }, is_long })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SuperTrendParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+    /// Distance of the bands from the high-low midpoint, in ATRs.
+    #[serde(default = "default_multiplier")]
+    pub multiplier: f64,
+}
+
+fn default_period() -> usize { 10 }
+fn default_multiplier() -> f64 { 3.0 }
+
+impl IParameter for SuperTrendParams {}
+
+/// SuperTrend: the `supertrend` line of `SuperTrendStop` on every bar and its `direction`, 1 in
+/// uptrends and -1 in downtrends.
+pub struct SuperTrend {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::HIGH, BarField::LOW, BarField::CLOSE],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::Required("multiplier"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::PositiveNumber("multiplier"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::CLOSE))),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::TrendIdentification));
+    groups.insert(Group::UseCase(UseCase::ReversalDetection));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Averaging));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    groups.insert(Group::OutputFormat(OutputFormat::MultiLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Absolute));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Intermediate));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Exponential));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Differential));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Crossovers));
+    groups
+}
+
+impl SuperTrend {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for SuperTrend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for SuperTrend {
+    fn short_name(&self) -> &'static str {
+        "SUPERTREND"
+    }
+
+    fn name(&self) -> &'static str {
+        "SuperTrend"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: SuperTrendParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let mut trend = SuperTrendStop::new(params.period, T::cast(params.multiplier))?;
+        let [supertrend, direction] = out.multi(["supertrend", "direction"], close.len());
+        for i in 0..close.len() {
+            let bar = trend.push(high[i], low[i], close[i]);
+            supertrend[i] = bar.map_or(T::nan(), |bar| bar.stop);
+            direction[i] = bar.map_or(T::nan(), |bar| bar.direction());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_supertrend_expected_values() {
+        // Every bar has a true range of 2, so with a one-bar ATR the bands sit 2 from the midpoint.
+        let input_data = InputData {
+            open: None,
+            high: Some(array![11.0, 11.5, 12.5, 14.0, 13.5, 12.0]),
+            low: Some(array![9.0, 9.5, 10.5, 12.0, 11.5, 10.0]),
+            close: Some(array![10.0, 10.5, 12.5, 13.5, 12.0, 10.5]),
+            volume: None,
+        };
+
+        let result = SuperTrend::new().calculate(&input_data, json!({ "period": 1, "multiplier": 1.0 })).unwrap();
+
+        if let OutputData::MultiSeries(series) = result {
+            // Short under the upper band, held at 12, until the close of 12.5 breaks it; then long
+            // on the lower band, held at 11, until the close of 10.5 falls through it.
+            assert_eq!(series["supertrend"], array![12.0, 12.0, 9.5, 11.0, 11.0, 13.0]);
+            assert_eq!(series["direction"], array![-1.0, -1.0, 1.0, 1.0, 1.0, -1.0]);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+
+    #[test]
+    fn test_supertrend_stream_matches_batch() {
+        let high = array![48.70, 48.72, 48.90, 48.87, 48.82, 49.05, 49.20, 48.10, 47.90, 48.60];
+        let low = array![47.79, 48.14, 48.39, 48.37, 48.24, 48.64, 48.94, 47.20, 47.10, 47.80];
+        let close = array![48.16, 48.61, 48.75, 48.63, 48.74, 49.03, 49.07, 47.35, 47.80, 48.55];
+        let input_data = InputData {
+            open: None,
+            high: Some(high.clone()),
+            low: Some(low.clone()),
+            close: Some(close.clone()),
+            volume: None,
+        };
+        let batch = SuperTrend::new().calculate(&input_data, json!({ "period": 3, "multiplier": 1.0 })).unwrap();
+
+        let mut stream = SuperTrendStop::new(3, 1.0).unwrap();
+        let live: Vec<Option<TrailingStop>> = (0..high.len()).map(|i| stream.push(high[i], low[i], close[i])).collect();
+
+        let (line, direction) = (batch.series("supertrend").unwrap(), batch.series("direction").unwrap());
+        assert_eq!(live[..2], [None, None]);
+        for i in 2..high.len() {
+            let bar = live[i].unwrap();
+            assert_eq!((bar.stop, bar.direction()), (line[i], direction[i]), "Mismatch at index {}", i);
+        }
+        assert!(SuperTrendStop::new(0, 1.0).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::roc::rate_of_change_into;
+use crate::indicators::utils::{exponential_moving_average_from_into, exponential_moving_average_into};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct TRIXParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+}
+
+fn default_period() -> usize { 30 }
+
+impl IParameter for TRIXParams {}
+
+/// TRIX: one-bar rate of change, in percent, of a triple EMA of the close. Each EMA is seeded with
+/// the SMA of the first `period` values of the one before, so the first value is on bar
+/// `3 * (period - 1) + 1`.
+pub struct TRIX {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+/// The triple EMA needs `3 * (period - 1) + 1` bars.
+fn validate_triple_ema_within_data_length(value: &Value, data: &dyn Candles) -> Result<(), IndicatorError> {
+    let period = value.get("period").and_then(|v| v.as_u64()).unwrap() as usize;
+    let input_length = data.field_len(&BarField::CLOSE).unwrap();
+    if 3 * (period - 1) >= input_length {
+        Err(IndicatorError::InvalidParameters(format!("Wrong parameter length. 3 * ('period' - 1) >= data length. ({} >= {})", 3 * (period - 1), input_length)))
+    } else {
+        Ok(())
+    }
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::CLOSE],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_triple_ema_within_data_length(value, data))),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::MomentumDetection));
+    groups.insert(Group::UseCase(UseCase::TrendIdentification));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Averaging));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Oscillation));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Percentage));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Long));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Intermediate));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Positional));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Exponential));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Ratio));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Crossovers));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Divergence));
+    groups
+}
+
+impl TRIX {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for TRIX {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for TRIX {
+    fn short_name(&self) -> &'static str {
+        "TRIX"
+    }
+
+    fn name(&self) -> &'static str {
+        "Triple Exponential Average Rate of Change"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: TRIXParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let period = params.period;
+        let [single, double, triple] = scratch.arrays(close.len());
+        exponential_moving_average_into(close, period, single);
+        exponential_moving_average_from_into(single.view(), period - 1, period, double);
+        exponential_moving_average_from_into(double.view(), 2 * (period - 1), period, triple);
+
+        let hundred = T::cast(100.0);
+        rate_of_change_into(triple.view(), 1, out.single(close.len()), |value, previous| ((value / previous) - T::one()) * hundred);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_trix_expected_values() {
+        let input_data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(array![1.0, 3.0, 5.0, 7.0, 9.0, 11.0]),
+            volume: None,
+        };
+
+        let result = TRIX::new().calculate(&input_data, json!({ "period": 2 })).unwrap();
+
+        if let OutputData::SingleSeries(trix) = result {
+            // On a straight line each EMA runs 1 below the one before, so the triple EMA is 4, 6
+            // and 8 on bars 3 to 5.
+            assert!(trix.slice(ndarray::s![..4]).iter().all(|value| value.is_nan()));
+            assert_eq!(trix[4], 50.0);
+            assert!((trix[5] - 100.0 / 3.0).abs() < 1e-12);
+            assert!(TRIX::new().calculate(&input_data, json!({ "period": 3 })).is_err());
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
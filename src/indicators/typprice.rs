@@ -0,0 +1,115 @@
+use std::collections::HashSet;
+use serde_json::Value;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::Validator;
+
+pub struct TypPrice {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::PriceTransformation));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Averaging));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Coincident));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Absolute));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Long));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Basic));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::MarketSuitability(MarketSuitability::RangeBound));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Positional));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Raw));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Averaging));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Patterns));
+
+    groups
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::HIGH, BarField::LOW, BarField::CLOSE],
+        vec![]
+    )
+}
+
+impl TypPrice {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for TypPrice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for TypPrice {
+    fn short_name(&self) -> &'static str {
+        "TYPPRICE"
+    }
+
+    fn name(&self) -> &'static str {
+        "Typical Price"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, _params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        self.validator.validate_data(data)?;
+
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+        let typ_price = out.single(high.len());
+        for i in 0..high.len() {
+            typ_price[i] = (high[i] + low[i] + close[i]) / T::cast(3.0);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_typ_price_expected_value() {
+        let input_data = InputData {
+            open: None,
+            high: Some(array![1.5, 2.5, 3.5]),
+            low: Some(array![0.5, 1.5, 2.0]),
+            close: Some(array![1.0, 2.5, 2.0]),
+            volume: None,
+        };
+
+        let result = TypPrice::new().calculate(&input_data, json!({})).unwrap();
+
+        if let OutputData::SingleSeries(typ_price_values) = result {
+            let expected = array![1.0, 6.5 / 3.0, 2.5];
+            for i in 0..expected.len() {
+                assert!((typ_price_values[i] - expected[i]).abs() < 1e-12, "Typical price mismatch at index {}", i);
+            }
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use std::collections::HashSet;
+use ndarray::s;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::{true_range, validate_parameter_within_data_length};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct ULTOSCParams {
+    #[serde(default = "default_period1")]
+    pub period1: usize,
+    #[serde(default = "default_period2")]
+    pub period2: usize,
+    #[serde(default = "default_period3")]
+    pub period3: usize,
+}
+
+fn default_period1() -> usize { 7 }
+fn default_period2() -> usize { 14 }
+fn default_period3() -> usize { 28 }
+
+impl IParameter for ULTOSCParams {}
+
+/// Ultimate Oscillator: the ratio of buying pressure (`close - min(low, previous close)`) to true
+/// range over three periods, averaged with weights 4, 2 and 1 and scaled to 0-100. As in TA-Lib,
+/// the periods are sorted so the shortest gets the largest weight, whatever order they are given in.
+pub struct ULTOSC {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::HIGH, BarField::LOW, BarField::CLOSE],
+        vec![
+            ParamRule::Required("period1"),
+            ParamRule::Required("period2"),
+            ParamRule::Required("period3"),
+            ParamRule::PositiveInteger("period1"),
+            ParamRule::PositiveInteger("period2"),
+            ParamRule::PositiveInteger("period3"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period1", BarField::CLOSE))),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period2", BarField::CLOSE))),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period3", BarField::CLOSE))),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::MomentumDetection));
+    groups.insert(Group::UseCase(UseCase::ReversalDetection));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Oscillation));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::RatioBased));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Leading));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Percentage));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Long));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Intermediate));
+    groups.insert(Group::MarketSuitability(MarketSuitability::RangeBound));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Volatile));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::SimpleAverage));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Ratio));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::PeakThroughLevels));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Divergence));
+    groups
+}
+
+impl ULTOSC {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for ULTOSC {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for ULTOSC {
+    fn short_name(&self) -> &'static str {
+        "ULTOSC"
+    }
+
+    fn name(&self) -> &'static str {
+        "Ultimate Oscillator"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: ULTOSCParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let length = close.len();
+        let mut periods = [params.period1, params.period2, params.period3];
+        periods.sort_unstable();
+
+        // Both need the previous close, so bar 0 has neither.
+        let [buying_pressure, ranges] = scratch.arrays(length);
+        for i in 1..length {
+            buying_pressure[i] = close[i] - low[i].min(close[i - 1]);
+            ranges[i] = true_range(high[i], low[i], Some(close[i - 1]));
+        }
+
+        let ultosc = out.single(length);
+        ultosc.fill(T::nan());
+        for i in periods[2]..length {
+            let mut total = T::zero();
+            for (weight, period) in [4.0, 2.0, 1.0].into_iter().zip(periods) {
+                let range = ranges.slice(s![i + 1 - period..=i]).sum();
+                if range != T::zero() {
+                    total += T::cast(weight) * (buying_pressure.slice(s![i + 1 - period..=i]).sum() / range);
+                }
+            }
+            ultosc[i] = T::cast(100.0) * (total / T::cast(7.0));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_ultosc_expected_values() {
+        let input_data = InputData {
+            open: None,
+            high: Some(array![11.0, 12.0, 12.0, 11.0]),
+            low: Some(array![9.0, 10.0, 10.0, 9.0]),
+            close: Some(array![10.0, 12.0, 10.0, 9.0]),
+            volume: None,
+        };
+
+        // Given out of order: sorted to 1, 2, 3.
+        let result = ULTOSC::new().calculate(&input_data, json!({ "period1": 3, "period2": 1, "period3": 2 })).unwrap();
+
+        if let OutputData::SingleSeries(ultosc) = result {
+            assert!(ultosc.slice(ndarray::s![..3]).iter().all(|value| value.is_nan()));
+            // Buying pressure 2, 0, 0 over true ranges 2, 2, 2.
+            assert_eq!(ultosc[3], 100.0 * ((4.0 * 0.0 + 2.0 * (0.0 / 4.0) + 2.0 / 6.0) / 7.0));
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
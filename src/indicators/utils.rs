@@ -1,45 +1,65 @@
-use ndarray::{s, Array1};
+use std::collections::VecDeque;
+use ndarray::{s, Array1, ArrayView1};
 use serde_json::Value;
-use crate::models::data::{BarField, InputData};
+use crate::models::data::{BarField, Candles};
+use crate::models::float::Float;
 use crate::models::indicator::IndicatorError;
 
-pub fn calculate_adl(
-    high: &Array1<f64>,
-    low: &Array1<f64>,
-    close: &Array1<f64>,
-    volume: &Array1<f64>,
-) -> Result<Array1<f64>, IndicatorError> {
-    let high_low_range = high - low;
-
-    // To avoid division by zero, create a mask where high_low_range == 0.0
-    let zero_range_mask = high_low_range.mapv(|x| x == 0.0);
-
-    // Compute Money Flow Multiplier (MFM)
-    let mfm_numerator = (close - low) - (high - close);
-    let mut mfm = &mfm_numerator / &high_low_range;
+pub fn calculate_adl<T: Float>(
+    high: ArrayView1<T>,
+    low: ArrayView1<T>,
+    close: ArrayView1<T>,
+    volume: ArrayView1<T>,
+) -> Result<Array1<T>, IndicatorError> {
+    let mut adl = Array1::<T>::zeros(high.len());
+    calculate_adl_into(high, low, close, volume, &mut adl)?;
+    Ok(adl)
+}
 
-    // Handle division by zero by setting MFM to zero where high_low_range == 0
-    mfm.iter_mut()
-        .zip(zero_range_mask.iter())
-        .for_each(|(mfm_value, &is_zero)| {
-            if is_zero {
-                *mfm_value = 0.0;
-            }
-        });
+pub fn calculate_adl_into<T: Float>(
+    high: ArrayView1<T>,
+    low: ArrayView1<T>,
+    close: ArrayView1<T>,
+    volume: ArrayView1<T>,
+    out: &mut Array1<T>,
+) -> Result<(), IndicatorError> {
+    let mut adl = T::zero();
+    for i in 0..high.len() {
+        let mfm = money_flow_multiplier(high[i], low[i], close[i]);
+
+        // Accumulate Money Flow Volume (MFV) into the ADL
+        adl += mfm * volume[i];
+        out[i] = adl;
+    }
 
-    // Compute Money Flow Volume (MFV)
-    let mfv = &mfm * volume;
+    Ok(())
+}
 
-    // Compute ADL as cumulative sum of MFV
-    let adl = cumulative_sum(&mfv);
+/// Money Flow Multiplier (MFM): where the close sits in the bar's range, from -1 at the low to 1
+/// at the high. Zero when high == low to avoid division by zero.
+pub fn money_flow_multiplier<T: Float>(high: T, low: T, close: T) -> T {
+    let high_low_range = high - low;
+    if high_low_range == T::zero() {
+        T::zero()
+    } else {
+        ((close - low) - (high - close)) / high_low_range
+    }
+}
 
-    Ok(adl)
+pub fn calculate_ema<T: Float>(
+    data: ArrayView1<T>,
+    period: usize,
+) -> Result<Array1<T>, IndicatorError> {
+    let mut ema = Array1::<T>::zeros(data.len());
+    calculate_ema_into(data, period, &mut ema)?;
+    Ok(ema)
 }
 
-pub fn calculate_ema(
-    data: &Array1<f64>,
+pub fn calculate_ema_into<T: Float>(
+    data: ArrayView1<T>,
     period: usize,
-) -> Result<Array1<f64>, IndicatorError> {
+    out: &mut Array1<T>,
+) -> Result<(), IndicatorError> {
     if period == 0 || period > data.len() {
         return Err(IndicatorError::InvalidParameters(
             "Invalid period for EMA calculation".to_string(),
@@ -47,89 +67,139 @@ pub fn calculate_ema(
     }
 
     let length = data.len();
-    let mut ema = Array1::<f64>::zeros(length);
-
-    let alpha = 2.0 / (period as f64 + 1.0);
+    let alpha = T::cast(2.0 / (period as f64 + 1.0));
 
     // Initialize EMA with the first data point
-    ema[period - 1] = data.slice(s![..period]).mean().unwrap();
+    out.slice_mut(s![..period - 1]).fill(T::zero());
+    out[period - 1] = data.slice(s![..period]).mean().unwrap();
 
     for i in period..length {
-        ema[i] = alpha * data[i] + (1.0 - alpha) * ema[i - 1];
+        out[i] = alpha * data[i] + (T::one() - alpha) * out[i - 1];
     }
 
-    Ok(ema)
+    Ok(())
 }
 
-pub fn cumulative_sum(input: &Array1<f64>) -> Array1<f64> {
+pub fn cumulative_sum<T: Float>(input: ArrayView1<T>) -> Array1<T> {
     let mut cumsum = Array1::zeros(input.len());
-    let mut sum = 0.0;
+    cumulative_sum_into(input, &mut cumsum);
+    cumsum
+}
+
+pub fn cumulative_sum_into<T: Float>(input: ArrayView1<T>, out: &mut Array1<T>) {
+    let mut sum = T::zero();
     for (i, &value) in input.iter().enumerate() {
         sum += value;
-        cumsum[i] = sum;
+        out[i] = sum;
     }
-    cumsum
 }
 
-pub fn calculate_true_range(
-    high: &Array1<f64>,
-    low: &Array1<f64>,
-    close: &Array1<f64>,
-) -> Result<Array1<f64>, IndicatorError> {
-    let length = high.len();
-    let mut tr = Array1::<f64>::zeros(length);
+pub fn calculate_true_range<T: Float>(
+    high: ArrayView1<T>,
+    low: ArrayView1<T>,
+    close: ArrayView1<T>,
+) -> Result<Array1<T>, IndicatorError> {
+    let mut tr = Array1::<T>::zeros(high.len());
+    calculate_true_range_into(high, low, close, &mut tr)?;
+    Ok(tr)
+}
 
-    for i in 0..length {
-        if i == 0 {
-            tr[i] = high[i] - low[i];
-        } else {
-            let hl = high[i] - low[i];
-            let hc = (high[i] - close[i - 1]).abs();
-            let lc = (low[i] - close[i - 1]).abs();
-            tr[i] = hl.max(hc).max(lc);
-        }
+pub fn calculate_true_range_into<T: Float>(
+    high: ArrayView1<T>,
+    low: ArrayView1<T>,
+    close: ArrayView1<T>,
+    out: &mut Array1<T>,
+) -> Result<(), IndicatorError> {
+    for i in 0..high.len() {
+        let previous_close = if i == 0 { None } else { Some(close[i - 1]) };
+        out[i] = true_range(high[i], low[i], previous_close);
     }
 
-    Ok(tr)
+    Ok(())
 }
 
+/// True range of one bar: its high-low range, widened to the previous close when there is one.
+pub fn true_range<T: Float>(high: T, low: T, previous_close: Option<T>) -> T {
+    match previous_close {
+        Some(previous_close) => {
+            let hl = high - low;
+            let hc = (high - previous_close).abs();
+            let lc = (low - previous_close).abs();
+            hl.max(hc).max(lc)
+        }
+        None => high - low,
+    }
+}
+
+/// True range that ignores the previous close on the first bar of every session, so overnight
+/// gaps do not count. `first_bars` marks those bars.
+pub fn calculate_session_true_range_into<T: Float>(
+    high: ArrayView1<T>,
+    low: ArrayView1<T>,
+    close: ArrayView1<T>,
+    first_bars: &[bool],
+    out: &mut Array1<T>,
+) -> Result<(), IndicatorError> {
+    calculate_true_range_into(high, low, close, out)?;
+    for (i, _) in first_bars.iter().enumerate().filter(|(_, &first)| first) {
+        out[i] = high[i] - low[i];
+    }
 
-fn get_signed_directional_movement(
-    target: &Array1<f64>,
-    comparative: &Array1<f64>,
-) -> Array1<f64> {
-    target.iter()
-        .zip(comparative.iter())
-        .map(|(&target, &comparative)| if target > comparative && target > 0.0 {target} else {0.0})
-        .collect::<Array1<f64>>()
+    Ok(())
 }
 
-pub fn calculate_directional_movements(
-    high: &Array1<f64>,
-    low: &Array1<f64>,
-) -> Result<(Array1<f64>, Array1<f64>), IndicatorError> {
-    let up_move = &high.slice(s![1..]) - &high.slice(s![..high.len() - 1]);
-    let down_move = &low.slice(s![..low.len() - 1]) - &low.slice(s![1..]);
+fn get_signed_directional_movement<T: Float>(target: T, comparative: T) -> T {
+    if target > comparative && target > T::zero() { target } else { T::zero() }
+}
 
-    let plus_dm = get_signed_directional_movement(&up_move, &down_move);
+pub fn calculate_directional_movements<T: Float>(
+    high: ArrayView1<T>,
+    low: ArrayView1<T>,
+) -> Result<(Array1<T>, Array1<T>), IndicatorError> {
+    let mut plus_dm = Array1::<T>::zeros(high.len());
+    let mut minus_dm = Array1::<T>::zeros(low.len());
+    calculate_directional_movements_into(high, low, &mut plus_dm, &mut minus_dm)?;
+    Ok((plus_dm, minus_dm))
+}
 
-    let minus_dm = get_signed_directional_movement(&down_move, &up_move);
+pub fn calculate_directional_movements_into<T: Float>(
+    high: ArrayView1<T>,
+    low: ArrayView1<T>,
+    plus_dm: &mut Array1<T>,
+    minus_dm: &mut Array1<T>,
+) -> Result<(), IndicatorError> {
+    if high.is_empty() {
+        return Ok(());
+    }
 
-    // Prepend a zero to align the output array lengths with the input
-    let mut plus_dm_full = Array1::<f64>::zeros(high.len());
-    plus_dm_full.slice_mut(s![1..]).assign(&plus_dm);
+    // The first bar has no previous bar to move from
+    plus_dm[0] = T::zero();
+    minus_dm[0] = T::zero();
 
-    let mut minus_dm_full = Array1::<f64>::zeros(low.len());
-    minus_dm_full.slice_mut(s![1..]).assign(&minus_dm);
+    for i in 1..high.len() {
+        let up_move = high[i] - high[i - 1];
+        let down_move = low[i - 1] - low[i];
+        plus_dm[i] = get_signed_directional_movement(up_move, down_move);
+        minus_dm[i] = get_signed_directional_movement(down_move, up_move);
+    }
 
-    Ok((plus_dm_full, minus_dm_full))
+    Ok(())
 }
 
 
-pub fn wilder_smoothing(
-    data: &Array1<f64>,
+pub fn wilder_smoothing<T: Float>(
+    data: ArrayView1<T>,
     period: usize,
-) -> Result<Array1<f64>, IndicatorError> {
+) -> Result<Array1<T>, IndicatorError> {
+    let mut smoothed = data.to_owned();
+    wilder_smoothing_in_place(&mut smoothed, period)?;
+    Ok(smoothed)
+}
+
+pub fn wilder_smoothing_in_place<T: Float>(
+    data: &mut Array1<T>,
+    period: usize,
+) -> Result<(), IndicatorError> {
     let length = data.len();
     if length < period {
         return Err(IndicatorError::InvalidInput(
@@ -137,42 +207,307 @@ pub fn wilder_smoothing(
         ));
     }
 
-    let mut smoothed = Array1::<f64>::zeros(length);
-
     // First smoothed value is the average of the first 'period' data points
     let initial_average = data.slice(s![..period]).mean().unwrap();
-    smoothed[period - 1] = initial_average;
+    data.slice_mut(s![..period - 1]).fill(T::zero());
+    data[period - 1] = initial_average;
 
     // Compute smoothed values for the rest of the data
     for i in period..length {
-        smoothed[i] = smoothed[i - 1] + (data[i] - smoothed[i - 1]) / period as f64;
+        data[i] = data[i - 1] + (data[i] - data[i - 1]) / T::cast(period as f64);
     }
 
-    Ok(smoothed)
+    Ok(())
+}
+
+/// Highest or lowest of the last `period` values pushed, with its index. A monotonic deque keeps
+/// only the values that can still become the extreme, so each push is amortised O(1) whatever the
+/// period. Ties go to the newest value, as in TA-Lib, unless `oldest_on_ties` is set. NaN values
+/// are skipped, like `f64::max` does, but still count towards the window.
+#[derive(Debug, Clone)]
+pub struct RollingExtremum<T: Float = f64> {
+    period: usize,
+    highest: bool,
+    oldest_on_ties: bool,
+    pushed: usize,
+    candidates: VecDeque<(usize, T)>,
+}
+
+impl<T: Float> RollingExtremum<T> {
+    pub fn max(period: usize) -> Self {
+        RollingExtremum { period, highest: true, oldest_on_ties: false, pushed: 0, candidates: VecDeque::with_capacity(period) }
+    }
+
+    pub fn min(period: usize) -> Self {
+        RollingExtremum { period, highest: false, oldest_on_ties: false, pushed: 0, candidates: VecDeque::with_capacity(period) }
+    }
+
+    /// Reports the oldest of equal extremes instead of the newest.
+    pub fn oldest_on_ties(mut self) -> Self {
+        self.oldest_on_ties = true;
+        self
+    }
+
+    /// Adds a value and returns the index (counting pushes from 0) and value of the extreme of the
+    /// last `period` values, or of all values while fewer have been pushed. `None` if they are all
+    /// NaN.
+    pub fn push(&mut self, value: T) -> Option<(usize, T)> {
+        while let Some(&(_, last)) = self.candidates.back().filter(|_| !value.is_nan()) {
+            let dominated = match (self.highest, self.oldest_on_ties) {
+                (true, false) => last <= value,
+                (true, true) => last < value,
+                (false, false) => last >= value,
+                (false, true) => last > value,
+            };
+            if !dominated {
+                break;
+            }
+            self.candidates.pop_back();
+        }
+        if !value.is_nan() {
+            self.candidates.push_back((self.pushed, value));
+        }
+        self.pushed += 1;
+        while self.candidates.front().is_some_and(|&(index, _)| index + self.period < self.pushed) {
+            self.candidates.pop_front();
+        }
+        self.candidates.front().copied()
+    }
+
+    /// Like [`RollingExtremum::push`], returning only the extreme value, NaN if there is none.
+    pub fn push_value(&mut self, value: T) -> T {
+        self.push(value).map_or(T::nan(), |(_, extreme)| extreme)
+    }
 }
 
-pub fn validate_parameter_within_data_length(value: &Value, data: &InputData, parameter: &str, bar_field: BarField) -> Result<(), IndicatorError> {
+/// Midpoint between the highest `high` and the lowest `low` of the last `period` bars, NaN
+/// during the first `period - 1` bars.
+pub fn rolling_midpoint_into<T: Float>(
+    high: ArrayView1<T>,
+    low: ArrayView1<T>,
+    period: usize,
+    out: &mut Array1<T>,
+) {
+    let (mut highest, mut lowest) = (RollingExtremum::max(period), RollingExtremum::min(period));
+    for i in 0..high.len() {
+        let high = highest.push_value(high[i]);
+        let low = lowest.push_value(low[i]);
+        out[i] = if i + 1 < period { T::nan() } else { (high + low) / T::cast(2.0) };
+    }
+}
+
+/// Highest `high` and lowest `low` of the last `period` bars, NaN during the first `period - 1`
+/// bars.
+pub fn rolling_extremes_into<T: Float>(
+    high: ArrayView1<T>,
+    low: ArrayView1<T>,
+    period: usize,
+    highest: &mut Array1<T>,
+    lowest: &mut Array1<T>,
+) {
+    let (mut highs, mut lows) = (RollingExtremum::max(period), RollingExtremum::min(period));
+    for i in 0..high.len() {
+        highest[i] = highs.push_value(high[i]);
+        lowest[i] = lows.push_value(low[i]);
+        if i + 1 < period {
+            highest[i] = T::nan();
+            lowest[i] = T::nan();
+        }
+    }
+}
+
+/// Extreme of the last `period` values, NaN during the first `period - 1` bars.
+pub fn rolling_extremum_into<T: Float>(values: ArrayView1<T>, mut extremum: RollingExtremum<T>, out: &mut Array1<T>) {
+    let period = extremum.period;
+    for i in 0..values.len() {
+        let value = extremum.push_value(values[i]);
+        out[i] = if i + 1 < period { T::nan() } else { value };
+    }
+}
+
+/// Index (into `values`) of the extreme of the last `period` values, NaN during the first
+/// `period - 1` bars.
+pub fn rolling_extremum_index_into<T: Float>(values: ArrayView1<T>, mut extremum: RollingExtremum<T>, out: &mut Array1<T>) {
+    let period = extremum.period;
+    for i in 0..values.len() {
+        let index = extremum.push(values[i]).map_or(T::nan(), |(index, _)| T::cast(index as f64));
+        out[i] = if i + 1 < period { T::nan() } else { index };
+    }
+}
+
+/// Sum of the last `period` values as a running total, NaN during the first `period - 1` bars.
+pub fn rolling_sum_into<T: Float>(values: ArrayView1<T>, period: usize, out: &mut Array1<T>) {
+    let mut total = T::zero();
+    for i in 0..values.len() {
+        total += values[i];
+        if i >= period {
+            total -= values[i - period];
+            if !total.is_finite() {
+                // A NaN or infinity may have left the window; sum it again.
+                total = values.slice(s![i + 1 - period..=i]).sum();
+            }
+        }
+        out[i] = if i + 1 < period { T::nan() } else { total };
+    }
+}
+
+/// Negative (`on_rising_volume == false`) or positive volume index, starting at 1000. The index
+/// follows the close's percentage change only on bars where volume falls (negative) or rises
+/// (positive) from the previous bar.
+pub fn volume_index_into<T: Float>(
+    close: ArrayView1<T>,
+    volume: ArrayView1<T>,
+    on_rising_volume: bool,
+    out: &mut Array1<T>,
+) {
+    let mut index = T::cast(1000.0);
+    for i in 0..close.len() {
+        if i > 0 && close[i - 1] != T::zero() {
+            let tracked = if on_rising_volume { volume[i] > volume[i - 1] } else { volume[i] < volume[i - 1] };
+            if tracked {
+                index += index * (close[i] - close[i - 1]) / close[i - 1];
+            }
+        }
+        out[i] = index;
+    }
+}
+
+/// Rogers-Satchell variance of one bar: `ln(H / C) ln(H / O) + ln(L / C) ln(L / O)`.
+pub fn rogers_satchell_term<T: Float>(open: T, high: T, low: T, close: T) -> T {
+    (high / close).ln() * (high / open).ln() + (low / close).ln() * (low / open).ln()
+}
+
+/// Mean of the last `period` values, ignoring values before `first`. NaN until `period` values
+/// are available.
+pub fn rolling_mean_into<T: Float>(values: ArrayView1<T>, first: usize, period: usize, out: &mut Array1<T>) {
+    out.fill(T::nan());
+    let count = T::cast(period as f64);
+    let mut total = T::zero();
+    for i in first..values.len() {
+        total += values[i];
+        if i >= first + period {
+            total -= values[i - period];
+            if !total.is_finite() {
+                // A NaN or infinity may have left the window; sum it again.
+                total = values.slice(s![i + 1 - period..=i]).sum();
+            }
+        }
+        if i + 1 >= first + period {
+            out[i] = total / count;
+        }
+    }
+}
+
+/// Sample variance (`period - 1` denominator) of the last `period` values, ignoring values before
+/// `first`. NaN until `period` values are available.
+pub fn rolling_sample_variance_into<T: Float>(values: ArrayView1<T>, first: usize, period: usize, out: &mut Array1<T>) {
+    rolling_variance_into(values, first, period, period - 1, out);
+}
+
+/// Population variance (`period` denominator) of the last `period` values, ignoring values before
+/// `first`. NaN until `period` values are available.
+pub fn rolling_population_variance_into<T: Float>(values: ArrayView1<T>, first: usize, period: usize, out: &mut Array1<T>) {
+    rolling_variance_into(values, first, period, period, out);
+}
+
+fn rolling_variance_into<T: Float>(values: ArrayView1<T>, first: usize, period: usize, denominator: usize, out: &mut Array1<T>) {
+    out.fill(T::nan());
+    // Mean and sum of squared deviations of the window, updated as values enter (Welford) and,
+    // once the window is full, as the oldest value is replaced.
+    let (mut mean, mut squared_deviations) = (T::zero(), T::zero());
+    for i in first..values.len() {
+        let value = values[i];
+        let previous_mean = mean;
+        if i < first + period {
+            mean += (value - previous_mean) / T::cast((i - first + 1) as f64);
+            squared_deviations += (value - previous_mean) * (value - mean);
+        } else {
+            let oldest = values[i - period];
+            mean += (value - oldest) / T::cast(period as f64);
+            squared_deviations += (value - oldest) * (value - mean + oldest - previous_mean);
+            if !squared_deviations.is_finite() {
+                // A NaN or infinity may have left the window; start again from its values.
+                let window = values.slice(s![i + 1 - period..=i]);
+                mean = window.sum() / T::cast(period as f64);
+                squared_deviations = window.iter().map(|&value| (value - mean) * (value - mean)).sum();
+            }
+        }
+        if i + 1 >= first + period {
+            // Rounding can leave a tiny negative sum; `max` would also turn NaN into zero.
+            let squared_deviations = if squared_deviations < T::zero() { T::zero() } else { squared_deviations };
+            out[i] = squared_deviations / T::cast(denominator as f64);
+        }
+    }
+}
+
+pub fn validate_parameter_within_data_length(value: &Value, data: &dyn Candles, parameter: &str, bar_field: BarField) -> Result<(), IndicatorError> {
     let period = value.get(parameter).and_then(|v| v.as_i64()).unwrap();
-    let input_field = data.get_by_bar_field(&bar_field).unwrap();
-    if period > input_field.len() as i64 {
-        Err(IndicatorError::InvalidParameters(format!("Wrong parameter length. '{}' > data length. ({} > {})", parameter, period, input_field.len())))
+    let input_length = data.field_len(&bar_field).unwrap();
+    if period > input_length as i64 {
+        Err(IndicatorError::InvalidParameters(format!("Wrong parameter length. '{}' > data length. ({} > {})", parameter, period, input_length)))
     } else {
         Ok(())
     }
 }
 
+pub fn validate_parameter_at_least(value: &Value, parameter: &str, minimum: i64) -> Result<(), IndicatorError> {
+    let number = value.get(parameter).and_then(|v| v.as_i64()).unwrap_or(0);
+    if number < minimum {
+        Err(IndicatorError::InvalidParameters(format!("Parameter '{}' must be at least {}. ({} < {})", parameter, minimum, number, minimum)))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn exponential_moving_average<T: Float>(
+    data: ArrayView1<T>,
+    period: usize,
+) -> Array1<T> {
+    let mut ema = Array1::<T>::zeros(data.len());
+    exponential_moving_average_into(data, period, &mut ema);
+    ema
+}
+
+pub fn exponential_moving_average_into<T: Float>(
+    data: ArrayView1<T>,
+    period: usize,
+    out: &mut Array1<T>,
+) {
+    exponential_moving_average_from_into(data, 0, period, out);
+}
+
+/// EMA of the values from `first` on, seeded with the SMA of their first `period` values, for
+/// series that have a warm-up of their own. NaN until the seed.
+pub fn exponential_moving_average_from_into<T: Float>(
+    data: ArrayView1<T>,
+    first: usize,
+    period: usize,
+    out: &mut Array1<T>,
+) {
+    let length = data.len();
+    let multiplier = T::cast(2.0 / (period as f64 + 1.0));
+    let seed = first + period - 1;
+
+    out.slice_mut(s![..seed]).fill(T::nan());
+    let initial_sma = data.slice(s![first..=seed]).mean().unwrap();
+    out[seed] = initial_sma;
+
+    for i in (seed + 1)..length {
+        out[i] = (data[i] - out[i - 1]) * multiplier + out[i - 1];
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use ndarray::{array, s, Array1};
-    use super::wilder_smoothing;
+    use super::{rolling_extremes_into, rolling_mean_into, rolling_midpoint_into, rolling_sample_variance_into, wilder_smoothing, RollingExtremum};
 
     #[test]
     fn test_wilder_smoothing() {
         let data = array![2.0, 3.0, 5.0, 6.0, 9.0, 11.0, 13.0];
         let period = 3;
 
-        let smoothed = wilder_smoothing(&data, period).unwrap();
+        let smoothed = wilder_smoothing(data.view(), period).unwrap();
 
         // Expected smoothed values calculated manually or from a trusted source
         let expected = array![
@@ -201,22 +536,67 @@ mod tests {
             }
         }
     }
-}
-
-pub fn exponential_moving_average(
-    data: &Array1<f64>,
-    period: usize,
-) -> Array1<f64> {
-    let length = data.len();
-    let mut ema = Array1::<f64>::from_elem(length, f64::NAN);
-    let multiplier = 2.0 / (period as f64 + 1.0);
 
-    let initial_sma = data.slice(s![..period]).mean().unwrap();
-    ema[period - 1] = initial_sma;
+    #[test]
+    fn test_rolling_mean_and_variance_match_window_scan() {
+        let mut values: Vec<f64> = (0..200).map(|i| 100.0 + ((i * 37) % 11) as f64 * 0.5 + i as f64 * 0.01).collect();
+        values[120] = f64::NAN;
+        let values = Array1::from(values);
+        for (first, period) in [(0, 2), (1, 3), (5, 14), (0, 50)] {
+            let (mut mean, mut variance) = (Array1::zeros(values.len()), Array1::zeros(values.len()));
+            rolling_mean_into(values.view(), first, period, &mut mean);
+            rolling_sample_variance_into(values.view(), first, period, &mut variance);
+            for i in 0..values.len() {
+                if i + 1 < first + period {
+                    assert!(mean[i].is_nan() && variance[i].is_nan());
+                    continue;
+                }
+                let window = values.slice(s![i + 1 - period..=i]);
+                let expected_mean = window.sum() / period as f64;
+                let expected_variance = window.mapv(|value| (value - expected_mean).powi(2)).sum() / (period - 1) as f64;
+                for (actual, expected) in [(mean[i], expected_mean), (variance[i], expected_variance)] {
+                    assert!((actual.is_nan() && expected.is_nan()) || (actual - expected).abs() < 1e-9, "period {} [{}]: {} != {}", period, i, actual, expected);
+                }
+            }
+        }
+    }
 
-    for i in period..length {
-        ema[i] = (data[i] - ema[i - 1]) * multiplier + ema[i - 1];
+    #[test]
+    fn test_rolling_extremum_matches_window_scan() {
+        // Few distinct values, so ties are common.
+        let values: Vec<f64> = (0..200).map(|i| ((i * 37) % 11) as f64).collect();
+        for period in [1, 3, 10, 50] {
+            let (mut newest, mut oldest) = (RollingExtremum::max(period), RollingExtremum::min(period).oldest_on_ties());
+            for (i, &value) in values.iter().enumerate() {
+                let start = (i + 1).saturating_sub(period);
+                let window = &values[start..=i];
+                let highest = window.iter().cloned().fold(f64::MIN, f64::max);
+                let lowest = window.iter().cloned().fold(f64::MAX, f64::min);
+                let newest_highest = start + window.iter().rposition(|&x| x == highest).unwrap();
+                let oldest_lowest = start + window.iter().position(|&x| x == lowest).unwrap();
+
+                assert_eq!(newest.push(value), Some((newest_highest, highest)), "max period {} [{}]", period, i);
+                assert_eq!(oldest.push(value), Some((oldest_lowest, lowest)), "min period {} [{}]", period, i);
+            }
+        }
     }
 
-    ema
-}
\ No newline at end of file
+    #[test]
+    fn test_rolling_extremum_skips_nan() {
+        let mut highest = RollingExtremum::max(3);
+        let pushed: Vec<_> = [1.0, f64::NAN, 5.0, f64::NAN, f64::NAN, f64::NAN].into_iter().map(|value| highest.push(value)).collect();
+        assert_eq!(pushed, [Some((0, 1.0)), Some((0, 1.0)), Some((2, 5.0)), Some((2, 5.0)), Some((2, 5.0)), None]);
+
+        // The extremes of a window with a NaN in the middle ignore it, as the window fold did.
+        let high = array![3.0, f64::NAN, 5.0, 4.0, 2.0];
+        let low = array![1.0, f64::NAN, 3.0, 2.0, 0.5];
+        let (mut highs, mut lows) = (Array1::zeros(5), Array1::zeros(5));
+        rolling_extremes_into(high.view(), low.view(), 3, &mut highs, &mut lows);
+        let mut midpoint = Array1::zeros(5);
+        rolling_midpoint_into(high.view(), low.view(), 3, &mut midpoint);
+
+        assert_eq!(highs.slice(s![2..]), array![5.0, 5.0, 5.0]);
+        assert_eq!(lows.slice(s![2..]), array![1.0, 2.0, 0.5]);
+        assert_eq!(midpoint.slice(s![2..]), array![3.0, 3.5, 2.75]);
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::{exponential_moving_average_into, validate_parameter_within_data_length};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct VOSCParams {
+    #[serde(default = "default_short_period")]
+    pub short_period: usize,
+    #[serde(default = "default_long_period")]
+    pub long_period: usize,
+}
+
+fn default_short_period() -> usize { 5 }
+fn default_long_period() -> usize { 10 }
+
+impl IParameter for VOSCParams {}
+
+pub struct VOSC {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::VOLUME],
+        vec![
+            ParamRule::Required("short_period"),
+            ParamRule::Required("long_period"),
+            ParamRule::PositiveInteger("short_period"),
+            ParamRule::PositiveInteger("long_period"),
+            ParamRule::CorrectPeriod { left: "short_period", right: "long_period" },
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "long_period", BarField::VOLUME))),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::VolumeConfirmation));
+    groups.insert(Group::UseCase(UseCase::MarketStrengthMeasurement));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Oscillation));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Averaging));
+    groups.insert(Group::DataInputType(DataInputType::VolumeBased));
+    groups.insert(Group::SignalType(SignalType::Coincident));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Percentage));
+    groups.insert(Group::OutputFormat(OutputFormat::Histogram));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Intermediate));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::MarketSuitability(MarketSuitability::RangeBound));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Exponential));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Differential));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Crossovers));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::ThresholdLevels));
+    groups
+}
+
+impl VOSC {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for VOSC {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for VOSC {
+    fn short_name(&self) -> &'static str {
+        "VOSC"
+    }
+
+    fn name(&self) -> &'static str {
+        "Volume Oscillator"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: VOSCParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let volume = data.get_by_bar_field(&BarField::VOLUME).unwrap();
+
+        let length = volume.len();
+        let [short_ema, long_ema] = scratch.arrays(length);
+        exponential_moving_average_into(volume, params.short_period, short_ema);
+        exponential_moving_average_into(volume, params.long_period, long_ema);
+
+        // Percentage difference between the short and long volume EMAs.
+        let oscillator = out.single(length);
+        for i in 0..length {
+            oscillator[i] = if long_ema[i] == T::zero() {
+                T::zero()
+            } else {
+                T::cast(100.0) * (short_ema[i] - long_ema[i]) / long_ema[i]
+            };
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_vosc_expected_value() {
+        let input_data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: None,
+            volume: Some(array![100.0, 200.0, 300.0, 200.0]),
+        };
+
+        let result = VOSC::new().calculate(&input_data, json!({ "short_period": 1, "long_period": 3 })).unwrap();
+
+        if let OutputData::SingleSeries(oscillator_values) = result {
+            // Long EMA 200 then 200; the one-bar EMA is the volume itself.
+            assert!(oscillator_values[0].is_nan() && oscillator_values[1].is_nan());
+            assert_eq!(oscillator_values[2], 50.0);
+            assert_eq!(oscillator_values[3], 0.0);
+            assert!(VOSC::new().calculate(&input_data, json!({ "short_period": 3, "long_period": 3 })).is_err());
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::models::price_source::PriceSource;
+use crate::timeframe::session::Session;
+use crate::validation::validator::{IParameter, Validator};
+
+/// Without an anchor VWAP restarts at every `session` boundary, which needs timestamps. With
+/// `anchor_index` or `anchor_timestamp` it accumulates from that bar (the first bar at or after
+/// the timestamp) to the end, and earlier bars are NaN.
+#[derive(Serialize, Deserialize)]
+pub struct VWAPParams {
+    #[serde(default)]
+    pub anchor_index: Option<usize>,
+    #[serde(default)]
+    pub anchor_timestamp: Option<i64>,
+    #[serde(default)]
+    pub session: Session,
+    #[serde(default = "default_price_source")]
+    pub price_source: PriceSource,
+}
+
+fn default_price_source() -> PriceSource { PriceSource::Typical }
+
+impl IParameter for VWAPParams {}
+
+/// Volume-weighted average price with bands `k` volume-weighted standard deviations away, for
+/// `k` = 1, 2 and 3.
+pub struct VWAP {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::VOLUME],
+        vec![],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    // Use Case
+    groups.insert(Group::UseCase(UseCase::TrendIdentification));
+    groups.insert(Group::UseCase(UseCase::SupportResistanceLevels));
+    groups.insert(Group::UseCase(UseCase::VolumeConfirmation));
+    // Mathematical Basis
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::VolumeWeighted));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::Averaging));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::StatisticalMethods));
+    // Data Input Type
+    groups.insert(Group::DataInputType(DataInputType::PriceVolumeCombined));
+    // Signal Type
+    groups.insert(Group::SignalType(SignalType::Coincident));
+    // Output Format
+    groups.insert(Group::OutputFormat(OutputFormat::MultiLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Band));
+    groups.insert(Group::OutputFormat(OutputFormat::Absolute));
+    // Timeframe Focus
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Short));
+    // Complexity Level
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Intermediate));
+    // Market Suitability
+    groups.insert(Group::MarketSuitability(MarketSuitability::Trending));
+    groups.insert(Group::MarketSuitability(MarketSuitability::RangeBound));
+    // Trading Strategy Suitability
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Scalping));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Intraday));
+    // Smoothing Technique
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::Raw));
+    // Calculation Methodology
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Cumulative));
+    // Signal Interpretation
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::Crossovers));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::ThresholdLevels));
+    groups
+}
+
+impl VWAP {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for VWAP {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// First bar the anchored VWAP accumulates from, or `None` for session VWAP.
+fn anchor<T: Float>(data: &GenericInputDataView<T>, params: &VWAPParams, length: usize) -> Result<Option<usize>, IndicatorError> {
+    let anchor = match (params.anchor_index, params.anchor_timestamp) {
+        (Some(_), Some(_)) => {
+            return Err(IndicatorError::InvalidParameters("Only one of 'anchor_index' and 'anchor_timestamp' can be set".to_string()));
+        }
+        (Some(index), None) => index,
+        (None, Some(timestamp)) => {
+            let timestamps = data.timestamp
+                .ok_or_else(|| IndicatorError::InvalidInput("Anchoring at a timestamp requires timestamps.".to_string()))?;
+            timestamps.iter().position(|&time| time >= timestamp).unwrap_or(length)
+        }
+        (None, None) => return Ok(None),
+    };
+    if anchor >= length {
+        return Err(IndicatorError::InvalidParameters("The anchor is after the last bar".to_string()));
+    }
+    Ok(Some(anchor))
+}
+
+impl<T: Float> Indicator<T> for VWAP {
+    fn short_name(&self) -> &'static str {
+        "VWAP"
+    }
+
+    fn name(&self) -> &'static str {
+        "Volume Weighted Average Price"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, _scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: VWAPParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let volume = data.get_by_bar_field(&BarField::VOLUME).unwrap();
+        let price = params.price_source.series(data)?;
+        let length = volume.len();
+        if data.timestamp.is_some_and(|timestamp| timestamp.len() != length) {
+            return Err(IndicatorError::InvalidInput("Input data series of the bars must have the same length.".to_string()));
+        }
+        let anchor = anchor(data, &params, length)?;
+        let timestamps = match anchor {
+            Some(_) => None,
+            None => Some(data.timestamp
+                .ok_or_else(|| IndicatorError::InvalidInput("Session VWAP requires timestamps.".to_string()))?),
+        };
+
+        let [vwap, mut bands @ ..] = out.multi(["vwap", "upper_1", "lower_1", "upper_2", "lower_2", "upper_3", "lower_3"], length);
+        vwap.fill(T::nan());
+        for band in bands.iter_mut() {
+            band.fill(T::nan());
+        }
+
+        // Weighted mean and sum of squared deviations, updated incrementally (West's algorithm).
+        let (mut total_volume, mut mean, mut squared_deviations) = (T::zero(), T::zero(), T::zero());
+        let mut session_start = None;
+        for i in anchor.unwrap_or(0)..length {
+            if let Some(timestamps) = timestamps {
+                let start = params.session.session_start(timestamps[i]);
+                if session_start != Some(start) {
+                    session_start = Some(start);
+                    (total_volume, mean, squared_deviations) = (T::zero(), T::zero(), T::zero());
+                }
+            }
+            if volume[i] > T::zero() {
+                total_volume += volume[i];
+                let previous_mean = mean;
+                mean += volume[i] / total_volume * (price[i] - previous_mean);
+                squared_deviations += volume[i] * (price[i] - previous_mean) * (price[i] - mean);
+            }
+            if total_volume == T::zero() {
+                continue;
+            }
+
+            vwap[i] = mean;
+            let deviation = (squared_deviations / total_volume).max(T::zero()).sqrt();
+            for (k, pair) in bands.chunks_mut(2).enumerate() {
+                let offset = T::cast((k + 1) as f64) * deviation;
+                pair[0][i] = mean + offset;
+                pair[1][i] = mean - offset;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::{array, Array1};
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData, TimestampedData};
+    use super::*;
+
+    const HOUR: i64 = 3_600_000;
+
+    fn input_data() -> TimestampedData {
+        // Two bars late on 2024-01-01 and two early on 2024-01-02 (UTC).
+        let start = 1_704_067_200_000 + 22 * HOUR;
+        let close = array![10.0, 12.0, 20.0, 22.0];
+        InputData {
+            open: None,
+            high: Some(close.clone()),
+            low: Some(close.clone()),
+            close: Some(close),
+            volume: Some(array![1.0, 3.0, 2.0, 2.0]),
+        }.with_timestamps(Array1::from_shape_fn(4, |i| start + i as i64 * HOUR))
+    }
+
+    fn series(output: OutputData) -> std::collections::HashMap<&'static str, Array1<f64>> {
+        match output {
+            OutputData::MultiSeries(series) => series,
+            OutputData::SingleSeries(_) => panic!("Unexpected output format"),
+        }
+    }
+
+    #[test]
+    fn test_session_vwap_resets_each_session() {
+        let output = series(VWAP::new().calculate_view(&input_data().view(), json!({})).unwrap());
+
+        assert_eq!(output["vwap"], array![10.0, 11.5, 20.0, 21.0]);
+        // Weighted variance of 10 (x1) and 12 (x3) is 0.75.
+        assert!((output["upper_1"][1] - (11.5 + 0.75_f64.sqrt())).abs() < 1e-12);
+        assert!((output["lower_3"][3] - 18.0).abs() < 1e-12);
+        assert_eq!(output["upper_2"][0], 10.0);
+    }
+
+    #[test]
+    fn test_session_vwap_requires_a_timestamp_per_bar() {
+        let data = TimestampedData { timestamp: Some(array![0]), ..input_data() };
+
+        assert!(matches!(VWAP::new().calculate_view(&data.view(), json!({})), Err(IndicatorError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_session_offset_moves_the_reset() {
+        // Local time three hours ahead of UTC: all four bars fall into the 2024-01-02 session.
+        let params = json!({ "session": { "utc_offset_minutes": 180, "start_minute": 0 } });
+        let output = series(VWAP::new().calculate_view(&input_data().view(), params).unwrap());
+
+        assert_eq!(output["vwap"][3], (10.0 + 36.0 + 40.0 + 44.0) / 8.0);
+    }
+
+    #[test]
+    fn test_anchored_vwap() {
+        let mut data = input_data();
+        let by_index = series(VWAP::new().calculate_view(&data.view(), json!({ "anchor_index": 1 })).unwrap());
+
+        assert!(by_index["vwap"][0].is_nan() && by_index["lower_1"][0].is_nan());
+        assert_eq!(by_index["vwap"][3], (36.0 + 40.0 + 44.0) / 7.0);
+
+        let anchor_timestamp = data.timestamp.as_ref().unwrap()[1] - 1;
+        let by_timestamp = series(VWAP::new().calculate_view(&data.view(), json!({ "anchor_timestamp": anchor_timestamp })).unwrap());
+        assert_eq!(by_timestamp["vwap"].slice(ndarray::s![1..]), by_index["vwap"].slice(ndarray::s![1..]));
+
+        data.timestamp = None;
+        assert!(VWAP::new().calculate_view(&data.view(), json!({ "anchor_index": 1 })).is_ok());
+        assert!(VWAP::new().calculate_view(&data.view(), json!({})).is_err());
+        assert!(VWAP::new().calculate_view(&data.view(), json!({ "anchor_index": 4 })).is_err());
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::indicators::utils::{rogers_satchell_term, rolling_mean_into, rolling_sample_variance_into, validate_parameter_at_least, validate_parameter_within_data_length};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{BarField, Candles, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::groups::{CalculationMethodology, ComplexityLevel, DataInputType, Group, MarketSuitability, MathematicalBasis, OutputFormat, SignalInterpretation, SignalType, SmoothingTechnique, TimeframeFocus, TradingStrategySuitability, UseCase};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::{IParameter, ParamRule, Validator};
+
+#[derive(Serialize, Deserialize)]
+pub struct YangZhangParams {
+    #[serde(default = "default_period")]
+    pub period: usize,
+    /// Bars per year; the variance is multiplied by it before taking the square root.
+    #[serde(default = "default_annualization")]
+    pub annualization: f64,
+}
+
+fn default_period() -> usize { 20 }
+fn default_annualization() -> f64 { 252.0 }
+
+impl IParameter for YangZhangParams {}
+
+/// Yang-Zhang (2000) volatility over `period` bars, annualized: the overnight (close to open)
+/// variance plus `k` times the open-to-close variance plus `1 - k` times the Rogers-Satchell
+/// variance, with `k = 0.34 / (1.34 + (period + 1) / (period - 1))`.
+pub struct YangZhang {
+    groups: HashSet<Group>,
+    validator: Validator,
+}
+
+fn create_validator() -> Validator {
+    Validator::new(
+        vec![BarField::OPEN, BarField::HIGH, BarField::LOW, BarField::CLOSE],
+        vec![
+            ParamRule::Required("period"),
+            ParamRule::PositiveInteger("period"),
+            ParamRule::Custom(Box::new(|value: &Value, data: &dyn Candles| validate_parameter_within_data_length(value, data, "period", BarField::HIGH))),
+            ParamRule::PositiveNumber("annualization"),
+            ParamRule::Custom(Box::new(|value: &Value, _: &dyn Candles| validate_parameter_at_least(value, "period", 2))),
+        ],
+    )
+}
+
+fn create_groups() -> HashSet<Group> {
+    let mut groups = HashSet::new();
+    groups.insert(Group::UseCase(UseCase::VolatilityMeasurement));
+    groups.insert(Group::MathematicalBasis(MathematicalBasis::StatisticalMethods));
+    groups.insert(Group::DataInputType(DataInputType::PriceBased));
+    groups.insert(Group::SignalType(SignalType::Lagging));
+    groups.insert(Group::OutputFormat(OutputFormat::SingleLine));
+    groups.insert(Group::OutputFormat(OutputFormat::Percentage));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Medium));
+    groups.insert(Group::TimeframeFocus(TimeframeFocus::Long));
+    groups.insert(Group::ComplexityLevel(ComplexityLevel::Advanced));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Volatile));
+    groups.insert(Group::MarketSuitability(MarketSuitability::Stable));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Swing));
+    groups.insert(Group::TradingStrategySuitability(TradingStrategySuitability::Positional));
+    groups.insert(Group::SmoothingTechnique(SmoothingTechnique::SimpleAverage));
+    groups.insert(Group::CalculationMethodology(CalculationMethodology::Statistical));
+    groups.insert(Group::SignalInterpretation(SignalInterpretation::ThresholdLevels));
+    groups
+}
+
+impl YangZhang {
+    pub fn new() -> Self {
+        let groups = create_groups();
+        let validator = create_validator();
+        Self { groups, validator }
+    }
+}
+
+impl Default for YangZhang {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Indicator<T> for YangZhang {
+    fn short_name(&self) -> &'static str {
+        "YANGZHANG"
+    }
+
+    fn name(&self) -> &'static str {
+        "Yang-Zhang Volatility"
+    }
+
+    fn get_groups(&mut self) -> &HashSet<Group> {
+        &self.groups
+    }
+
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        let params: YangZhangParams = serde_json::from_value(params)
+            .map_err(|e| IndicatorError::InvalidParameters(e.to_string()))?;
+
+        self.validator.validate(data, &params)?;
+
+        let open = data.get_by_bar_field(&BarField::OPEN).unwrap();
+        let high = data.get_by_bar_field(&BarField::HIGH).unwrap();
+        let low = data.get_by_bar_field(&BarField::LOW).unwrap();
+        let close = data.get_by_bar_field(&BarField::CLOSE).unwrap();
+
+        let length = high.len();
+        let annualization = T::cast(params.annualization);
+        let period = params.period;
+        let [overnight, intraday, rogers_satchell, overnight_variance, intraday_variance, rogers_satchell_variance, variance] = scratch.arrays(length);
+        for i in 0..length {
+            overnight[i] = if i == 0 { T::nan() } else { (open[i] / close[i - 1]).ln() };
+            intraday[i] = (close[i] / open[i]).ln();
+            rogers_satchell[i] = rogers_satchell_term(open[i], high[i], low[i], close[i]);
+        }
+        // Every term starts at the second bar, where the first overnight return is known.
+        rolling_sample_variance_into(overnight.view(), 1, period, overnight_variance);
+        rolling_sample_variance_into(intraday.view(), 1, period, intraday_variance);
+        rolling_mean_into(rogers_satchell.view(), 1, period, rogers_satchell_variance);
+
+        let k = T::cast(0.34 / (1.34 + (period as f64 + 1.0) / (period as f64 - 1.0)));
+        for i in 0..length {
+            variance[i] = overnight_variance[i] + k * intraday_variance[i] + (T::one() - k) * rogers_satchell_variance[i];
+        }
+
+        let volatility = out.single(length);
+        for i in 0..length {
+            volatility[i] = (variance[i] * annualization).sqrt();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::Array1;
+    use serde_json::json;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    #[test]
+    fn test_yang_zhang_without_gaps_or_drift() {
+        // Every bar opens at the previous close and closes where it opened, 1% below its high and
+        // at its low: only the Rogers-Satchell part, 0.01^2 per bar, is non-zero.
+        let open = Array1::from_elem(6, 100.0);
+        let input_data = InputData {
+            high: Some(&open * 0.01_f64.exp()),
+            low: Some(open.clone()),
+            close: Some(open.clone()),
+            open: Some(open),
+            volume: None,
+        };
+
+        let result = YangZhang::new().calculate(&input_data, json!({ "period": 3, "annualization": 1.0 })).unwrap();
+
+        if let OutputData::SingleSeries(values) = result {
+            let k = 0.34 / (1.34 + 4.0 / 2.0);
+            assert!(values[2].is_nan());
+            assert!((values[3] - ((1.0 - k) * 0.0001_f64).sqrt()).abs() < 1e-12);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+
+    #[test]
+    fn test_yang_zhang_overnight_gaps() {
+        // Alternating +-1% opening gaps with flat bars: only the overnight variance, 4 * 0.01^2 / 3.
+        let close = Array1::from_shape_fn(6, |i| 100.0 * if i % 2 == 0 { 1.0 } else { 0.01_f64.exp() });
+        let input_data = InputData {
+            open: Some(close.clone()),
+            high: Some(close.clone()),
+            low: Some(close.clone()),
+            close: Some(close),
+            volume: None,
+        };
+
+        let result = YangZhang::new().calculate(&input_data, json!({ "period": 4, "annualization": 1.0 })).unwrap();
+
+        if let OutputData::SingleSeries(values) = result {
+            assert!((values[4] - (4.0 * 0.0001 / 3.0_f64).sqrt()).abs() < 1e-12);
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
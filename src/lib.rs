@@ -1,3 +1,13 @@
+// Indicator types are named after their TA-Lib function names (ADX, ATR, BBANDS, ...).
+#![allow(clippy::upper_case_acronyms)]
+
 pub mod models;
-mod indicators;
-mod validation;
\ No newline at end of file
+pub mod indicators;
+pub mod validation;
+pub mod signals;
+pub mod backtest;
+pub mod optimizer;
+pub mod timeframe;
+pub mod bars;
+pub mod transforms;
+pub mod expression;
@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use ndarray::Array1;
+use crate::models::data::GenericOutputData;
+use crate::models::float::Float;
+
+/// Caller-owned output arrays filled by `Indicator::calculate_into`.
+///
+/// Arrays are only reallocated when the requested length changes, so reusing one
+/// `OutputBuffers` across calls over same-length inputs does not allocate.
+#[derive(Debug)]
+pub struct OutputBuffers<T: Float = f64> {
+    slots: Vec<(&'static str, Array1<T>)>,
+    active: usize,
+    single: bool,
+}
+
+/// Caller-owned scratch arrays for intermediate results of `Indicator::calculate_into`.
+#[derive(Debug)]
+pub struct Workspace<T: Float = f64> {
+    arrays: Vec<Array1<T>>,
+}
+
+fn ensure_length<T: Float>(array: &mut Array1<T>, length: usize) {
+    if array.len() != length {
+        *array = Array1::zeros(length);
+    }
+}
+
+impl<T: Float> Default for OutputBuffers<T> {
+    fn default() -> Self {
+        Self { slots: Vec::new(), active: 0, single: false }
+    }
+}
+
+impl<T: Float> Default for Workspace<T> {
+    fn default() -> Self {
+        Self { arrays: Vec::new() }
+    }
+}
+
+impl<T: Float> OutputBuffers<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn activate(&mut self, names: &[&'static str], length: usize) {
+        while self.slots.len() < names.len() {
+            self.slots.push(("", Array1::zeros(0)));
+        }
+        for (slot, &name) in self.slots.iter_mut().zip(names) {
+            slot.0 = name;
+            ensure_length(&mut slot.1, length);
+        }
+        self.active = names.len();
+    }
+
+    /// Prepares a single-series output of `length` values and returns it.
+    pub fn single(&mut self, length: usize) -> &mut Array1<T> {
+        self.activate(&["real"], length);
+        self.single = true;
+        &mut self.slots[0].1
+    }
+
+    /// Prepares one output series of `length` values per name and returns them in the same order.
+    pub fn multi<const N: usize>(&mut self, names: [&'static str; N], length: usize) -> [&mut Array1<T>; N] {
+        self.activate(&names, length);
+        self.single = false;
+        let mut slots = self.slots.iter_mut();
+        std::array::from_fn(|_| &mut slots.next().unwrap().1)
+    }
+
+    /// The series written by the last single-series calculation.
+    pub fn as_single(&self) -> Option<&Array1<T>> {
+        if self.single && self.active == 1 {
+            Some(&self.slots[0].1)
+        } else {
+            None
+        }
+    }
+
+    /// A named series written by the last multi-series calculation.
+    pub fn get(&self, name: &str) -> Option<&Array1<T>> {
+        if self.single {
+            return None;
+        }
+        self.slots[..self.active].iter()
+            .find(|(slot_name, _)| *slot_name == name)
+            .map(|(_, array)| array)
+    }
+
+    /// The series written by the last calculation, in the order they were requested.
+    pub fn series(&self) -> impl Iterator<Item = (&'static str, &Array1<T>)> {
+        self.slots[..self.active].iter().map(|(name, array)| (*name, array))
+    }
+
+    /// Copies the active buffers into an `OutputData`, keeping the buffers for reuse.
+    pub fn to_output(&self) -> GenericOutputData<T> {
+        if self.single {
+            GenericOutputData::SingleSeries(self.slots[0].1.clone())
+        } else {
+            GenericOutputData::MultiSeries(self.slots[..self.active].iter().cloned().collect())
+        }
+    }
+
+    pub fn into_output(mut self) -> GenericOutputData<T> {
+        if self.single {
+            GenericOutputData::SingleSeries(self.slots.swap_remove(0).1)
+        } else {
+            self.slots.truncate(self.active);
+            GenericOutputData::MultiSeries(self.slots.into_iter().collect::<HashMap<_, _>>())
+        }
+    }
+}
+
+impl<T: Float> Workspace<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Borrows `N` distinct scratch arrays of `length` values. Their contents are unspecified.
+    pub fn arrays<const N: usize>(&mut self, length: usize) -> [&mut Array1<T>; N] {
+        while self.arrays.len() < N {
+            self.arrays.push(Array1::zeros(0));
+        }
+        let mut arrays = self.arrays.iter_mut();
+        std::array::from_fn(|_| {
+            let array = arrays.next().unwrap();
+            ensure_length(array, length);
+            array
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_buffers_reuse_allocation() {
+        let mut buffers = OutputBuffers::<f64>::new();
+        let first = buffers.single(8).as_ptr();
+        let second = buffers.single(8).as_ptr();
+        assert_eq!(first, second);
+
+        let [upper, lower] = buffers.multi(["upper", "lower"], 8);
+        upper.fill(1.0);
+        lower.fill(-1.0);
+        assert_eq!(upper.as_ptr(), first);
+        assert!(buffers.as_single().is_none());
+        assert_eq!(buffers.get("lower").unwrap()[0], -1.0);
+    }
+
+    #[test]
+    fn test_output_buffers_into_output() {
+        let mut buffers = OutputBuffers::<f64>::new();
+        let [a, b] = buffers.multi(["a", "b"], 3);
+        a.fill(1.0);
+        b.fill(2.0);
+        buffers.single(2).fill(3.0);
+
+        match buffers.into_output() {
+            GenericOutputData::SingleSeries(values) => assert_eq!(values.to_vec(), vec![3.0, 3.0]),
+            _ => panic!("Unexpected output format"),
+        }
+    }
+
+    #[test]
+    fn test_workspace_arrays_are_distinct() {
+        let mut workspace = Workspace::<f64>::new();
+        let [a, b, c] = workspace.arrays(4);
+        a.fill(1.0);
+        b.fill(2.0);
+        c.fill(3.0);
+        let pointers = [a.as_ptr(), b.as_ptr(), c.as_ptr()];
+
+        let [a, b] = workspace.arrays(4);
+        assert_eq!([a.as_ptr(), b.as_ptr()], [pointers[0], pointers[1]]);
+    }
+}
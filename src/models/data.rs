@@ -1,16 +1,27 @@
 use std::collections::HashMap;
-use ndarray::{Array1, Array2};
+use std::ops::{Add, Div, Mul, Neg, Range, Sub};
+use ndarray::{s, Array1, ArrayView1, ArrayView2, Axis, Zip};
+use crate::models::float::Float;
+use crate::models::indicator::IndicatorError;
 
-pub struct InputData {
-    pub open: Option<Array1<f64>>,
-    pub high: Option<Array1<f64>>,
-    pub low: Option<Array1<f64>>,
-    pub close: Option<Array1<f64>>,
-    pub volume: Option<Array1<f64>>,
+/// Bar series with element type `T`. Most code uses the `f64` alias [`InputData`].
+pub struct GenericInputData<T: Float> {
+    pub open: Option<Array1<T>>,
+    pub high: Option<Array1<T>>,
+    pub low: Option<Array1<T>>,
+    pub close: Option<Array1<T>>,
+    pub volume: Option<Array1<T>>,
 }
 
-impl InputData {
-    pub fn get_by_bar_field(&self, bar_field: &BarField) -> Option<&Array1<f64>> {
+/// Presence and length of bar fields, independent of the float type.
+pub trait Candles {
+    fn field_len(&self, bar_field: &BarField) -> Option<usize>;
+}
+
+pub type InputData = GenericInputData<f64>;
+
+impl<T: Float> GenericInputData<T> {
+    pub fn get_by_bar_field(&self, bar_field: &BarField) -> Option<&Array1<T>> {
         match bar_field {
             BarField::OPEN => self.open.as_ref(),
             BarField::HIGH => self.high.as_ref(),
@@ -21,10 +32,309 @@ impl InputData {
     }
 }
 
+impl<T: Float> Candles for GenericInputData<T> {
+    fn field_len(&self, bar_field: &BarField) -> Option<usize> {
+        self.get_by_bar_field(bar_field).map(|series| series.len())
+    }
+}
+
+impl<T: Float> GenericInputData<T> {
+    /// Borrows every present series without copying.
+    pub fn view(&self) -> GenericInputDataView<'_, T> {
+        GenericInputDataView {
+            open: self.open.as_ref().map(|series| series.view()),
+            high: self.high.as_ref().map(|series| series.view()),
+            low: self.low.as_ref().map(|series| series.view()),
+            close: self.close.as_ref().map(|series| series.view()),
+            volume: self.volume.as_ref().map(|series| series.view()),
+            timestamp: None,
+        }
+    }
+
+    /// Attaches the open time of every bar, in Unix epoch milliseconds.
+    pub fn with_timestamps(self, timestamp: Array1<i64>) -> GenericTimestampedData<T> {
+        GenericTimestampedData { bars: self, timestamp: Some(timestamp) }
+    }
+}
+
+/// Bar series with the open time of every bar, when known. Resampling, bar building and chart
+/// transforms return it; [`GenericInputData::with_timestamps`] builds it from plain bars.
+pub struct GenericTimestampedData<T: Float> {
+    pub bars: GenericInputData<T>,
+    /// Bar open times in Unix epoch milliseconds.
+    pub timestamp: Option<Array1<i64>>,
+}
+
+pub type TimestampedData = GenericTimestampedData<f64>;
+
+impl<T: Float> GenericTimestampedData<T> {
+    /// Borrows the bars and their timestamps without copying.
+    pub fn view(&self) -> GenericInputDataView<'_, T> {
+        let view = self.bars.view();
+        match &self.timestamp {
+            Some(timestamp) => view.with_timestamps(timestamp.view()),
+            None => view,
+        }
+    }
+}
+
+/// Borrowed bar series with element type `T`. Most code uses the `f64` alias [`InputDataView`].
+#[derive(Clone, Copy)]
+pub struct GenericInputDataView<'a, T: Float> {
+    pub open: Option<ArrayView1<'a, T>>,
+    pub high: Option<ArrayView1<'a, T>>,
+    pub low: Option<ArrayView1<'a, T>>,
+    pub close: Option<ArrayView1<'a, T>>,
+    pub volume: Option<ArrayView1<'a, T>>,
+    /// Bar open times in Unix epoch milliseconds.
+    pub timestamp: Option<ArrayView1<'a, i64>>,
+}
+
+pub type InputDataView<'a> = GenericInputDataView<'a, f64>;
+
+/// Column index of each bar field in a matrix with one row per bar.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ColumnMapping {
+    pub open: Option<usize>,
+    pub high: Option<usize>,
+    pub low: Option<usize>,
+    pub close: Option<usize>,
+    pub volume: Option<usize>,
+}
+
+impl ColumnMapping {
+    /// Columns laid out as open, high, low, close, volume.
+    pub fn ohlcv() -> Self {
+        ColumnMapping { open: Some(0), high: Some(1), low: Some(2), close: Some(3), volume: Some(4) }
+    }
+
+    /// Columns laid out as open, high, low, close.
+    pub fn ohlc() -> Self {
+        ColumnMapping { volume: None, ..Self::ohlcv() }
+    }
+
+    fn get_by_bar_field(&self, bar_field: &BarField) -> Option<usize> {
+        match bar_field {
+            BarField::OPEN => self.open,
+            BarField::HIGH => self.high,
+            BarField::LOW => self.low,
+            BarField::CLOSE => self.close,
+            BarField::VOLUME => self.volume,
+        }
+    }
+}
+
+impl<'a, T: Float> GenericInputDataView<'a, T> {
+    /// Borrows the columns of `bars` (rows = bars) named by `columns`, without timestamps.
+    pub fn from_array2(bars: ArrayView2<'a, T>, columns: &ColumnMapping) -> Result<Self, IndicatorError> {
+        let column = |bar_field: BarField| -> Result<Option<ArrayView1<'a, T>>, IndicatorError> {
+            match columns.get_by_bar_field(&bar_field) {
+                Some(index) if index >= bars.ncols() => Err(IndicatorError::InvalidInput(format!(
+                    "Column {} for field '{}' is out of bounds for {} columns.", index, bar_field.to_str(), bars.ncols()
+                ))),
+                Some(index) => Ok(Some(bars.index_axis_move(Axis(1), index))),
+                None => Ok(None),
+            }
+        };
+
+        Ok(GenericInputDataView {
+            open: column(BarField::OPEN)?,
+            high: column(BarField::HIGH)?,
+            low: column(BarField::LOW)?,
+            close: column(BarField::CLOSE)?,
+            volume: column(BarField::VOLUME)?,
+            timestamp: None,
+        })
+    }
+
+    pub fn get_by_bar_field(&self, bar_field: &BarField) -> Option<ArrayView1<'a, T>> {
+        match bar_field {
+            BarField::OPEN => self.open,
+            BarField::HIGH => self.high,
+            BarField::LOW => self.low,
+            BarField::CLOSE => self.close,
+            BarField::VOLUME => self.volume,
+        }
+    }
+
+    /// Checks that every present series, and the timestamps if any, have one value per bar.
+    pub fn validate_same_length(&self) -> Result<(), IndicatorError> {
+        let series = [self.open, self.high, self.low, self.close, self.volume];
+        let mut lengths = series.iter().flatten().map(|series| series.len()).chain(self.timestamp.map(|timestamp| timestamp.len()));
+        let first = lengths.next();
+        if lengths.any(|length| Some(length) != first) {
+            return Err(IndicatorError::InvalidInput("Input data series of the bars must have the same length.".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Restricts every series to the bars in `range`, still without copying.
+    pub fn slice(&self, range: Range<usize>) -> Self {
+        let slice = |series: Option<ArrayView1<'a, T>>| series.map(|series| series.slice_move(s![range.clone()]));
+        GenericInputDataView {
+            open: slice(self.open),
+            high: slice(self.high),
+            low: slice(self.low),
+            close: slice(self.close),
+            volume: slice(self.volume),
+            timestamp: self.timestamp.map(|timestamp| timestamp.slice_move(s![range.clone()])),
+        }
+    }
+
+    /// Attaches the open time of every bar, in Unix epoch milliseconds.
+    pub fn with_timestamps(self, timestamp: ArrayView1<'a, i64>) -> Self {
+        GenericInputDataView { timestamp: Some(timestamp), ..self }
+    }
+
+    /// Copies the borrowed series into owned input data, without the timestamps.
+    pub fn to_input_data(&self) -> GenericInputData<T> {
+        GenericInputData {
+            open: self.open.map(|series| series.to_owned()),
+            high: self.high.map(|series| series.to_owned()),
+            low: self.low.map(|series| series.to_owned()),
+            close: self.close.map(|series| series.to_owned()),
+            volume: self.volume.map(|series| series.to_owned()),
+        }
+    }
+}
+
+impl<T: Float> Candles for GenericInputDataView<'_, T> {
+    fn field_len(&self, bar_field: &BarField) -> Option<usize> {
+        self.get_by_bar_field(bar_field).map(|series| series.len())
+    }
+}
+
+/// Indicator output with element type `T`. Most code uses the `f64` alias [`OutputData`].
 #[derive(Debug, Clone)]
-pub enum OutputData {
-    SingleSeries(Array1<f64>),
-    MultiSeries(HashMap<&'static str, Array1<f64>>)
+pub enum GenericOutputData<T: Float> {
+    SingleSeries(Array1<T>),
+    MultiSeries(HashMap<&'static str, Array1<T>>)
+}
+
+pub type OutputData = GenericOutputData<f64>;
+
+impl<T: Float> GenericOutputData<T> {
+    /// Borrows a named output series. A single-series output is named `real`.
+    pub fn series(&self, name: &str) -> Result<ArrayView1<'_, T>, IndicatorError> {
+        let series = match self {
+            GenericOutputData::SingleSeries(values) => (name == "real").then_some(values),
+            GenericOutputData::MultiSeries(values) => values.get(name),
+        };
+        series.map(|values| values.view())
+            .ok_or_else(|| IndicatorError::InvalidInput(format!("Output series '{}' does not exist.", name)))
+    }
+
+    /// A named output series moved to the bars it belongs to when displaced by `displacement`
+    /// bars (see `Indicator::displacement`). Shifting forward extends the series past the last
+    /// input bar; shifting back drops values before the first bar. Bars nothing lands on are NaN.
+    pub fn displaced_series(&self, name: &str, displacement: isize) -> Result<Array1<T>, IndicatorError> {
+        let values = self.series(name)?;
+        let length = values.len();
+        let shift = displacement.unsigned_abs().min(length);
+        let mut displaced = Array1::from_elem(length + displacement.max(0) as usize, T::nan());
+        if displacement >= 0 {
+            displaced.slice_mut(s![shift..]).assign(&values);
+        } else {
+            displaced.slice_mut(s![..length - shift]).assign(&values.slice(s![shift..]));
+        }
+        Ok(displaced)
+    }
+}
+
+impl<T: Float> GenericOutputData<T> {
+    /// Combines two outputs value by value. A single series is combined with every series of a
+    /// multi-series output; two multi-series outputs are combined by name. Panics if the names or
+    /// lengths differ, like ndarray arithmetic on mismatched shapes.
+    pub fn zip_with(&self, other: &Self, operation: impl Fn(T, T) -> T) -> Self {
+        let combine = |left: &Array1<T>, right: &Array1<T>| {
+            assert_eq!(left.len(), right.len(), "Output series have different lengths.");
+            Zip::from(left).and(right).map_collect(|&left, &right| operation(left, right))
+        };
+        match (self, other) {
+            (GenericOutputData::SingleSeries(left), GenericOutputData::SingleSeries(right)) => {
+                GenericOutputData::SingleSeries(combine(left, right))
+            }
+            (GenericOutputData::SingleSeries(left), GenericOutputData::MultiSeries(right)) => {
+                GenericOutputData::MultiSeries(right.iter().map(|(&name, right)| (name, combine(left, right))).collect())
+            }
+            (GenericOutputData::MultiSeries(left), GenericOutputData::SingleSeries(right)) => {
+                GenericOutputData::MultiSeries(left.iter().map(|(&name, left)| (name, combine(left, right))).collect())
+            }
+            (GenericOutputData::MultiSeries(left), GenericOutputData::MultiSeries(right)) => {
+                assert!(left.len() == right.len() && left.keys().all(|name| right.contains_key(name)), "Output series names differ.");
+                GenericOutputData::MultiSeries(left.iter().map(|(&name, left)| (name, combine(left, &right[name]))).collect())
+            }
+        }
+    }
+
+    /// Applies `operation` to every value of every series.
+    pub fn map(&self, operation: impl Fn(T) -> T) -> Self {
+        match self {
+            GenericOutputData::SingleSeries(values) => GenericOutputData::SingleSeries(values.mapv(operation)),
+            GenericOutputData::MultiSeries(series) => {
+                GenericOutputData::MultiSeries(series.iter().map(|(&name, values)| (name, values.mapv(&operation))).collect())
+            }
+        }
+    }
+}
+
+/// `macd - signal`, `&upper - &lower`, `output * 100.0`, ...: element-wise on outputs (see
+/// `GenericOutputData::zip_with`) or between an output and a scalar.
+macro_rules! impl_output_operator {
+    ($operator:ident, $method:ident, $symbol:tt) => {
+        impl<T: Float> $operator for GenericOutputData<T> {
+            type Output = GenericOutputData<T>;
+
+            fn $method(self, other: Self) -> Self::Output {
+                self.zip_with(&other, |left, right| left $symbol right)
+            }
+        }
+
+        impl<T: Float> $operator for &GenericOutputData<T> {
+            type Output = GenericOutputData<T>;
+
+            fn $method(self, other: Self) -> Self::Output {
+                self.zip_with(other, |left, right| left $symbol right)
+            }
+        }
+
+        impl<T: Float> $operator<T> for GenericOutputData<T> {
+            type Output = GenericOutputData<T>;
+
+            fn $method(self, scalar: T) -> Self::Output {
+                self.map(|value| value $symbol scalar)
+            }
+        }
+
+        impl<T: Float> $operator<T> for &GenericOutputData<T> {
+            type Output = GenericOutputData<T>;
+
+            fn $method(self, scalar: T) -> Self::Output {
+                self.map(|value| value $symbol scalar)
+            }
+        }
+    };
+}
+
+impl_output_operator!(Add, add, +);
+impl_output_operator!(Sub, sub, -);
+impl_output_operator!(Mul, mul, *);
+impl_output_operator!(Div, div, /);
+
+impl<T: Float> Neg for GenericOutputData<T> {
+    type Output = GenericOutputData<T>;
+
+    fn neg(self) -> Self::Output {
+        self.map(|value| -value)
+    }
+}
+
+impl<T: Float> Neg for &GenericOutputData<T> {
+    type Output = GenericOutputData<T>;
+
+    fn neg(self) -> Self::Output {
+        self.map(|value| -value)
+    }
 }
 
 pub enum BarField {
@@ -45,4 +355,77 @@ impl BarField {
             BarField::VOLUME => "VOLUME",
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+    use super::*;
+
+    #[test]
+    fn test_view_from_array2_borrows_mapped_columns() {
+        let bars = array![
+            [1.0, 1.5, 0.5, 1.2, 100.0],
+            [2.0, 2.5, 1.5, 2.2, 200.0],
+            [3.0, 3.5, 2.5, 3.2, 300.0],
+        ];
+
+        let view = InputDataView::from_array2(bars.view(), &ColumnMapping::ohlc()).unwrap();
+        assert_eq!(view.high.unwrap(), array![1.5, 2.5, 3.5]);
+        assert_eq!(view.close.unwrap(), array![1.2, 2.2, 3.2]);
+        assert!(view.volume.is_none());
+        assert_eq!(view.open.unwrap().as_ptr(), bars.as_ptr());
+
+        let sliced = view.slice(1..3);
+        assert_eq!(sliced.low.unwrap(), array![1.5, 2.5]);
+        assert_eq!(sliced.field_len(&BarField::CLOSE), Some(2));
+    }
+
+    #[test]
+    fn test_displaced_series() {
+        let output = OutputData::SingleSeries(array![1.0, 2.0, 3.0]);
+
+        let forward = output.displaced_series("real", 2).unwrap();
+        assert!(forward[0].is_nan() && forward[1].is_nan());
+        assert_eq!(forward.slice(s![2..]), array![1.0, 2.0, 3.0]);
+
+        let back = output.displaced_series("real", -1).unwrap();
+        assert_eq!(back.slice(s![..2]), array![2.0, 3.0]);
+        assert!(back[2].is_nan());
+        assert!(output.displaced_series("real", -5).unwrap().iter().all(|value| value.is_nan()));
+    }
+
+    #[test]
+    fn test_view_from_array2_rejects_out_of_bounds_column() {
+        let bars = array![[1.0, 1.5], [2.0, 2.5]];
+        let mapping = ColumnMapping { close: Some(2), ..ColumnMapping::default() };
+
+        assert!(InputDataView::from_array2(bars.view(), &mapping).is_err());
+    }
+
+    #[test]
+    fn test_output_arithmetic() {
+        let fast = OutputData::SingleSeries(array![3.0, 5.0, f64::NAN]);
+        let slow = OutputData::SingleSeries(array![1.0, 2.0, 4.0]);
+
+        let difference = &fast - &slow;
+        assert_eq!(difference.series("real").unwrap().slice(s![..2]), array![2.0, 3.0]);
+        assert!(difference.series("real").unwrap()[2].is_nan());
+        assert_eq!((slow.clone() * 100.0).series("real").unwrap(), array![100.0, 200.0, 400.0]);
+        assert_eq!((-(slow.clone() / slow.clone()) + 2.0).series("real").unwrap(), array![1.0, 1.0, 1.0]);
+
+        let bands = OutputData::MultiSeries(HashMap::from([("upper", array![4.0, 6.0, 8.0]), ("lower", array![2.0, 2.0, 2.0])]));
+        let relative = &bands / &slow;
+        assert_eq!(relative.series("upper").unwrap(), array![4.0, 3.0, 2.0]);
+        assert_eq!(relative.series("lower").unwrap(), array![2.0, 1.0, 0.5]);
+        assert_eq!((&bands - &bands).series("upper").unwrap(), array![0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Output series names differ.")]
+    fn test_output_arithmetic_requires_matching_names() {
+        let bands = OutputData::MultiSeries(HashMap::from([("upper", array![1.0])]));
+        let other = OutputData::MultiSeries(HashMap::from([("lower", array![1.0])]));
+        let _ = bands + other;
+    }
+}
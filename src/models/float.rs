@@ -0,0 +1,22 @@
+use std::fmt::{Debug, Display};
+use std::iter::Sum;
+use ndarray::ScalarOperand;
+use num_traits::{FromPrimitive, NumAssign};
+
+/// Element type of input and output series. Implemented for `f32` and `f64`.
+pub trait Float: num_traits::Float + NumAssign + FromPrimitive + ScalarOperand + Sum + Debug + Display + Send + Sync + 'static {
+    /// Converts an `f64` constant or parameter, rounding to the nearest representable value.
+    fn cast(value: f64) -> Self;
+}
+
+impl Float for f32 {
+    fn cast(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl Float for f64 {
+    fn cast(value: f64) -> Self {
+        value
+    }
+}
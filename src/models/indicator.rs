@@ -1,6 +1,8 @@
 use crate::models::groups::Group;
 use std::collections::HashSet;
-use crate::models::data::{InputData, OutputData};
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{GenericInputData, GenericInputDataView, GenericOutputData};
+use crate::models::float::Float;
 use serde_json::Value;
 
 #[derive(Debug, Clone)]
@@ -10,9 +12,33 @@ pub enum IndicatorError {
     CalculationError(String),
 }
 
-pub trait Indicator {
-    fn short_name(&self) -> &'static str;
-    fn name(&self) -> &'static str;
+pub trait Indicator<T: Float = f64>: Send + Sync {
+    fn short_name(&self) -> &str;
+    fn name(&self) -> &str;
     fn get_groups(&mut self) -> &HashSet<Group>;
-    fn calculate(&self, data: &InputData, params: Value) -> Result<OutputData, IndicatorError>;
-}
\ No newline at end of file
+
+    /// Bars the output `series` is displaced by: the value calculated on bar `i` belongs to bar
+    /// `i + displacement`, possibly past the last input bar. Outputs are always returned aligned
+    /// to the bar they were calculated on; see `GenericOutputData::displaced_series`.
+    fn displacement(&self, _series: &str, _params: &Value) -> Result<isize, IndicatorError> {
+        Ok(0)
+    }
+
+    /// Calculates the indicator over borrowed series into caller-owned buffers, using `scratch` for
+    /// intermediate results. Reusing both across calls avoids allocating output and intermediate arrays.
+    fn calculate_view_into(&self, data: &GenericInputDataView<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError>;
+
+    fn calculate_into(&self, data: &GenericInputData<T>, params: Value, out: &mut OutputBuffers<T>, scratch: &mut Workspace<T>) -> Result<(), IndicatorError> {
+        self.calculate_view_into(&data.view(), params, out, scratch)
+    }
+
+    fn calculate_view(&self, data: &GenericInputDataView<T>, params: Value) -> Result<GenericOutputData<T>, IndicatorError> {
+        let mut out = OutputBuffers::new();
+        self.calculate_view_into(data, params, &mut out, &mut Workspace::new())?;
+        Ok(out.into_output())
+    }
+
+    fn calculate(&self, data: &GenericInputData<T>, params: Value) -> Result<GenericOutputData<T>, IndicatorError> {
+        self.calculate_view(&data.view(), params)
+    }
+}
@@ -1,4 +1,8 @@
 pub mod groups;
 pub mod indicator;
 pub mod data;
-mod registry;
\ No newline at end of file
+pub mod float;
+pub mod buffers;
+pub mod registry;
+pub mod panel;
+pub mod price_source;
@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use ndarray::{Array2, ArrayView2, Axis};
+use rayon::prelude::*;
+use serde_json::Value;
+use crate::models::buffers::{OutputBuffers, Workspace};
+use crate::models::data::{GenericInputData, GenericInputDataView, GenericOutputData};
+use crate::models::float::Float;
+use crate::models::indicator::{Indicator, IndicatorError};
+
+/// Bars of many symbols, one `symbols × time` matrix per field.
+#[derive(Clone, Copy)]
+pub struct PanelView<'a, T: Float = f64> {
+    pub open: Option<ArrayView2<'a, T>>,
+    pub high: Option<ArrayView2<'a, T>>,
+    pub low: Option<ArrayView2<'a, T>>,
+    pub close: Option<ArrayView2<'a, T>>,
+    pub volume: Option<ArrayView2<'a, T>>,
+}
+
+/// Output of a panel calculation, one `symbols × time` matrix per output series.
+///
+/// Rows of symbols that failed are NaN and their errors are listed in `errors`.
+#[derive(Debug)]
+pub struct PanelOutput<T: Float = f64> {
+    pub series: HashMap<&'static str, Array2<T>>,
+    pub errors: Vec<(usize, IndicatorError)>,
+}
+
+impl<'a, T: Float> PanelView<'a, T> {
+    fn fields(&self) -> [Option<ArrayView2<'a, T>>; 5] {
+        [self.open, self.high, self.low, self.close, self.volume]
+    }
+
+    /// Number of symbols (rows) in the panel.
+    pub fn symbols(&self) -> Result<usize, IndicatorError> {
+        let mut rows = self.fields().into_iter().flatten().map(|field| field.nrows());
+        let symbols = rows.next()
+            .ok_or_else(|| IndicatorError::InvalidInput("Empty panel.".to_string()))?;
+        if rows.any(|field_rows| field_rows != symbols) {
+            return Err(IndicatorError::InvalidInput("Panel fields must have the same number of symbols.".to_string()));
+        }
+        Ok(symbols)
+    }
+
+    /// Borrows the bars of the symbol in row `symbol`.
+    pub fn symbol(&self, symbol: usize) -> GenericInputDataView<'a, T> {
+        let row = |field: Option<ArrayView2<'a, T>>| field.map(|field| field.index_axis_move(Axis(0), symbol));
+        GenericInputDataView {
+            open: row(self.open),
+            high: row(self.high),
+            low: row(self.low),
+            close: row(self.close),
+            volume: row(self.volume),
+            timestamp: None,
+        }
+    }
+}
+
+/// Calculates `indicator` for every symbol in parallel.
+///
+/// Each symbol gets its own result, so one symbol failing does not affect the others.
+pub fn calculate_symbols<K, T, I>(
+    indicator: &I,
+    symbols: &HashMap<K, GenericInputData<T>>,
+    params: &Value,
+) -> HashMap<K, Result<GenericOutputData<T>, IndicatorError>>
+where
+    K: Eq + Hash + Clone + Send + Sync,
+    T: Float,
+    I: Indicator<T> + ?Sized,
+{
+    symbols.par_iter()
+        .map_init(
+            || (OutputBuffers::new(), Workspace::new()),
+            |(out, scratch), (symbol, data)| {
+                let result = indicator.calculate_into(data, params.clone(), out, scratch)
+                    .map(|_| out.to_output());
+                (symbol.clone(), result)
+            },
+        )
+        .collect()
+}
+
+/// Calculates `indicator` for every row of `panel` in parallel and stacks the results.
+///
+/// Fails only if the panel itself is malformed; errors of single symbols are reported in
+/// [`PanelOutput::errors`].
+pub fn calculate_panel<T, I>(
+    indicator: &I,
+    panel: &PanelView<T>,
+    params: &Value,
+) -> Result<PanelOutput<T>, IndicatorError>
+where
+    T: Float,
+    I: Indicator<T> + ?Sized,
+{
+    let symbols = panel.symbols()?;
+    let results: Vec<Result<OutputBuffers<T>, IndicatorError>> = (0..symbols).into_par_iter()
+        .map_init(Workspace::new, |scratch, symbol| {
+            let mut out = OutputBuffers::new();
+            indicator.calculate_view_into(&panel.symbol(symbol), params.clone(), &mut out, scratch)
+                .map(|_| out)
+        })
+        .collect();
+
+    // Shape the output after the first symbol that succeeded.
+    let mut series: HashMap<&'static str, Array2<T>> = results.iter()
+        .find_map(|result| result.as_ref().ok())
+        .map(|out| out.series().map(|(name, values)| (name, Array2::from_elem((symbols, values.len()), T::nan()))).collect())
+        .unwrap_or_default();
+
+    let mut errors = Vec::new();
+    for (symbol, result) in results.into_iter().enumerate() {
+        let out = match result {
+            Ok(out) => out,
+            Err(error) => {
+                errors.push((symbol, error));
+                continue;
+            }
+        };
+
+        let matches_shape = out.series().count() == series.len()
+            && out.series().all(|(name, values)| series.get(name).is_some_and(|stacked| stacked.ncols() == values.len()));
+        if !matches_shape {
+            errors.push((symbol, IndicatorError::CalculationError("Output shape differs from the other symbols.".to_string())));
+            continue;
+        }
+
+        for (name, values) in out.series() {
+            series.get_mut(name).unwrap().row_mut(symbol).assign(values);
+        }
+    }
+
+    Ok(PanelOutput { series, errors })
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{array, Array1};
+    use serde_json::json;
+    use crate::indicators::atr::ATR;
+    use crate::indicators::bbands::BBands;
+    use crate::models::data::{InputData, OutputData};
+    use super::*;
+
+    fn bars(offset: f64, length: usize) -> InputData {
+        let close = Array1::from_shape_fn(length, |i| offset + (i as f64 * 0.7).sin() * 2.0 + i as f64 * 0.1);
+        InputData {
+            open: None,
+            high: Some(&close + 1.0),
+            low: Some(&close - 1.0),
+            close: Some(close),
+            volume: None,
+        }
+    }
+
+    #[test]
+    fn test_calculate_symbols_reports_errors_per_symbol() {
+        let symbols = HashMap::from([
+            ("AAA", bars(10.0, 30)),
+            ("BBB", bars(20.0, 30)),
+            ("CCC", bars(30.0, 3)),
+        ]);
+        let indicator = ATR::new();
+        let params = json!({ "period": 5 });
+
+        let results = calculate_symbols(&indicator, &symbols, &params);
+
+        assert_eq!(results.len(), 3);
+        assert!(results["CCC"].is_err());
+        for symbol in ["AAA", "BBB"] {
+            let expected = indicator.calculate(&symbols[symbol], params.clone()).unwrap();
+            match (&results[symbol], expected) {
+                (Ok(OutputData::SingleSeries(actual)), OutputData::SingleSeries(expected)) => {
+                    for i in 4..expected.len() {
+                        assert_eq!(actual[i], expected[i], "Mismatch for {} at index {}", symbol, i);
+                    }
+                }
+                _ => panic!("Unexpected output format"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_calculate_panel_stacks_series_per_symbol() {
+        let data = [bars(10.0, 40), bars(20.0, 40), bars(30.0, 40)];
+        let stack = |field: fn(&InputData) -> &Array1<f64>| {
+            Array2::from_shape_fn((data.len(), 40), |(symbol, i)| field(&data[symbol])[i])
+        };
+        let high = stack(|bars| bars.high.as_ref().unwrap());
+        let low = stack(|bars| bars.low.as_ref().unwrap());
+        let close = stack(|bars| bars.close.as_ref().unwrap());
+
+        let panel = PanelView { open: None, high: Some(high.view()), low: Some(low.view()), close: Some(close.view()), volume: None };
+        let indicator = BBands::new();
+        let params = json!({ "period": 10, "std_dev_multiplier": 2.0 });
+
+        let output = calculate_panel(&indicator, &panel, &params).unwrap();
+
+        assert!(output.errors.is_empty());
+        assert_eq!(output.series.len(), 3);
+        let middle = &output.series["middle_band"];
+        assert_eq!(middle.dim(), (3, 40));
+        if let OutputData::MultiSeries(expected) = indicator.calculate(&data[2], params).unwrap() {
+            for i in 9..40 {
+                assert!((middle[[2, i]] - expected["middle_band"][i]).abs() < 1e-12, "Mismatch at index {}", i);
+            }
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+
+    #[test]
+    fn test_calculate_panel_keeps_failed_symbols_as_nan() {
+        let high = array![[2.0, 3.0, 4.0], [2.0, 3.0, 4.0]];
+        let low = array![[1.0, 2.0, 3.0], [1.0, 2.0, 3.0]];
+        let close = array![[1.5, 2.5, 3.5], [1.5, 2.5, 3.5]];
+        let panel = PanelView { open: None, high: Some(high.view()), low: Some(low.view()), close: Some(close.view()), volume: None };
+
+        let output = calculate_panel(&ATR::new(), &panel, &json!({ "period": 4 })).unwrap();
+
+        assert_eq!(output.errors.len(), 2);
+        assert!(output.series.is_empty());
+
+        let short_close = array![[1.5, 2.5, 3.5]];
+        let mismatched = PanelView { close: Some(short_close.view()), ..panel };
+        assert!(calculate_panel(&ATR::new(), &mismatched, &json!({ "period": 2 })).is_err());
+    }
+}
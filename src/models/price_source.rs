@@ -0,0 +1,103 @@
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use crate::indicators::avgprice::AvgPrice;
+use crate::indicators::medprice::MedPrice;
+use crate::indicators::typprice::TypPrice;
+use crate::indicators::wclprice::WclPrice;
+use crate::models::data::{BarField, GenericInputData, GenericInputDataView};
+use crate::models::float::Float;
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::validation::validator::Validator;
+
+/// Series an indicator can read as its input: a raw field or a per-bar price transform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceSource {
+    Open,
+    High,
+    Low,
+    Close,
+    Volume,
+    /// AVGPRICE, `(O + H + L + C) / 4`.
+    Average,
+    /// MEDPRICE, `(H + L) / 2`.
+    Median,
+    /// TYPPRICE, `(H + L + C) / 3`.
+    Typical,
+    /// WCLPRICE, `(H + L + 2C) / 4`.
+    WeightedClose,
+}
+
+impl PriceSource {
+    pub fn series<T: Float>(&self, data: &GenericInputDataView<T>) -> Result<Array1<T>, IndicatorError> {
+        let field = match self {
+            PriceSource::Open => BarField::OPEN,
+            PriceSource::High => BarField::HIGH,
+            PriceSource::Low => BarField::LOW,
+            PriceSource::Close => BarField::CLOSE,
+            PriceSource::Volume => BarField::VOLUME,
+            PriceSource::Average => return transform(&AvgPrice::new(), data),
+            PriceSource::Median => return transform(&MedPrice::new(), data),
+            PriceSource::Typical => return transform(&TypPrice::new(), data),
+            PriceSource::WeightedClose => return transform(&WclPrice::new(), data),
+        };
+        let series = data.get_by_bar_field(&field).map(|series| series.to_owned());
+        Validator::new(vec![field], vec![]).validate_data(data)?;
+        Ok(series.unwrap())
+    }
+
+    /// Copies `data` with its close replaced by this source, so close-based indicators
+    /// (BBANDS, APO, ...) run on it unchanged.
+    pub fn as_close<T: Float>(&self, data: &GenericInputDataView<T>) -> Result<GenericInputData<T>, IndicatorError> {
+        let close = self.series(data)?;
+        let mut derived = data.to_input_data();
+        derived.close = Some(close);
+        Ok(derived)
+    }
+}
+
+fn transform<T: Float>(indicator: &dyn Indicator<T>, data: &GenericInputDataView<T>) -> Result<Array1<T>, IndicatorError> {
+    Ok(indicator.calculate_view(data, json!({}))?.series("real")?.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+    use crate::indicators::bbands::BBands;
+    use crate::models::data::InputData;
+    use super::*;
+
+    fn input_data() -> InputData {
+        InputData {
+            open: Some(array![10.0, 11.0, 12.0, 11.5, 12.5]),
+            high: Some(array![11.0, 12.5, 12.8, 12.0, 13.0]),
+            low: Some(array![9.5, 10.8, 11.2, 11.0, 12.0]),
+            close: Some(array![10.8, 12.0, 11.5, 11.8, 12.9]),
+            volume: None,
+        }
+    }
+
+    #[test]
+    fn test_bbands_on_typical_price() {
+        let data = input_data();
+        let typical = PriceSource::Typical.as_close(&data.view()).unwrap();
+        assert_eq!(typical.close.as_ref().unwrap()[0], (11.0 + 9.5 + 10.8) / 3.0);
+        assert_eq!(typical.high, data.high);
+
+        let params = json!({ "period": 3, "std_dev_multiplier": 2.0 });
+        let bands = BBands::new().calculate(&typical, params.clone()).unwrap();
+        let close_bands = BBands::new().calculate(&data, params).unwrap();
+        assert_ne!(bands.series("middle_band").unwrap()[4], close_bands.series("middle_band").unwrap()[4]);
+    }
+
+    #[test]
+    fn test_raw_field_source_requires_the_field() {
+        let mut data = input_data();
+        assert_eq!(PriceSource::Open.series(&data.view()).unwrap(), data.open.clone().unwrap());
+        data.open = None;
+        assert!(PriceSource::Open.series(&data.view()).is_err());
+        assert!(PriceSource::Average.series(&data.view()).is_err());
+        assert_eq!(serde_json::from_value::<PriceSource>(json!("weighted_close")).unwrap(), PriceSource::WeightedClose);
+    }
+}
@@ -1,23 +1,319 @@
 use std::collections::HashMap;
-use crate::models::data::OutputData;
+use serde_json::json;
+use crate::indicators::ad::AD;
+use crate::indicators::adosc::ADOSC;
+use crate::indicators::adx::ADX;
+use crate::indicators::adxr::ADXR;
+use crate::indicators::apo::APO;
+use crate::indicators::aroon::AROON;
+use crate::indicators::atr::ATR;
+use crate::indicators::atrstop::ATRStop;
+use crate::indicators::avgprice::AvgPrice;
+use crate::indicators::bbands::BBands;
+use crate::indicators::bbandspb::BBandsPercentB;
+use crate::indicators::bop::BOP;
+use crate::indicators::cci::CCI;
+use crate::indicators::chandelier::Chandelier;
+use crate::indicators::cmf::CMF;
+use crate::indicators::cmo::CMO;
+use crate::indicators::donchian::Donchian;
+use crate::indicators::efi::EFI;
+use crate::indicators::emv::EMV;
+use crate::indicators::envelope::Envelope;
+use crate::indicators::ewma::EWMA;
+use crate::indicators::garch::GARCH;
+use crate::indicators::garmanklass::GarmanKlass;
+use crate::indicators::gjrgarch::GJRGARCH;
+use crate::indicators::hv::HV;
+use crate::indicators::ichimoku::Ichimoku;
+use crate::indicators::keltner::Keltner;
+use crate::indicators::kvo::KVO;
+use crate::indicators::mathoperator::{MathOperator, Operator};
+use crate::indicators::mathtransform::{Function, MathTransform};
+use crate::indicators::max::MAX;
+use crate::indicators::maxindex::MAXINDEX;
+use crate::indicators::medprice::MedPrice;
+use crate::indicators::mfi::MFI;
+use crate::indicators::midpoint::MidPoint;
+use crate::indicators::midprice::MidPrice;
+use crate::indicators::min::MIN;
+use crate::indicators::minindex::MININDEX;
+use crate::indicators::minmax::MINMAX;
+use crate::indicators::minmaxindex::MINMAXINDEX;
+use crate::indicators::mom::MOM;
+use crate::indicators::natr::NATR;
+use crate::indicators::nvi::NVI;
+use crate::indicators::obv::OBV;
+use crate::indicators::parkinson::Parkinson;
+use crate::indicators::pvi::PVI;
+use crate::indicators::roc::ROC;
+use crate::indicators::rocp::ROCP;
+use crate::indicators::rocr::ROCR;
+use crate::indicators::rocr100::ROCR100;
+use crate::indicators::rogerssatchell::RogersSatchell;
+use crate::indicators::sar::SAR;
+use crate::indicators::sarext::SAREXT;
+use crate::indicators::sum::SUM;
+use crate::indicators::supertrend::SuperTrend;
+use crate::indicators::trix::TRIX;
+use crate::indicators::typprice::TypPrice;
+use crate::indicators::ultosc::ULTOSC;
+use crate::indicators::vosc::VOSC;
+use crate::indicators::vwap::VWAP;
+use crate::indicators::wclprice::WclPrice;
+use crate::indicators::yangzhang::YangZhang;
+use crate::models::data::{InputData, OutputData};
 use crate::models::groups::Group;
 use crate::models::indicator::{Indicator, IndicatorError};
 
+/// Indicators keyed by their short name (`"ADX"`, `"BBANDS"`, ...).
 pub struct IndicatorRegistry {
-    indicators: HashMap<String, Vec<Box<dyn Indicator>>>,
-    groups: HashMap<Group, Vec<Box<dyn Indicator>>>,
+    indicators: HashMap<String, Box<dyn Indicator>>,
+    groups: HashMap<Group, Vec<String>>,
 }
 
+/// Indicators looked up by short name. Calculations use every indicator's default parameters.
 pub trait AccessorByName {
+    /// The indicators named `names`, in that order, or `None` if any name is unknown.
     fn get_by_names(&self, names: Vec<&str>) -> Option<Vec<&dyn Indicator>>;
-    fn calculate_by_names(&self, names: Vec<&str>) -> Result<Vec<OutputData>, IndicatorError>;
+    /// Outputs of the indicators named `names` over `data`, in that order.
+    fn calculate_by_names(&self, names: Vec<&str>, data: &InputData) -> Result<Vec<OutputData>, IndicatorError>;
 }
 
+/// Indicators looked up by group. Calculations use every indicator's default parameters.
 pub trait AccessorByGroup {
+    /// The indicators belonging to all of `groups`, ordered by short name, or `None` if there are
+    /// none.
     fn get_by_groups(&self, groups: Vec<Group>) -> Option<Vec<&dyn Indicator>>;
-    fn calculate_by_groups(&self, groups: Vec<Group>) -> Result<Vec<OutputData>, IndicatorError>;
+    /// Outputs of the indicators belonging to all of `groups` over `data`, in `get_by_groups` order.
+    fn calculate_by_groups(&self, groups: Vec<Group>, data: &InputData) -> Result<Vec<OutputData>, IndicatorError>;
 }
 
 pub trait Registry {
     fn register_indicator(&mut self, indicator: Box<dyn Indicator>);
-}
\ No newline at end of file
+}
+
+impl IndicatorRegistry {
+    pub fn new() -> Self {
+        IndicatorRegistry { indicators: HashMap::new(), groups: HashMap::new() }
+    }
+
+    /// A registry holding every indicator of the crate.
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.register_indicator(Box::new(AD::new()));
+        registry.register_indicator(Box::new(ADOSC::new()));
+        registry.register_indicator(Box::new(ADX::new()));
+        registry.register_indicator(Box::new(ADXR::new()));
+        registry.register_indicator(Box::new(APO::new()));
+        registry.register_indicator(Box::new(AROON::new()));
+        registry.register_indicator(Box::new(ATR::new()));
+        registry.register_indicator(Box::new(ATRStop::new()));
+        registry.register_indicator(Box::new(AvgPrice::new()));
+        registry.register_indicator(Box::new(BBands::new()));
+        registry.register_indicator(Box::new(BBandsPercentB::new()));
+        registry.register_indicator(Box::new(BOP::new()));
+        registry.register_indicator(Box::new(CCI::new()));
+        registry.register_indicator(Box::new(Chandelier::new()));
+        registry.register_indicator(Box::new(CMF::new()));
+        registry.register_indicator(Box::new(CMO::new()));
+        registry.register_indicator(Box::new(Donchian::new()));
+        registry.register_indicator(Box::new(EFI::new()));
+        registry.register_indicator(Box::new(EMV::new()));
+        registry.register_indicator(Box::new(Envelope::new()));
+        registry.register_indicator(Box::new(EWMA::new()));
+        registry.register_indicator(Box::new(GARCH::new()));
+        registry.register_indicator(Box::new(GarmanKlass::new()));
+        registry.register_indicator(Box::new(GJRGARCH::new()));
+        registry.register_indicator(Box::new(HV::new()));
+        registry.register_indicator(Box::new(Ichimoku::new()));
+        registry.register_indicator(Box::new(Keltner::new()));
+        registry.register_indicator(Box::new(KVO::new()));
+        for operator in Operator::ALL {
+            registry.register_indicator(Box::new(MathOperator::new(operator)));
+        }
+        for function in Function::ALL {
+            registry.register_indicator(Box::new(MathTransform::new(function)));
+        }
+        registry.register_indicator(Box::new(MAX::new()));
+        registry.register_indicator(Box::new(MAXINDEX::new()));
+        registry.register_indicator(Box::new(MedPrice::new()));
+        registry.register_indicator(Box::new(MFI::new()));
+        registry.register_indicator(Box::new(MidPoint::new()));
+        registry.register_indicator(Box::new(MidPrice::new()));
+        registry.register_indicator(Box::new(MIN::new()));
+        registry.register_indicator(Box::new(MININDEX::new()));
+        registry.register_indicator(Box::new(MINMAX::new()));
+        registry.register_indicator(Box::new(MINMAXINDEX::new()));
+        registry.register_indicator(Box::new(MOM::new()));
+        registry.register_indicator(Box::new(NATR::new()));
+        registry.register_indicator(Box::new(NVI::new()));
+        registry.register_indicator(Box::new(OBV::new()));
+        registry.register_indicator(Box::new(Parkinson::new()));
+        registry.register_indicator(Box::new(PVI::new()));
+        registry.register_indicator(Box::new(ROC::new()));
+        registry.register_indicator(Box::new(ROCP::new()));
+        registry.register_indicator(Box::new(ROCR::new()));
+        registry.register_indicator(Box::new(ROCR100::new()));
+        registry.register_indicator(Box::new(RogersSatchell::new()));
+        registry.register_indicator(Box::new(SAR::new()));
+        registry.register_indicator(Box::new(SAREXT::new()));
+        registry.register_indicator(Box::new(SUM::new()));
+        registry.register_indicator(Box::new(SuperTrend::new()));
+        registry.register_indicator(Box::new(TRIX::new()));
+        registry.register_indicator(Box::new(TypPrice::new()));
+        registry.register_indicator(Box::new(ULTOSC::new()));
+        registry.register_indicator(Box::new(VOSC::new()));
+        registry.register_indicator(Box::new(VWAP::new()));
+        registry.register_indicator(Box::new(WclPrice::new()));
+        registry.register_indicator(Box::new(YangZhang::new()));
+        registry
+    }
+
+    pub fn get(&self, short_name: &str) -> Option<&dyn Indicator> {
+        self.indicators.get(short_name).map(|indicator| indicator.as_ref())
+    }
+
+    pub fn short_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.indicators.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Short names of the indicators belonging to `group`.
+    pub fn short_names_in_group(&self, group: &Group) -> Vec<&str> {
+        let mut names: Vec<&str> = self.groups.get(group).map_or_else(Vec::new, |names| names.iter().map(String::as_str).collect());
+        names.sort_unstable();
+        names
+    }
+}
+
+impl Default for IndicatorRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Registry for IndicatorRegistry {
+    /// Registers `indicator` under its short name, replacing any indicator with the same name.
+    fn register_indicator(&mut self, mut indicator: Box<dyn Indicator>) {
+        let short_name = indicator.short_name().to_string();
+        for names in self.groups.values_mut() {
+            names.retain(|name| *name != short_name);
+        }
+        for group in indicator.get_groups().iter() {
+            self.groups.entry(group.clone()).or_default().push(short_name.clone());
+        }
+        self.indicators.insert(short_name, indicator);
+    }
+}
+
+impl AccessorByName for IndicatorRegistry {
+    fn get_by_names(&self, names: Vec<&str>) -> Option<Vec<&dyn Indicator>> {
+        names.into_iter().map(|name| self.get(name)).collect()
+    }
+
+    fn calculate_by_names(&self, names: Vec<&str>, data: &InputData) -> Result<Vec<OutputData>, IndicatorError> {
+        names.into_iter()
+            .map(|name| {
+                self.get(name)
+                    .ok_or_else(|| IndicatorError::InvalidInput(format!("Unknown indicator '{}'.", name)))?
+                    .calculate(data, json!({}))
+            })
+            .collect()
+    }
+}
+
+impl AccessorByGroup for IndicatorRegistry {
+    fn get_by_groups(&self, groups: Vec<Group>) -> Option<Vec<&dyn Indicator>> {
+        let (first, rest) = groups.split_first()?;
+        let indicators: Vec<&dyn Indicator> = self.short_names_in_group(first).into_iter()
+            .filter(|&name| rest.iter().all(|group| self.groups.get(group).is_some_and(|names| names.iter().any(|other| other == name))))
+            .filter_map(|name| self.get(name))
+            .collect();
+        (!indicators.is_empty()).then_some(indicators)
+    }
+
+    fn calculate_by_groups(&self, groups: Vec<Group>, data: &InputData) -> Result<Vec<OutputData>, IndicatorError> {
+        self.get_by_groups(groups).unwrap_or_default().into_iter()
+            .map(|indicator| indicator.calculate(data, json!({})))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::Array1;
+    use crate::models::groups::{MathematicalBasis, OutputFormat, UseCase};
+    use super::*;
+
+    fn oscillating_bars(length: usize) -> InputData {
+        let close: Array1<f64> = (0..length).map(|i| 100.0 + 5.0 * (i as f64 / 7.0).sin() + i as f64 * 0.1).collect();
+        InputData {
+            open: Some(close.mapv(|price| price - 0.3)),
+            high: Some(close.mapv(|price| price + 1.0)),
+            low: Some(close.mapv(|price| price - 1.0)),
+            close: Some(close.clone()),
+            volume: Some(close.mapv(|price| price * 10.0)),
+        }
+    }
+
+    #[test]
+    fn test_registry_lookup() {
+        let registry = IndicatorRegistry::with_builtins();
+
+        assert_eq!(registry.get("ATR").unwrap().name(), "Average True Range");
+        assert!(registry.get("XYZ").is_none());
+        assert_eq!(registry.short_names().len(), 79);
+        assert!(registry.short_names_in_group(&Group::UseCase(UseCase::VolatilityMeasurement)).contains(&"ATR"));
+        assert_eq!(
+            registry.short_names_in_group(&Group::UseCase(UseCase::PriceTransformation)),
+            vec![
+                "ACOS", "ADD", "ASIN", "ATAN", "AVGPRICE", "CEIL", "COS", "COSH", "DIV", "EXP", "FLOOR", "LN", "LOG10", "MEDPRICE",
+                "MIDPOINT", "MIDPRICE", "MULT", "SIN", "SINH", "SQRT", "SUB", "SUM", "TAN", "TANH", "TYPPRICE", "WCLPRICE",
+            ]
+        );
+        assert_eq!(
+            registry.short_names_in_group(&Group::MathematicalBasis(MathematicalBasis::ProbabilisticMethods)),
+            vec!["EWMA", "GARCH", "GJRGARCH"]
+        );
+        assert_eq!(
+            registry.short_names_in_group(&Group::OutputFormat(OutputFormat::Band)),
+            vec!["BBANDS", "BBANDSPB", "DONCHIAN", "ENVELOPE", "KELTNER", "VWAP"]
+        );
+    }
+
+    #[test]
+    fn test_calculate_by_groups() {
+        let registry = IndicatorRegistry::with_builtins();
+        let data = oscillating_bars(120);
+
+        let momentum = vec![Group::UseCase(UseCase::MomentumDetection)];
+        assert_eq!(
+            registry.short_names_in_group(&momentum[0]),
+            vec!["ADOSC", "APO", "AROON", "BOP", "CCI", "CMO", "EFI", "EMV", "MFI", "MOM", "ROC", "ROCP", "ROCR", "ROCR100", "TRIX", "ULTOSC"]
+        );
+        let outputs = registry.calculate_by_groups(momentum.clone(), &data).unwrap();
+        assert_eq!(outputs.len(), 16);
+
+        let percentages: Vec<&str> = registry.get_by_groups(vec![momentum[0].clone(), Group::OutputFormat(OutputFormat::Percentage)])
+            .unwrap().iter().map(|indicator| indicator.short_name()).collect();
+        assert_eq!(percentages, vec!["AROON", "CMO", "MFI", "ROC", "ROCP", "ROCR100", "TRIX", "ULTOSC"]);
+        assert!(registry.get_by_groups(vec![momentum[0].clone(), Group::UseCase(UseCase::PriceTransformation)]).is_none());
+        assert!(registry.get_by_groups(vec![]).is_none());
+    }
+
+    #[test]
+    fn test_calculate_by_names() {
+        let registry = IndicatorRegistry::with_builtins();
+        let data = oscillating_bars(60);
+
+        let names: Vec<&str> = registry.get_by_names(vec!["ROC", "MOM"]).unwrap().iter().map(|indicator| indicator.short_name()).collect();
+        assert_eq!(names, vec!["ROC", "MOM"]);
+        assert!(registry.get_by_names(vec!["MOM", "XYZ"]).is_none());
+
+        let outputs = registry.calculate_by_names(vec!["MOM"], &data).unwrap();
+        assert_eq!(outputs[0].series("real").unwrap()[10], data.close.as_ref().unwrap()[10] - data.close.as_ref().unwrap()[0]);
+        assert!(registry.calculate_by_names(vec!["MOM", "XYZ"], &data).is_err());
+    }
+}
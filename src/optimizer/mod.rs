@@ -0,0 +1,209 @@
+//! Parameter search over indicator params.
+//!
+//! Candidates are plain JSON params, expanded from a [`ParamGrid`] either exhaustively or by
+//! random sampling. Each candidate goes through `Indicator::calculate_view`, and therefore through
+//! the indicator's `Validator`; rejected candidates are reported instead of scored.
+
+use std::cmp::Ordering;
+use rayon::prelude::*;
+use serde_json::{Map, Value};
+use crate::models::data::{InputDataView, OutputData};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::models::registry::IndicatorRegistry;
+
+pub mod walk_forward;
+
+/// Scores an indicator output over the bars it was calculated on. Higher is better.
+pub type Objective<'a> = dyn Fn(&InputDataView, &OutputData) -> f64 + Send + Sync + 'a;
+
+/// Candidate values for every parameter.
+#[derive(Debug, Clone, Default)]
+pub struct ParamGrid {
+    params: Vec<(String, Vec<Value>)>,
+}
+
+/// Score of one params value.
+#[derive(Debug, Clone)]
+pub struct Evaluation {
+    pub params: Value,
+    pub score: f64,
+}
+
+/// Evaluations ranked from the best score down. NaN scores rank last.
+#[derive(Debug, Clone)]
+pub struct OptimizationResult {
+    pub ranked: Vec<Evaluation>,
+    pub rejected: Vec<(Value, IndicatorError)>,
+}
+
+impl OptimizationResult {
+    pub fn best(&self) -> Option<&Evaluation> {
+        self.ranked.first()
+    }
+}
+
+impl ParamGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a parameter and its candidate values.
+    pub fn with<V: Into<Value>>(mut self, name: &str, values: impl IntoIterator<Item = V>) -> Self {
+        self.params.push((name.to_string(), values.into_iter().map(Into::into).collect()));
+        self
+    }
+
+    /// Number of combinations in the grid.
+    pub fn len(&self) -> usize {
+        if self.params.is_empty() {
+            return 0;
+        }
+        self.params.iter().map(|(_, values)| values.len()).product()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The params value of combination `index`, the last parameter varying fastest.
+    fn combination(&self, mut index: usize) -> Value {
+        let mut params = Map::new();
+        for (name, values) in self.params.iter().rev() {
+            params.insert(name.clone(), values[index % values.len()].clone());
+            index /= values.len();
+        }
+        Value::Object(params)
+    }
+
+    /// Every combination of the grid.
+    pub fn combinations(&self) -> Vec<Value> {
+        (0..self.len()).map(|index| self.combination(index)).collect()
+    }
+
+    /// `count` distinct combinations drawn at random; the same `seed` draws the same ones.
+    pub fn sample(&self, count: usize, seed: u64) -> Vec<Value> {
+        let mut indices: Vec<usize> = (0..self.len()).collect();
+        let mut state = seed;
+        let count = count.min(indices.len());
+        // Partial Fisher-Yates shuffle driven by SplitMix64.
+        for i in 0..count {
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^= z >> 31;
+            let j = i + (z % (indices.len() - i) as u64) as usize;
+            indices.swap(i, j);
+        }
+        indices[..count].iter().map(|&index| self.combination(index)).collect()
+    }
+}
+
+fn rank(evaluations: &mut [Evaluation]) {
+    evaluations.sort_by(|a, b| match (a.score.is_nan(), b.score.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => b.score.partial_cmp(&a.score).unwrap(),
+    });
+}
+
+/// Calculates `indicator` for every candidate in parallel and ranks them by `objective`.
+pub fn optimize(indicator: &dyn Indicator, data: &InputDataView, candidates: &[Value], objective: &Objective) -> OptimizationResult {
+    let results: Vec<(Value, Result<f64, IndicatorError>)> = candidates.par_iter()
+        .map(|params| {
+            let score = indicator.calculate_view(data, params.clone())
+                .map(|output| objective(data, &output));
+            (params.clone(), score)
+        })
+        .collect();
+
+    let mut ranked = Vec::new();
+    let mut rejected = Vec::new();
+    for (params, score) in results {
+        match score {
+            Ok(score) => ranked.push(Evaluation { params, score }),
+            Err(error) => rejected.push((params, error)),
+        }
+    }
+    rank(&mut ranked);
+
+    OptimizationResult { ranked, rejected }
+}
+
+/// [`optimize`] for the indicator registered under `short_name`.
+pub fn optimize_by_name(registry: &IndicatorRegistry, short_name: &str, data: &InputDataView, candidates: &[Value], objective: &Objective) -> Result<OptimizationResult, IndicatorError> {
+    let indicator = registry.get(short_name)
+        .ok_or_else(|| IndicatorError::InvalidInput(format!("Indicator '{}' is not registered.", short_name)))?;
+    Ok(optimize(indicator, data, candidates, objective))
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::Array1;
+    use serde_json::json;
+    use crate::backtest::{self, BacktestConfig};
+    use crate::models::data::InputData;
+    use crate::signals::crossovers::crossovers;
+    use super::*;
+
+    pub(crate) fn input_data(length: usize) -> InputData {
+        let close = Array1::from_shape_fn(length, |i| 100.0 + (i as f64 * 0.15).sin() * 10.0 + i as f64 * 0.05);
+        InputData {
+            open: Some(close.clone()),
+            high: Some(&close + 1.0),
+            low: Some(&close - 1.0),
+            close: Some(close),
+            volume: None,
+        }
+    }
+
+    #[test]
+    fn test_param_grid_combinations() {
+        let grid = ParamGrid::new()
+            .with("period", [10, 20])
+            .with("std_dev_multiplier", [1.5, 2.0, 2.5]);
+
+        let combinations = grid.combinations();
+
+        assert_eq!(grid.len(), 6);
+        assert_eq!(combinations[0], json!({ "period": 10, "std_dev_multiplier": 1.5 }));
+        assert_eq!(combinations[5], json!({ "period": 20, "std_dev_multiplier": 2.5 }));
+    }
+
+    #[test]
+    fn test_param_grid_sample_is_deterministic_and_distinct() {
+        let grid = ParamGrid::new().with("period", 2..30);
+
+        let sample = grid.sample(10, 42);
+
+        assert_eq!(sample, grid.sample(10, 42));
+        assert_eq!(sample.len(), 10);
+        for (i, params) in sample.iter().enumerate() {
+            assert!(!sample[i + 1..].contains(params));
+        }
+        assert_eq!(grid.sample(100, 1).len(), 28);
+    }
+
+    #[test]
+    fn test_optimize_ranks_and_rejects_invalid_params() {
+        let data = input_data(200);
+        let registry = IndicatorRegistry::with_builtins();
+        // Period 0 and negative multipliers do not pass the BBANDS validator.
+        let candidates = ParamGrid::new().with("period", [0, 5, 20]).with("std_dev_multiplier", [2.0, -1.0]).combinations();
+
+        // Long while the close is above the middle band.
+        let objective = |data: &InputDataView, output: &OutputData| {
+            let close = data.close.unwrap();
+            let signals = crossovers(close, output.series("middle_band").unwrap()).unwrap();
+            let positions = backtest::positions_from_signals(&signals, close.len(), false);
+            backtest::run(data, positions.view(), &BacktestConfig::default()).unwrap().metrics.total_return
+        };
+        let result = optimize_by_name(&registry, "BBANDS", &data.view(), &candidates, &objective).unwrap();
+
+        assert_eq!(result.ranked.len(), 2);
+        assert_eq!(result.rejected.len(), 4);
+        assert!(result.ranked[0].score >= result.ranked[1].score);
+        assert!(optimize_by_name(&registry, "XYZ", &data.view(), &candidates, &objective).is_err());
+    }
+}
@@ -0,0 +1,108 @@
+use std::ops::Range;
+use serde_json::Value;
+use crate::models::data::InputDataView;
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::optimizer::{optimize, Evaluation, Objective, OptimizationResult};
+
+/// Rolling in-sample/out-of-sample split. Windows advance by `out_of_sample` bars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalkForward {
+    pub in_sample: usize,
+    pub out_of_sample: usize,
+    /// Keeps every in-sample window starting at bar 0 instead of rolling it.
+    pub anchored: bool,
+}
+
+/// One walk-forward step: the best in-sample params and their out-of-sample score.
+///
+/// The out-of-sample slice is calculated on its own, so indicator warm-up falls inside it.
+#[derive(Debug, Clone)]
+pub struct WalkForwardWindow {
+    pub in_sample: Range<usize>,
+    pub out_of_sample: Range<usize>,
+    pub optimization: OptimizationResult,
+    /// Out-of-sample score of the best in-sample params, NaN if no candidate was accepted.
+    pub out_of_sample_score: f64,
+}
+
+impl WalkForwardWindow {
+    pub fn best(&self) -> Option<&Evaluation> {
+        self.optimization.best()
+    }
+}
+
+impl WalkForward {
+    /// The in-sample and out-of-sample ranges over `length` bars.
+    pub fn windows(&self, length: usize) -> Vec<(Range<usize>, Range<usize>)> {
+        let mut windows = Vec::new();
+        let mut start = 0;
+        while start + self.in_sample + self.out_of_sample <= length {
+            let split = start + self.in_sample;
+            let in_sample_start = if self.anchored { 0 } else { start };
+            windows.push((in_sample_start..split, split..split + self.out_of_sample));
+            start += self.out_of_sample;
+        }
+        windows
+    }
+
+    pub fn run(&self, indicator: &dyn Indicator, data: &InputDataView, candidates: &[Value], objective: &Objective) -> Result<Vec<WalkForwardWindow>, IndicatorError> {
+        if self.in_sample == 0 || self.out_of_sample == 0 {
+            return Err(IndicatorError::InvalidParameters("Walk-forward windows must not be empty".to_string()));
+        }
+        let length = data.close.or(data.high).or(data.open).map_or(0, |series| series.len());
+
+        Ok(self.windows(length).into_iter()
+            .map(|(in_sample, out_of_sample)| {
+                let optimization = optimize(indicator, &data.slice(in_sample.clone()), candidates, objective);
+                let out_of_sample_data = data.slice(out_of_sample.clone());
+                let out_of_sample_score = optimization.best()
+                    .and_then(|best| indicator.calculate_view(&out_of_sample_data, best.params.clone()).ok())
+                    .map_or(f64::NAN, |output| objective(&out_of_sample_data, &output));
+                WalkForwardWindow { in_sample, out_of_sample, optimization, out_of_sample_score }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::indicators::atr::ATR;
+    use crate::models::data::OutputData;
+    use crate::optimizer::ParamGrid;
+    use crate::optimizer::tests::input_data;
+    use super::*;
+
+    #[test]
+    fn test_windows() {
+        let rolling = WalkForward { in_sample: 100, out_of_sample: 25, anchored: false };
+        assert_eq!(rolling.windows(160), vec![(0..100, 100..125), (25..125, 125..150)]);
+
+        let anchored = WalkForward { anchored: true, ..rolling };
+        assert_eq!(anchored.windows(160), vec![(0..100, 100..125), (0..125, 125..150)]);
+    }
+
+    #[test]
+    fn test_walk_forward_picks_in_sample_best() {
+        let data = input_data(300);
+        let candidates = ParamGrid::new().with("period", [5, 10, 20]).combinations();
+        // Prefers the smoothest ATR: the lowest average bar-to-bar change.
+        let objective = |_: &InputDataView, output: &OutputData| {
+            let atr = output.series("real").unwrap();
+            let changes: Vec<f64> = atr.windows(2).into_iter()
+                .filter(|pair| !pair[0].is_nan())
+                .map(|pair| (pair[1] - pair[0]).abs())
+                .collect();
+            -changes.iter().sum::<f64>() / changes.len() as f64
+        };
+
+        let walk_forward = WalkForward { in_sample: 150, out_of_sample: 50, anchored: false };
+        let windows = walk_forward.run(&ATR::new(), &data.view(), &candidates, &objective).unwrap();
+
+        assert_eq!(windows.len(), 3);
+        for window in &windows {
+            assert_eq!(window.best().unwrap().params, json!({ "period": 20 }));
+            assert!(!window.out_of_sample_score.is_nan());
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use std::cmp::Ordering;
+use ndarray::ArrayView1;
+use crate::models::float::Float;
+use crate::models::indicator::IndicatorError;
+use crate::signals::{validate_same_length, Direction, Signal};
+
+/// Emits a signal whenever the sign of `difference(i)` flips.
+///
+/// Bars where the difference is zero or NaN keep the previous side, so touching a line
+/// without crossing it does not produce a signal.
+fn sign_changes<T: Float>(length: usize, difference: impl Fn(usize) -> T) -> Vec<Signal> {
+    let mut signals = Vec::new();
+    let mut side = None;
+    for i in 0..length {
+        let current = match difference(i).partial_cmp(&T::zero()) {
+            Some(Ordering::Greater) => Direction::Bullish,
+            Some(Ordering::Less) => Direction::Bearish,
+            _ => continue,
+        };
+        if side.is_some_and(|previous| previous != current) {
+            signals.push(Signal::new(i, current));
+        }
+        side = Some(current);
+    }
+    signals
+}
+
+/// Crossovers of `fast` over `slow`: bullish when it crosses above, bearish when it crosses below.
+pub fn crossovers<T: Float>(fast: ArrayView1<T>, slow: ArrayView1<T>) -> Result<Vec<Signal>, IndicatorError> {
+    let length = validate_same_length(&[fast.len(), slow.len()])?;
+    Ok(sign_changes(length, |i| fast[i] - slow[i]))
+}
+
+/// Crossings of `series` through a fixed `level`: bullish upwards, bearish downwards.
+pub fn level_crossings<T: Float>(series: ArrayView1<T>, level: T) -> Vec<Signal> {
+    sign_changes(series.len(), |i| series[i] - level)
+}
+
+/// Breakouts of `price` out of a band: bullish when it closes above `upper`, bearish when it
+/// closes below `lower`. Only the bar leaving the band is reported.
+pub fn band_breakouts<T: Float>(price: ArrayView1<T>, upper: ArrayView1<T>, lower: ArrayView1<T>) -> Result<Vec<Signal>, IndicatorError> {
+    let length = validate_same_length(&[price.len(), upper.len(), lower.len()])?;
+    let above = sign_changes(length, |i| price[i] - upper[i]).into_iter()
+        .filter(|signal| signal.direction == Direction::Bullish);
+    let below = sign_changes(length, |i| price[i] - lower[i]).into_iter()
+        .filter(|signal| signal.direction == Direction::Bearish);
+
+    let mut signals: Vec<Signal> = above.chain(below).collect();
+    signals.sort_by_key(|signal| signal.index);
+    Ok(signals)
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::indicators::aroon::AROON;
+    use crate::models::data::InputData;
+    use crate::models::indicator::Indicator;
+    use super::*;
+
+    #[test]
+    fn test_crossovers() {
+        let fast = array![1.0, 2.0, 3.0, 2.0, 1.0, 2.0];
+        let slow = array![2.0, 2.0, 2.0, 2.0, 2.0, 2.0];
+
+        let signals = crossovers(fast.view(), slow.view()).unwrap();
+
+        assert_eq!(signals, vec![Signal::new(2, Direction::Bullish), Signal::new(4, Direction::Bearish)]);
+    }
+
+    #[test]
+    fn test_crossovers_ignore_nan_and_length_mismatch() {
+        let fast = array![f64::NAN, f64::NAN, 1.0, 3.0];
+        let slow = array![2.0, 2.0, 2.0, 2.0];
+        assert_eq!(crossovers(fast.view(), slow.view()).unwrap(), vec![Signal::new(3, Direction::Bullish)]);
+
+        let short = array![1.0, 2.0];
+        assert!(crossovers(fast.view(), short.view()).is_err());
+    }
+
+    #[test]
+    fn test_level_crossings() {
+        let adx = array![f64::NAN, 20.0, 24.0, 26.0, 30.0, 25.0, 24.0];
+
+        let signals = level_crossings(adx.view(), 25.0);
+
+        assert_eq!(signals, vec![Signal::new(3, Direction::Bullish), Signal::new(6, Direction::Bearish)]);
+    }
+
+    #[test]
+    fn test_band_breakouts() {
+        let close = array![10.0, 12.5, 13.0, 10.0, 7.0, 6.0, 10.0];
+        let upper = array![12.0, 12.0, 12.0, 12.0, 12.0, 12.0, 12.0];
+        let lower = array![8.0, 8.0, 8.0, 8.0, 8.0, 8.0, 8.0];
+
+        let signals = band_breakouts(close.view(), upper.view(), lower.view()).unwrap();
+
+        assert_eq!(signals, vec![Signal::new(1, Direction::Bullish), Signal::new(4, Direction::Bearish)]);
+    }
+
+    #[test]
+    fn test_crossovers_from_output_data() {
+        let high = array![10.0, 11.0, 12.0, 13.0, 12.0, 11.0, 10.0, 9.0, 10.0, 11.0, 12.0, 13.0];
+        let low = &high - 1.0;
+        let input_data = InputData {
+            open: None,
+            high: Some(high),
+            low: Some(low),
+            close: None,
+            volume: None,
+        };
+
+        let output = AROON::new().calculate(&input_data, json!({ "period": 3 })).unwrap();
+        let signals = crossovers(output.series("aroon_up").unwrap(), output.series("aroon_down").unwrap()).unwrap();
+
+        assert_eq!(signals, vec![Signal::new(5, Direction::Bearish), Signal::new(9, Direction::Bullish)]);
+        assert!(output.series("missing").is_err());
+    }
+}
@@ -0,0 +1,92 @@
+use ndarray::ArrayView1;
+use crate::models::float::Float;
+use crate::models::indicator::IndicatorError;
+use crate::signals::{validate_same_length, Direction, Signal};
+
+/// Indices of bars that are strictly above (`peaks`) or below (troughs) the `order` bars on
+/// either side of them.
+fn pivots<T: Float>(series: ArrayView1<T>, order: usize, peaks: bool) -> Vec<usize> {
+    let length = series.len();
+    if length < 2 * order + 1 {
+        return Vec::new();
+    }
+
+    (order..length - order)
+        .filter(|&i| {
+            let value = series[i];
+            !value.is_nan() && (i - order..=i + order).filter(|&j| j != i).all(|j| {
+                if peaks { value > series[j] } else { value < series[j] }
+            })
+        })
+        .collect()
+}
+
+/// Price-vs-indicator divergences between consecutive price pivots.
+///
+/// A bullish divergence is a lower low in `price` with a higher low in `indicator`; a bearish
+/// divergence is a higher high in `price` with a lower high in `indicator`. Pivots are bars that
+/// are the extreme of the `order` bars on either side, so a divergence is only known `order` bars
+/// after its second pivot; signals are reported at that bar to avoid look-ahead.
+pub fn divergences<T: Float>(price: ArrayView1<T>, indicator: ArrayView1<T>, order: usize) -> Result<Vec<Signal>, IndicatorError> {
+    validate_same_length(&[price.len(), indicator.len()])?;
+    if order == 0 {
+        return Err(IndicatorError::InvalidParameters("Parameter 'order' must be a positive integer".to_string()));
+    }
+
+    let mut signals = Vec::new();
+    for (peaks, direction) in [(false, Direction::Bullish), (true, Direction::Bearish)] {
+        for pair in pivots(price, order, peaks).windows(2) {
+            let (first, second) = (pair[0], pair[1]);
+            if indicator[first].is_nan() || indicator[second].is_nan() {
+                continue;
+            }
+            let diverges = match direction {
+                Direction::Bullish => price[second] < price[first] && indicator[second] > indicator[first],
+                Direction::Bearish => price[second] > price[first] && indicator[second] < indicator[first],
+            };
+            if diverges {
+                signals.push(Signal::new(second + order, direction));
+            }
+        }
+    }
+
+    signals.sort_by_key(|signal| signal.index);
+    Ok(signals)
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+    use super::*;
+
+    #[test]
+    fn test_bullish_divergence() {
+        // Lower lows at 2 and 6, higher lows in the oscillator.
+        let price = array![10.0, 9.0, 8.0, 9.0, 10.0, 9.0, 7.0, 8.0, 9.0];
+        let oscillator = array![50.0, 40.0, 30.0, 40.0, 50.0, 45.0, 35.0, 45.0, 50.0];
+
+        let signals = divergences(price.view(), oscillator.view(), 1).unwrap();
+
+        assert_eq!(signals, vec![Signal::new(7, Direction::Bullish)]);
+    }
+
+    #[test]
+    fn test_bearish_divergence() {
+        // Higher highs at 2 and 6, lower highs in the oscillator.
+        let price = array![10.0, 11.0, 12.0, 11.0, 10.0, 12.0, 13.0, 12.0, 11.0];
+        let oscillator = array![50.0, 60.0, 70.0, 60.0, 50.0, 60.0, 65.0, 55.0, 50.0];
+
+        let signals = divergences(price.view(), oscillator.view(), 1).unwrap();
+
+        assert_eq!(signals, vec![Signal::new(7, Direction::Bearish)]);
+    }
+
+    #[test]
+    fn test_no_divergence_when_indicator_confirms() {
+        let price = array![10.0, 11.0, 12.0, 11.0, 10.0, 12.0, 13.0, 12.0, 11.0];
+        let oscillator = array![50.0, 60.0, 70.0, 60.0, 50.0, 70.0, 80.0, 70.0, 60.0];
+
+        assert!(divergences(price.view(), oscillator.view(), 1).unwrap().is_empty());
+        assert!(divergences(price.view(), oscillator.view(), 0).is_err());
+    }
+}
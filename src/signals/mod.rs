@@ -0,0 +1,36 @@
+//! Discrete events derived from indicator series.
+//!
+//! Series are borrowed as `ArrayView1`, so they can come straight from an `OutputData`:
+//! `crossovers(output.series("aroon_up")?, output.series("aroon_down")?)`.
+
+use crate::models::indicator::IndicatorError;
+
+pub mod crossovers;
+pub mod divergence;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Bullish,
+    Bearish,
+}
+
+/// An event at bar `index`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Signal {
+    pub index: usize,
+    pub direction: Direction,
+}
+
+impl Signal {
+    pub fn new(index: usize, direction: Direction) -> Self {
+        Signal { index, direction }
+    }
+}
+
+fn validate_same_length(lengths: &[usize]) -> Result<usize, IndicatorError> {
+    let length = lengths.first().copied().unwrap_or(0);
+    if lengths.iter().any(|&other| other != length) {
+        return Err(IndicatorError::InvalidInput("Signal series must have the same length.".to_string()));
+    }
+    Ok(length)
+}
@@ -0,0 +1,288 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use ndarray::ArrayView1;
+use serde::{Deserialize, Serialize};
+use crate::models::indicator::IndicatorError;
+use crate::timeframe::session::{Session, TradingSessions};
+use crate::timeframe::{DAY, MINUTE};
+
+/// A calendar date, used to name holidays and half-days. Serialized as `YYYY-MM-DD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    pub fn new(year: i32, month: u32, day: u32) -> Self {
+        Date { year, month, day }
+    }
+
+    /// Days since 1970-01-01 in the proleptic Gregorian calendar.
+    pub fn days_since_epoch(&self) -> i64 {
+        let year = self.year as i64 - if self.month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = self.month as i64;
+        let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// The date `days` days after 1970-01-01, the inverse of [`Date::days_since_epoch`].
+    pub fn from_days_since_epoch(days: i64) -> Self {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month + 2) / 5 + 1;
+        let month = if month < 10 { month + 3 } else { month - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        Date::new(year as i32, month as u32, day as u32)
+    }
+
+    /// Number of days in `month` of `year`, or 0 for a month outside 1..=12.
+    pub fn days_in_month(year: i32, month: u32) -> u32 {
+        match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+            2 => 28,
+            _ => 0,
+        }
+    }
+}
+
+impl FromStr for Date {
+    type Err = IndicatorError;
+
+    /// Parses `YYYY-MM-DD`.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || IndicatorError::InvalidInput(format!("Invalid date '{}', expected YYYY-MM-DD.", text));
+        let mut parts = text.split('-');
+        let (Some(year), Some(month), Some(day), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+            return Err(invalid());
+        };
+        let date = Date::new(
+            year.parse().map_err(|_| invalid())?,
+            month.parse().map_err(|_| invalid())?,
+            day.parse().map_err(|_| invalid())?,
+        );
+        if !(1..=Date::days_in_month(date.year, date.month)).contains(&date.day) {
+            return Err(invalid());
+        }
+        Ok(date)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl TryFrom<String> for Date {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        text.parse().map_err(|_| format!("invalid date '{}', expected YYYY-MM-DD", text))
+    }
+}
+
+impl From<Date> for String {
+    fn from(date: Date) -> Self {
+        date.to_string()
+    }
+}
+
+fn parse_minute(text: &str) -> Result<i64, IndicatorError> {
+    let invalid = || IndicatorError::InvalidInput(format!("Invalid time '{}', expected HH:MM.", text));
+    let (hours, minutes) = text.split_once(':').ok_or_else(invalid)?;
+    let (hours, minutes): (i64, i64) = (hours.parse().map_err(|_| invalid())?, minutes.parse().map_err(|_| invalid())?);
+    if !(0..24).contains(&hours) || !(0..60).contains(&minutes) {
+        return Err(invalid());
+    }
+    Ok(hours * 60 + minutes)
+}
+
+/// Regular trading hours on weekdays, minus holidays, with early closes on half-days.
+///
+/// Sessions open at `session.start_minute` and close at `close_minute` (local minutes after
+/// midnight). A close at or before the start means the session runs past midnight. A session
+/// belongs to the local date it opens on.
+///
+/// As a parameter it is written as `{ "session": { ... }, "close_minute": 960, "holidays":
+/// ["2024-07-04"], "half_days": { "2024-07-03": 780 } }`, with half-day closes in local minutes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradingCalendar {
+    #[serde(default)]
+    pub session: Session,
+    pub close_minute: i64,
+    #[serde(default)]
+    holidays: HashSet<Date>,
+    #[serde(default)]
+    half_days: HashMap<Date, i64>,
+}
+
+impl TradingCalendar {
+    pub fn new(session: Session, close_minute: i64) -> Self {
+        TradingCalendar { session, close_minute, holidays: HashSet::new(), half_days: HashMap::new() }
+    }
+
+    pub fn with_holiday(mut self, date: Date) -> Self {
+        self.holidays.insert(date);
+        self
+    }
+
+    /// Marks `date` as a half-day closing at `close_minute`.
+    pub fn with_half_day(mut self, date: Date, close_minute: i64) -> Self {
+        self.half_days.insert(date, close_minute);
+        self
+    }
+
+    /// Adds the holidays and half-days listed in `text`, one per line: `YYYY-MM-DD` for a
+    /// holiday, `YYYY-MM-DD HH:MM` for a half-day closing at that local time. Blank lines and
+    /// anything after `#` are ignored.
+    pub fn with_holidays_from_str(mut self, text: &str) -> Result<Self, IndicatorError> {
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            let date: Date = fields.next().unwrap().parse()?;
+            self = match (fields.next(), fields.next()) {
+                (None, _) => self.with_holiday(date),
+                (Some(close), None) => self.with_half_day(date, parse_minute(close)?),
+                _ => return Err(IndicatorError::InvalidInput(format!("Invalid calendar line '{}'.", line))),
+            };
+        }
+        Ok(self)
+    }
+
+    /// Reads a holiday file in the format of [`TradingCalendar::with_holidays_from_str`].
+    pub fn with_holidays_from_file(self, path: impl AsRef<Path>) -> Result<Self, IndicatorError> {
+        let text = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| IndicatorError::InvalidInput(format!("Cannot read '{}': {}", path.as_ref().display(), e)))?;
+        self.with_holidays_from_str(&text)
+    }
+
+    /// Local date (days since epoch) of the session containing `timestamp`.
+    fn session_day(&self, timestamp: i64) -> i64 {
+        (self.session.session_start(timestamp) + self.session.utc_offset_minutes * MINUTE).div_euclid(DAY)
+    }
+
+    /// Whether the exchange trades on the local date `day`, counted from the epoch.
+    pub fn is_trading_day(&self, day: i64) -> bool {
+        // The epoch was a Thursday; 5 and 6 are Saturday and Sunday.
+        let weekday = (day + 3).rem_euclid(7);
+        weekday < 5 && !self.holidays.contains(&Date::from_days_since_epoch(day))
+    }
+
+    /// Whether the market is open at `timestamp`.
+    pub fn is_open(&self, timestamp: i64) -> bool {
+        let day = self.session_day(timestamp);
+        if !self.is_trading_day(day) {
+            return false;
+        }
+        let close_minute = self.half_days.get(&Date::from_days_since_epoch(day)).copied().unwrap_or(self.close_minute);
+        let mut minutes = (close_minute - self.session.start_minute).rem_euclid(24 * 60);
+        if minutes == 0 {
+            minutes = 24 * 60;
+        }
+        timestamp - self.session.session_start(timestamp) < minutes * MINUTE
+    }
+
+    /// Marks the bars that fall within trading hours.
+    pub fn open_mask(&self, timestamps: ArrayView1<i64>) -> Vec<bool> {
+        timestamps.iter().map(|&timestamp| self.is_open(timestamp)).collect()
+    }
+}
+
+/// Regular sessions only: bars before the open, after the (possibly early) close, on weekends
+/// and on holidays belong to no session.
+impl TradingSessions for TradingCalendar {
+    fn trading_session(&self, timestamp: i64) -> Option<i64> {
+        self.is_open(timestamp).then(|| self.session.session_start(timestamp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::timeframe::HOUR;
+    use super::*;
+
+    fn new_york() -> TradingCalendar {
+        TradingCalendar::new(Session::new(-300, 9 * 60 + 30), 16 * 60)
+    }
+
+    /// 09:30 New York time (UTC-5) on `date`.
+    fn open_at(date: Date) -> i64 {
+        date.days_since_epoch() * DAY + 14 * HOUR + 30 * MINUTE
+    }
+
+    #[test]
+    fn test_dates() {
+        assert_eq!(Date::new(1970, 1, 1).days_since_epoch(), 0);
+        assert_eq!(Date::new(2024, 1, 1).days_since_epoch() * DAY, 1_704_067_200_000);
+        assert_eq!(Date::new(2000, 3, 1).days_since_epoch(), 11_017);
+        assert_eq!("2024-02-29".parse::<Date>().unwrap(), Date::new(2024, 2, 29));
+        assert!("2024-13-01".parse::<Date>().is_err());
+        assert!("2024-02-31".parse::<Date>().is_err());
+        assert!("2023-02-29".parse::<Date>().is_err());
+        assert!("1900-02-29".parse::<Date>().is_err());
+        assert!("2000-02-29".parse::<Date>().is_ok());
+        for days in [-719_468, -1, 0, 11_016, 11_017, 19_782, 2_932_896] {
+            assert_eq!(Date::from_days_since_epoch(days).days_since_epoch(), days);
+        }
+        assert_eq!(Date::new(2024, 7, 4).to_string(), "2024-07-04");
+    }
+
+    #[test]
+    fn test_regular_hours_and_weekends() {
+        let calendar = new_york();
+        // 2024-01-05 is a Friday.
+        let friday = open_at(Date::new(2024, 1, 5));
+
+        assert!(calendar.is_open(friday));
+        assert!(calendar.is_open(friday + 6 * HOUR + 29 * MINUTE));
+        assert!(!calendar.is_open(friday + 6 * HOUR + 30 * MINUTE));
+        assert!(!calendar.is_open(friday - 1));
+        assert!(!calendar.is_open(friday + DAY));
+        assert!(calendar.is_open(friday + 3 * DAY));
+    }
+
+    #[test]
+    fn test_holidays_and_half_days_from_file() {
+        let path = std::env::temp_dir().join(format!("rustick-holidays-{}.txt", std::process::id()));
+        std::fs::write(&path, "# US equities\n2024-07-04\n\n2024-07-03 13:00  # early close\n").unwrap();
+
+        let calendar = new_york().with_holidays_from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let half_day = open_at(Date::new(2024, 7, 3));
+        assert!(calendar.is_open(half_day + 3 * HOUR));
+        assert!(!calendar.is_open(half_day + 3 * HOUR + 30 * MINUTE));
+        assert!(!calendar.is_open(open_at(Date::new(2024, 7, 4))));
+        assert!(calendar.is_open(open_at(Date::new(2024, 7, 5))));
+        assert!(new_york().with_holidays_from_str("2024-07-04 1pm").is_err());
+        assert!(new_york().with_holidays_from_file("/nonexistent/holidays.txt").is_err());
+    }
+
+    #[test]
+    fn test_calendar_parameter() {
+        let calendar = new_york().with_holiday(Date::new(2024, 7, 4)).with_half_day(Date::new(2024, 7, 3), 13 * 60);
+        let value = serde_json::to_value(&calendar).unwrap();
+
+        assert_eq!(value["holidays"], serde_json::json!(["2024-07-04"]));
+        assert_eq!(value["half_days"], serde_json::json!({ "2024-07-03": 780 }));
+        assert_eq!(serde_json::from_value::<TradingCalendar>(value).unwrap(), calendar);
+        assert!(serde_json::from_value::<TradingCalendar>(serde_json::json!({ "close_minute": 960, "holidays": ["2024-02-30"] })).is_err());
+    }
+}
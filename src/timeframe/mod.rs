@@ -0,0 +1,227 @@
+//! Higher-timeframe indicators on base-timeframe bars.
+//!
+//! Timestamped bars are aggregated into buckets of a [`Timeframe`], an indicator runs on the
+//! aggregated bars and its values are projected back onto the base bars. A higher-timeframe
+//! value is only projected onto base bars of *later* buckets: the bar it belongs to has closed
+//! by then, so no base bar sees a value computed from its own or future prices.
+
+use ndarray::{s, Array1, ArrayView1};
+use serde_json::Value;
+use crate::models::data::{GenericInputData, GenericInputDataView, GenericOutputData, GenericTimestampedData};
+use crate::models::float::Float;
+use crate::models::indicator::{Indicator, IndicatorError};
+
+pub mod calendar;
+pub mod session;
+
+const MINUTE: i64 = 60_000;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+
+/// Fixed-length buckets of `duration` milliseconds, starting at multiples of `duration` after
+/// the Unix epoch shifted by `offset` milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeframe {
+    pub duration: i64,
+    pub offset: i64,
+}
+
+impl Timeframe {
+    pub fn minutes(count: i64) -> Self {
+        Timeframe { duration: count * MINUTE, offset: 0 }
+    }
+
+    pub fn hours(count: i64) -> Self {
+        Timeframe { duration: count * HOUR, offset: 0 }
+    }
+
+    /// UTC days. Use [`Timeframe::with_offset`] for days starting at another hour.
+    pub fn days(count: i64) -> Self {
+        Timeframe { duration: count * DAY, offset: 0 }
+    }
+
+    /// Weeks starting on Monday 00:00 UTC.
+    pub fn weeks(count: i64) -> Self {
+        // The Unix epoch is a Thursday.
+        Timeframe { duration: count * 7 * DAY, offset: 4 * DAY }
+    }
+
+    pub fn with_offset(self, offset: i64) -> Self {
+        Timeframe { offset, ..self }
+    }
+
+    /// Start of the bucket containing `timestamp`.
+    pub fn bucket_start(&self, timestamp: i64) -> i64 {
+        (timestamp - self.offset).div_euclid(self.duration) * self.duration + self.offset
+    }
+}
+
+/// Bars aggregated into a higher timeframe.
+pub struct Resampled<T: Float = f64> {
+    /// One bar per non-empty bucket, timestamped with the bucket start.
+    pub bars: GenericTimestampedData<T>,
+    /// Index into `bars` of the bucket of every base bar.
+    pub bucket: Vec<usize>,
+}
+
+fn aggregate<T: Float>(series: Option<ArrayView1<T>>, ranges: &[(usize, usize)], reduce: impl Fn(ArrayView1<T>) -> T) -> Option<Array1<T>> {
+    series.map(|series| ranges.iter().map(|&(start, end)| reduce(series.slice_move(s![start..end]))).collect())
+}
+
+/// Aggregates timestamped bars into `timeframe` buckets: first open, highest high, lowest low,
+/// last close and summed volume.
+pub fn resample<T: Float>(data: &GenericInputDataView<T>, timeframe: Timeframe) -> Result<Resampled<T>, IndicatorError> {
+    if timeframe.duration <= 0 {
+        return Err(IndicatorError::InvalidParameters("Timeframe duration must be positive".to_string()));
+    }
+    let timestamp = data.timestamp
+        .ok_or_else(|| IndicatorError::InvalidInput("Resampling requires timestamps.".to_string()))?;
+    data.validate_same_length()?;
+    if timestamp.windows(2).into_iter().any(|pair| pair[1] < pair[0]) {
+        return Err(IndicatorError::InvalidInput("Timestamps must not decrease.".to_string()));
+    }
+
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    let mut starts = Vec::new();
+    let mut bucket = Vec::with_capacity(timestamp.len());
+    for (i, &time) in timestamp.iter().enumerate() {
+        let start = timeframe.bucket_start(time);
+        if starts.last() != Some(&start) {
+            starts.push(start);
+            ranges.push((i, i));
+        }
+        ranges.last_mut().unwrap().1 = i + 1;
+        bucket.push(ranges.len() - 1);
+    }
+
+    let bars = GenericInputData {
+        open: aggregate(data.open, &ranges, |bars| bars[0]),
+        high: aggregate(data.high, &ranges, |bars| bars.iter().copied().fold(T::neg_infinity(), T::max)),
+        low: aggregate(data.low, &ranges, |bars| bars.iter().copied().fold(T::infinity(), T::min)),
+        close: aggregate(data.close, &ranges, |bars| bars[bars.len() - 1]),
+        volume: aggregate(data.volume, &ranges, |bars| bars.sum()),
+    }.with_timestamps(Array1::from(starts));
+
+    Ok(Resampled { bars, bucket })
+}
+
+impl<T: Float> Resampled<T> {
+    fn project_series(&self, values: &Array1<T>) -> Array1<T> {
+        // Outputs shorter than the bars (e.g. ADOSC) are aligned to the last bars.
+        let bars = self.bucket.last().map_or(0, |&last| last + 1);
+        let skipped = bars.saturating_sub(values.len());
+        self.bucket.iter()
+            .map(|&bucket| match bucket.checked_sub(1) {
+                Some(previous) if previous >= skipped => values[previous - skipped],
+                _ => T::nan(),
+            })
+            .collect()
+    }
+
+    /// Projects an output calculated on `bars` onto the base bars. Every base bar gets the value
+    /// of the last bucket that closed before its own bucket began; base bars of the first bucket
+    /// are NaN.
+    pub fn project(&self, output: &GenericOutputData<T>) -> GenericOutputData<T> {
+        match output {
+            GenericOutputData::SingleSeries(values) => GenericOutputData::SingleSeries(self.project_series(values)),
+            GenericOutputData::MultiSeries(series) => GenericOutputData::MultiSeries(
+                series.iter().map(|(&name, values)| (name, self.project_series(values))).collect()
+            ),
+        }
+    }
+}
+
+/// Runs `indicator` on `data` resampled to `timeframe` and projects the result back onto `data`.
+pub fn calculate_on_timeframe<T: Float>(indicator: &dyn Indicator<T>, data: &GenericInputDataView<T>, params: Value, timeframe: Timeframe) -> Result<GenericOutputData<T>, IndicatorError> {
+    let resampled = resample(data, timeframe)?;
+    let output = indicator.calculate_view(&resampled.bars.view(), params)?;
+    Ok(resampled.project(&output))
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::indicators::atr::ATR;
+    use crate::models::data::{InputData, OutputData, TimestampedData};
+    use super::*;
+
+    fn hourly_bars() -> TimestampedData {
+        // Three days of six hourly bars, starting 2024-01-01 (a Monday) 00:00 UTC.
+        let start = 1_704_067_200_000;
+        let hours: Vec<i64> = (0..3).flat_map(|day| (0..6).map(move |hour| day * 24 + hour)).collect();
+        let close = Array1::from_shape_fn(18, |i| 100.0 + i as f64);
+        InputData {
+            open: Some(&close - 0.5),
+            high: Some(&close + 1.0),
+            low: Some(&close - 1.0 - (&close - 100.0) / 10.0),
+            close: Some(close),
+            volume: Some(Array1::from_elem(18, 10.0)),
+        }.with_timestamps(hours.iter().map(|hour| start + hour * HOUR).collect())
+    }
+
+    #[test]
+    fn test_resample_aggregates_buckets() {
+        let data = hourly_bars();
+
+        let resampled = resample(&data.view(), Timeframe::days(1)).unwrap();
+        let bars = &resampled.bars.bars;
+
+        assert_eq!(bars.open.as_ref().unwrap(), array![99.5, 105.5, 111.5]);
+        assert_eq!(bars.high.as_ref().unwrap(), array![106.0, 112.0, 118.0]);
+        assert_eq!(bars.low.as_ref().unwrap()[1], 106.0 - 1.0 - 0.6);
+        assert_eq!(bars.close.as_ref().unwrap(), array![105.0, 111.0, 117.0]);
+        assert_eq!(bars.volume.as_ref().unwrap(), array![60.0, 60.0, 60.0]);
+        assert_eq!(resampled.bars.timestamp.as_ref().unwrap()[1], 1_704_067_200_000 + DAY);
+        assert_eq!(resampled.bucket[5..8], [0, 1, 1]);
+    }
+
+    #[test]
+    fn test_weekly_buckets_start_on_monday() {
+        let monday = 1_704_067_200_000;
+        let week = Timeframe::weeks(1);
+
+        assert_eq!(week.bucket_start(monday + 6 * DAY), monday);
+        assert_eq!(week.bucket_start(monday - 1), monday - 7 * DAY);
+    }
+
+    #[test]
+    fn test_projection_has_no_lookahead() {
+        let data = hourly_bars();
+        let params = json!({ "period": 1 });
+
+        let projected = calculate_on_timeframe(&ATR::new(), &data.view(), params.clone(), Timeframe::days(1)).unwrap();
+        let daily = ATR::new().calculate_view(&resample(&data.view(), Timeframe::days(1)).unwrap().bars.view(), params).unwrap();
+
+        if let (OutputData::SingleSeries(projected), OutputData::SingleSeries(daily)) = (projected, daily) {
+            assert_eq!(projected.len(), 18);
+            // Day 0 has no closed daily bar yet; day 1 sees day 0 and day 2 sees day 1.
+            assert!(projected.iter().take(6).all(|value| value.is_nan()));
+            assert!(projected.iter().skip(6).take(6).all(|&value| value == daily[0]));
+            assert!(projected.iter().skip(12).all(|&value| value == daily[1]));
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+
+    #[test]
+    fn test_resample_requires_ordered_timestamps() {
+        let mut data = hourly_bars();
+        assert!(resample(&hourly_bars().bars.view(), Timeframe::days(1)).is_err());
+
+        data.timestamp.as_mut().unwrap()[3] = 0;
+        assert!(resample(&data.view(), Timeframe::days(1)).is_err());
+    }
+
+    #[test]
+    fn test_resample_requires_a_timestamp_per_bar() {
+        let data = hourly_bars();
+        let view = data.view();
+
+        // Fewer timestamps than bars, then more.
+        let timestamp = view.timestamp.map(|timestamp| timestamp.slice_move(s![..17]));
+        assert!(resample(&GenericInputDataView { timestamp, ..view }, Timeframe::days(1)).is_err());
+        let close = view.close.map(|close| close.slice_move(s![..17]));
+        assert!(resample(&GenericInputDataView { close, ..view }, Timeframe::days(1)).is_err());
+    }
+}
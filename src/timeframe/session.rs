@@ -0,0 +1,232 @@
+use ndarray::{s, Array1, ArrayView1};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::models::data::{GenericInputDataView, GenericOutputData};
+use crate::models::float::Float;
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::timeframe::{Timeframe, MINUTE};
+
+/// Daily trading sessions starting `start_minute` minutes after local midnight, where local time
+/// is `utc_offset_minutes` ahead of UTC (`-300` for New York in winter).
+///
+/// The default is a session starting at midnight UTC.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    #[serde(default)]
+    pub utc_offset_minutes: i64,
+    #[serde(default)]
+    pub start_minute: i64,
+}
+
+impl Session {
+    pub fn new(utc_offset_minutes: i64, start_minute: i64) -> Self {
+        Session { utc_offset_minutes, start_minute }
+    }
+
+    /// One-day buckets aligned to the session start.
+    pub fn timeframe(&self) -> Timeframe {
+        Timeframe::days(1).with_offset((self.start_minute - self.utc_offset_minutes) * MINUTE)
+    }
+
+    /// Start of the session containing `timestamp`, in Unix epoch milliseconds.
+    pub fn session_start(&self, timestamp: i64) -> i64 {
+        self.timeframe().bucket_start(timestamp)
+    }
+}
+
+/// Assigns bars to trading sessions. A [`Session`] covers the whole day; a
+/// [`TradingCalendar`](crate::timeframe::calendar::TradingCalendar) only its regular hours.
+pub trait TradingSessions {
+    /// Start of the session `timestamp` trades in, or `None` outside trading hours.
+    fn trading_session(&self, timestamp: i64) -> Option<i64>;
+
+    /// Marks the bars that do not continue the session of the bar before them: the first bar of
+    /// every session and every bar outside one.
+    fn first_bars(&self, timestamps: ArrayView1<i64>) -> Vec<bool> {
+        let mut previous = None;
+        timestamps.iter()
+            .map(|&timestamp| {
+                let session = self.trading_session(timestamp);
+                let first = session.is_none() || session != previous;
+                previous = session;
+                first
+            })
+            .collect()
+    }
+}
+
+impl TradingSessions for Session {
+    fn trading_session(&self, timestamp: i64) -> Option<i64> {
+        Some(self.session_start(timestamp))
+    }
+}
+
+/// Whether the gap between one session's last close and the next session's first bar counts
+/// towards the true range.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OvernightGap {
+    #[default]
+    Include,
+    Exclude,
+}
+
+/// Runs `indicator` on every session's bars separately, so nothing carries over from one session
+/// into the next, and joins the results. Outputs shorter than their session (e.g. ADOSC) are
+/// aligned to its last bar. Bars outside trading hours are NaN.
+///
+/// Sessions the indicator rejects, typically because they are shorter than its period, are
+/// NaN. If it rejects every session, the first error is returned.
+pub fn calculate_per_session<T: Float>(indicator: &dyn Indicator<T>, data: &GenericInputDataView<T>, params: Value, sessions: &dyn TradingSessions) -> Result<GenericOutputData<T>, IndicatorError> {
+    let timestamps = data.timestamp
+        .ok_or_else(|| IndicatorError::InvalidInput("Per-session calculation requires timestamps.".to_string()))?;
+    data.validate_same_length()?;
+    let length = timestamps.len();
+
+    // Runs of consecutive bars in the same session.
+    let mut ranges: Vec<(Option<i64>, usize, usize)> = Vec::new();
+    for (i, &timestamp) in timestamps.iter().enumerate() {
+        let session = sessions.trading_session(timestamp);
+        match ranges.last_mut() {
+            Some((previous, _, end)) if *previous == session => *end = i + 1,
+            _ => ranges.push((session, i, i + 1)),
+        }
+    }
+
+    let mut joined: Option<GenericOutputData<T>> = None;
+    let mut first_error = None;
+    for (start, end) in ranges.into_iter().filter_map(|(session, start, end)| session.map(|_| (start, end))) {
+        let output = match indicator.calculate_view(&data.slice(start..end), params.clone()) {
+            Ok(output) => output,
+            Err(error) => {
+                first_error.get_or_insert(error);
+                continue;
+            }
+        };
+        let joined = joined.get_or_insert_with(|| match &output {
+            GenericOutputData::SingleSeries(_) => GenericOutputData::SingleSeries(Array1::from_elem(length, T::nan())),
+            GenericOutputData::MultiSeries(series) => GenericOutputData::MultiSeries(
+                series.keys().map(|&name| (name, Array1::from_elem(length, T::nan()))).collect()
+            ),
+        });
+        let place = |target: &mut Array1<T>, values: &Array1<T>| {
+            target.slice_mut(s![end - values.len()..end]).assign(values);
+        };
+        match (joined, &output) {
+            (GenericOutputData::SingleSeries(target), GenericOutputData::SingleSeries(values)) => place(target, values),
+            (GenericOutputData::MultiSeries(targets), GenericOutputData::MultiSeries(series)) => {
+                for (name, values) in series {
+                    if let Some(target) = targets.get_mut(name) {
+                        place(target, values);
+                    }
+                }
+            }
+            _ => return Err(IndicatorError::CalculationError("Sessions returned different output formats.".to_string())),
+        }
+    }
+
+    match (joined, first_error) {
+        (Some(joined), _) => Ok(joined),
+        (None, Some(error)) => Err(error),
+        (None, None) => Err(IndicatorError::InvalidInput("No bars fall within a trading session.".to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::indicators::atr::ATR;
+    use crate::indicators::sum::SUM;
+    use crate::models::data::{InputData, OutputData, TimestampedData};
+    use crate::timeframe::calendar::{Date, TradingCalendar};
+    use crate::timeframe::HOUR;
+    use super::*;
+
+    fn two_sessions() -> TimestampedData {
+        // Three hourly bars on each of 2024-01-01 and 2024-01-02, with a gap up overnight.
+        let start = 1_704_067_200_000 + 10 * HOUR;
+        let close = array![10.0, 11.0, 12.0, 20.0, 21.0, 22.0];
+        InputData {
+            open: None,
+            high: Some(&close + 0.5),
+            low: Some(&close - 0.5),
+            close: Some(close),
+            volume: None,
+        }.with_timestamps(array![0, 1, 2, 24, 25, 26].mapv(|hour: i64| start + hour * HOUR))
+    }
+
+    #[test]
+    fn test_session_start_with_offset() {
+        // 2024-01-02 14:30 UTC is 09:30 in New York (UTC-5).
+        let open = 1_704_067_200_000 + 24 * HOUR + 14 * HOUR + 30 * MINUTE;
+        let new_york = Session::new(-300, 9 * 60 + 30);
+
+        assert_eq!(new_york.session_start(open), open);
+        assert_eq!(new_york.session_start(open + 6 * HOUR), open);
+        assert_eq!(new_york.session_start(open - 1), open - 24 * HOUR);
+        assert_eq!(Session::default().session_start(open), open - 14 * HOUR - 30 * MINUTE);
+    }
+
+    #[test]
+    fn test_first_bars() {
+        let data = two_sessions();
+        let first = Session::default().first_bars(data.timestamp.as_ref().unwrap().view());
+        assert_eq!(first, [true, false, false, true, false, false]);
+    }
+
+    #[test]
+    fn test_calculate_per_session_resets() {
+        let data = two_sessions();
+        let params = json!({ "period": 2 });
+
+        let per_session = calculate_per_session(&ATR::new(), &data.view(), params.clone(), &Session::default()).unwrap();
+        let continuous = ATR::new().calculate(&data.bars, params).unwrap();
+
+        if let (OutputData::SingleSeries(per_session), OutputData::SingleSeries(continuous)) = (per_session, continuous) {
+            // The second session starts over: no overnight gap and a new warm-up bar.
+            assert!(per_session[3].is_nan());
+            assert_eq!(per_session[4], 1.25);
+            assert!(continuous[3] > 4.0);
+        } else {
+            panic!("Unexpected output format");
+        }
+        assert!(calculate_per_session(&ATR::new(), &data.view(), json!({ "period": 4 }), &Session::default()).is_err());
+    }
+
+    #[test]
+    fn test_calculate_per_session_follows_regular_hours() {
+        // New York time (UTC-5): 16:30 on 2024-07-02, 08:30 to 13:30 hourly on 2024-07-03 and
+        // 09:30 and 10:30 on each of 2024-07-04 and 2024-07-05.
+        let day = Date::new(2024, 7, 3).days_since_epoch() * 24 * HOUR + 13 * HOUR + 30 * MINUTE;
+        let hours: Array1<i64> = array![-16, 0, 1, 2, 3, 4, 5, 25, 26, 49, 50];
+        let data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(Array1::from_shape_fn(11, |i| i as f64 + 1.0)),
+            volume: None,
+        }.with_timestamps(hours.mapv(|hour| day + hour * HOUR));
+        let sum = |sessions: &dyn TradingSessions| {
+            let output = calculate_per_session(&SUM::new(), &data.view(), json!({ "period": 2 }), sessions).unwrap();
+            format!("{:?}", output.series("real").unwrap().to_vec())
+        };
+        let expected = |values: [f64; 11]| format!("{:?}", values.to_vec());
+        let nan = f64::NAN;
+        let calendar = TradingCalendar::new(Session::new(-300, 9 * 60 + 30), 16 * 60);
+
+        // A plain session joins the after-hours and pre-market bars into one session.
+        assert_eq!(sum(&calendar.session), expected([nan, 3.0, nan, 7.0, 9.0, 11.0, 13.0, nan, 17.0, nan, 21.0]));
+        assert_eq!(sum(&calendar), expected([nan, nan, nan, 7.0, 9.0, 11.0, 13.0, nan, 17.0, nan, 21.0]));
+        // Bars on a holiday, and after an early close at 13:00, are outside every session.
+        let holidays = calendar.with_holiday(Date::new(2024, 7, 4)).with_half_day(Date::new(2024, 7, 3), 13 * 60);
+        assert_eq!(sum(&holidays), expected([nan, nan, nan, 7.0, 9.0, 11.0, nan, nan, nan, nan, 21.0]));
+    }
+
+    #[test]
+    fn test_calculate_per_session_requires_a_timestamp_per_bar() {
+        let data = TimestampedData { timestamp: Some(array![0, HOUR]), ..two_sessions() };
+
+        assert!(calculate_per_session(&ATR::new(), &data.view(), json!({ "period": 1 }), &Session::default()).is_err());
+    }
+}
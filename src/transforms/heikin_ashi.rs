@@ -0,0 +1,88 @@
+use crate::bars::Bar;
+use crate::models::data::{BarField, InputDataView, TimestampedData};
+use crate::models::indicator::IndicatorError;
+use crate::transforms::{timestamp_at, to_input_data, validate_fields, volume_at};
+
+/// Heikin-Ashi candles, one per source bar.
+///
+/// The close is the bar's average price, the open is the midpoint of the previous
+/// Heikin-Ashi candle's body (of the source bar's open and close for the first bar), and the
+/// high and low also cover the Heikin-Ashi open and close.
+pub fn heikin_ashi(data: &InputDataView) -> Result<TimestampedData, IndicatorError> {
+    validate_fields(data, vec![BarField::OPEN, BarField::HIGH, BarField::LOW, BarField::CLOSE])?;
+    let open = data.open.unwrap();
+    let high = data.high.unwrap();
+    let low = data.low.unwrap();
+    let close = data.close.unwrap();
+
+    let mut bars: Vec<Bar> = Vec::with_capacity(open.len());
+    for i in 0..open.len() {
+        let ha_close = (open[i] + high[i] + low[i] + close[i]) / 4.0;
+        let ha_open = match bars.last() {
+            Some(previous) => (previous.open + previous.close) / 2.0,
+            None => (open[i] + close[i]) / 2.0,
+        };
+        bars.push(Bar {
+            timestamp: timestamp_at(data, i),
+            open: ha_open,
+            high: high[i].max(ha_open).max(ha_close),
+            low: low[i].min(ha_open).min(ha_close),
+            close: ha_close,
+            volume: volume_at(data, i),
+        });
+    }
+
+    Ok(to_input_data(&bars, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+    use serde_json::json;
+    use crate::indicators::adx::ADX;
+    use crate::models::data::{InputData, OutputData};
+    use crate::models::indicator::Indicator;
+    use super::*;
+
+    #[test]
+    fn test_heikin_ashi() {
+        let data = InputData {
+            open: Some(array![10.0, 11.0, 12.0]),
+            high: Some(array![12.0, 13.0, 12.5]),
+            low: Some(array![9.0, 10.5, 10.0]),
+            close: Some(array![11.0, 12.0, 10.5]),
+            volume: None,
+        };
+
+        let candles = heikin_ashi(&data.view()).unwrap().bars;
+
+        assert_eq!(candles.close.as_ref().unwrap(), array![10.5, 11.625, 11.25]);
+        assert_eq!(candles.open.as_ref().unwrap(), array![10.5, 10.5, 11.0625]);
+        assert_eq!(candles.high.as_ref().unwrap(), array![12.0, 13.0, 12.5]);
+        assert_eq!(candles.low.as_ref().unwrap(), array![9.0, 10.5, 10.0]);
+        assert!(candles.volume.is_none());
+        assert!(heikin_ashi(&data.view()).unwrap().timestamp.is_none());
+    }
+
+    #[test]
+    fn test_adx_runs_on_heikin_ashi() {
+        let close = ndarray::Array1::from_shape_fn(40, |i| 100.0 + (i as f64 * 0.3).sin() * 5.0 + i as f64 * 0.2);
+        let data = InputData {
+            open: Some(&close - 0.3),
+            high: Some(&close + 1.0),
+            low: Some(&close - 1.0),
+            close: Some(close),
+            volume: None,
+        };
+
+        let candles = heikin_ashi(&data.view()).unwrap();
+        let result = ADX::new().calculate_view(&candles.view(), json!({ "period": 5 })).unwrap();
+
+        if let OutputData::SingleSeries(adx_values) = result {
+            assert_eq!(adx_values.len(), 40);
+            assert!(!adx_values[39].is_nan());
+        } else {
+            panic!("Unexpected output format");
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use crate::bars::Bar;
+use crate::models::data::{BarField, InputDataView, TimestampedData};
+use crate::models::indicator::IndicatorError;
+use crate::transforms::{line_bar, timestamp_at, to_input_data, validate_fields, volume_at, Reversal};
+
+/// Kagi lines built from closes, one bar per vertical line from its start to its extreme.
+///
+/// A line extends while the close makes new extremes in its direction and reverses once the
+/// close retraces `reversal` from the extreme; the new line starts at that extreme. The last,
+/// still open line is included.
+pub fn kagi(data: &InputDataView, reversal: Reversal) -> Result<TimestampedData, IndicatorError> {
+    validate_fields(data, vec![BarField::CLOSE])?;
+    if !reversal.is_valid() {
+        return Err(IndicatorError::InvalidParameters("Reversal amount must be a positive number".to_string()));
+    }
+    let close = data.close.unwrap();
+
+    let mut lines: Vec<Bar> = Vec::new();
+    let (mut start, mut extreme) = (close[0], close[0]);
+    let mut rising: Option<bool> = None;
+    let mut start_index = 0;
+    let mut volume = 0.0;
+    for i in 0..close.len() {
+        volume += volume_at(data, i);
+        let price = close[i];
+        match rising {
+            None => {
+                if (price - start).abs() >= reversal.amount(start) {
+                    rising = Some(price > start);
+                    extreme = price;
+                }
+            }
+            Some(up) => {
+                if (up && price > extreme) || (!up && price < extreme) {
+                    extreme = price;
+                } else if (extreme - price).abs() >= reversal.amount(extreme) {
+                    lines.push(line_bar(timestamp_at(data, start_index), start, extreme, volume - volume_at(data, i)));
+                    volume = volume_at(data, i);
+                    start = extreme;
+                    start_index = i;
+                    extreme = price;
+                    rising = Some(!up);
+                }
+            }
+        }
+    }
+    if rising.is_some() {
+        lines.push(line_bar(timestamp_at(data, start_index), start, extreme, volume));
+    }
+
+    Ok(to_input_data(&lines, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+    use crate::models::data::InputData;
+    use super::*;
+
+    #[test]
+    fn test_kagi_lines() {
+        let data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(array![10.0, 10.5, 12.0, 11.5, 13.0, 10.5, 10.0, 11.2]),
+            volume: None,
+        };
+
+        let lines = kagi(&data.view(), Reversal::Fixed(1.0)).unwrap().bars;
+
+        // Up from 10 to 13, down to 10, then the open line up to 11.2.
+        assert_eq!(lines.open.unwrap(), array![10.0, 13.0, 10.0]);
+        assert_eq!(lines.close.unwrap(), array![13.0, 10.0, 11.2]);
+        assert!(kagi(&data.view(), Reversal::Percent(0.0)).is_err());
+    }
+}
@@ -0,0 +1,129 @@
+//! Chart-type transforms that turn bars into new bars.
+//!
+//! Every transform returns `TimestampedData`, so any indicator can run on the result's view, e.g.
+//! ADX on Heikin-Ashi candles. Transforms that drop the time axis (Renko, Kagi, point and figure and
+//! range bars) emit one bar per brick, line or column: it carries the timestamp of the source
+//! bar it started on and the volume of the source bars it spans.
+
+use crate::bars::{bars_to_input_data, Bar};
+use crate::models::data::{BarField, InputDataView, TimestampedData};
+use crate::models::indicator::IndicatorError;
+use crate::validation::validator::Validator;
+
+pub mod heikin_ashi;
+pub mod kagi;
+pub mod point_and_figure;
+pub mod range_bars;
+pub mod renko;
+
+/// Price distance that makes a Kagi line reverse.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Reversal {
+    Fixed(f64),
+    /// Fraction of the line's extreme price.
+    Percent(f64),
+}
+
+impl Reversal {
+    fn amount(&self, price: f64) -> f64 {
+        match self {
+            Reversal::Fixed(amount) => *amount,
+            Reversal::Percent(fraction) => price.abs() * fraction,
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        match self {
+            Reversal::Fixed(amount) => *amount > 0.0,
+            Reversal::Percent(fraction) => *fraction > 0.0,
+        }
+    }
+}
+
+/// Checks that `fields` are present, that there is at least one bar and that volume and
+/// timestamps, if any, have one value per bar.
+fn validate_fields(data: &InputDataView, fields: Vec<BarField>) -> Result<(), IndicatorError> {
+    Validator::new(fields, vec![]).validate_data(data)?;
+    data.validate_same_length()?;
+    if data.close.is_some_and(|close| close.is_empty()) {
+        return Err(IndicatorError::InvalidInput("Empty input.".to_string()));
+    }
+    Ok(())
+}
+
+/// Source bar volume and timestamp, defaulting to zero when the fields are missing.
+fn volume_at(data: &InputDataView, i: usize) -> f64 {
+    data.volume.map_or(0.0, |volume| volume[i])
+}
+
+fn timestamp_at(data: &InputDataView, i: usize) -> i64 {
+    data.timestamp.map_or(0, |timestamp| timestamp[i])
+}
+
+/// Converts transformed bars, keeping timestamps and volume only if the source had them.
+fn to_input_data(bars: &[Bar], source: &InputDataView) -> TimestampedData {
+    let mut data = bars_to_input_data(bars);
+    if source.timestamp.is_none() {
+        data.timestamp = None;
+    }
+    if source.volume.is_none() {
+        data.bars.volume = None;
+    }
+    data
+}
+
+/// A bar spanning prices from `open` to `close`.
+fn line_bar(timestamp: i64, open: f64, close: f64, volume: f64) -> Bar {
+    Bar { timestamp, open, high: open.max(close), low: open.min(close), close, volume }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{array, Array1};
+    use crate::models::data::InputData;
+    use super::*;
+    use super::kagi::kagi;
+    use super::point_and_figure::point_and_figure;
+    use super::range_bars::range_bars;
+    use super::renko::{renko, BoxSize};
+
+    fn transform_all(data: &InputDataView) -> Vec<Result<TimestampedData, IndicatorError>> {
+        vec![
+            renko(data, BoxSize::Fixed(1.0)),
+            kagi(data, Reversal::Fixed(1.0)),
+            point_and_figure(data, 1.0, 3),
+            range_bars(data, 1.0),
+        ]
+    }
+
+    #[test]
+    fn test_transforms_reject_empty_input() {
+        let empty = InputData {
+            open: Some(Array1::zeros(0)),
+            high: Some(Array1::zeros(0)),
+            low: Some(Array1::zeros(0)),
+            close: Some(Array1::zeros(0)),
+            volume: None,
+        };
+
+        assert!(transform_all(&empty.view()).iter().all(|result| matches!(result, Err(IndicatorError::InvalidInput(_)))));
+    }
+
+    #[test]
+    fn test_transforms_reject_short_volume_and_timestamps() {
+        let close = array![10.0, 12.0, 9.0];
+        let bars = || InputData {
+            open: Some(close.clone()),
+            high: Some(&close + 1.0),
+            low: Some(&close - 1.0),
+            close: Some(close.clone()),
+            volume: None,
+        };
+
+        assert!(transform_all(&bars().view()).iter().all(|result| result.is_ok()));
+        let short_volume = InputData { volume: Some(array![1.0, 2.0]), ..bars() };
+        assert!(transform_all(&short_volume.view()).iter().all(|result| result.is_err()));
+        let short_timestamp = array![0, 60_000];
+        assert!(transform_all(&bars().view().with_timestamps(short_timestamp.view())).iter().all(|result| result.is_err()));
+    }
+}
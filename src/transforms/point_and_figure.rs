@@ -0,0 +1,88 @@
+use crate::bars::Bar;
+use crate::models::data::{BarField, InputDataView, TimestampedData};
+use crate::models::indicator::IndicatorError;
+use crate::transforms::{line_bar, timestamp_at, to_input_data, validate_fields, volume_at};
+
+/// Point-and-figure columns built from closes on a grid of `box_size`, one bar per column.
+///
+/// X columns run from `open` (bottom box) up to `close` (top box), O columns the other way.
+/// A column extends by whole boxes and reverses once the close is `reversal_boxes` boxes past
+/// its last box; the new column starts one box beyond it. The last column is included.
+pub fn point_and_figure(data: &InputDataView, box_size: f64, reversal_boxes: usize) -> Result<TimestampedData, IndicatorError> {
+    validate_fields(data, vec![BarField::CLOSE])?;
+    if box_size <= 0.0 || reversal_boxes == 0 {
+        return Err(IndicatorError::InvalidParameters("Box size and reversal must be positive".to_string()));
+    }
+    let close = data.close.unwrap();
+    let floor = |price: f64| (price / box_size).floor() * box_size;
+    let ceil = |price: f64| (price / box_size).ceil() * box_size;
+    let reversal = reversal_boxes as f64 * box_size;
+
+    let mut columns: Vec<Bar> = Vec::new();
+    let (mut top, mut bottom) = (floor(close[0]), floor(close[0]));
+    let mut rising: Option<bool> = None;
+    let mut start_index = 0;
+    let mut volume = 0.0;
+    for i in 0..close.len() {
+        volume += volume_at(data, i);
+        let price = close[i];
+        let (open, column_close) = if rising == Some(true) { (bottom, top) } else { (top, bottom) };
+        match rising {
+            None if price >= top + box_size => {
+                top = floor(price);
+                rising = Some(true);
+            }
+            None if price <= bottom - box_size => {
+                bottom = ceil(price);
+                rising = Some(false);
+            }
+            Some(true) if price >= top + box_size => top = floor(price),
+            Some(false) if price <= bottom - box_size => bottom = ceil(price),
+            Some(up) if (up && price <= top - reversal) || (!up && price >= bottom + reversal) => {
+                columns.push(line_bar(timestamp_at(data, start_index), open, column_close, volume - volume_at(data, i)));
+                volume = volume_at(data, i);
+                start_index = i;
+                if up {
+                    top -= box_size;
+                    bottom = ceil(price);
+                } else {
+                    bottom += box_size;
+                    top = floor(price);
+                }
+                rising = Some(!up);
+            }
+            _ => {}
+        }
+    }
+    if let Some(up) = rising {
+        let (open, column_close) = if up { (bottom, top) } else { (top, bottom) };
+        columns.push(line_bar(timestamp_at(data, start_index), open, column_close, volume));
+    }
+
+    Ok(to_input_data(&columns, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+    use crate::models::data::InputData;
+    use super::*;
+
+    #[test]
+    fn test_point_and_figure_columns() {
+        let data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(array![10.0, 11.2, 13.5, 12.0, 10.9, 9.5, 11.0, 13.2]),
+            volume: None,
+        };
+
+        let columns = point_and_figure(&data.view(), 1.0, 3).unwrap().bars;
+
+        // X from 10 to 13, O from 12 down to 10 after the 3-box reversal at 9.5, then X from 11 to 13.
+        assert_eq!(columns.open.unwrap(), array![10.0, 12.0, 11.0]);
+        assert_eq!(columns.close.unwrap(), array![13.0, 10.0, 13.0]);
+        assert!(point_and_figure(&data.view(), 1.0, 0).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use crate::bars::Bar;
+use crate::models::data::{BarField, InputDataView, TimestampedData};
+use crate::models::indicator::IndicatorError;
+use crate::transforms::{timestamp_at, to_input_data, validate_fields, volume_at};
+
+/// Bars whose high-low range is exactly `range`, except the last, still open one.
+///
+/// Each source bar is walked as open, low, high, close (open, high, low, close for down
+/// bars). A range bar closes where the walk first spans `range` and the next one opens at that
+/// price. Source volume goes to the range bar that is open at the source bar's close. Prices
+/// that are not finite are skipped, and a range too small to change the price it is added to is
+/// rejected.
+pub fn range_bars(data: &InputDataView, range: f64) -> Result<TimestampedData, IndicatorError> {
+    validate_fields(data, vec![BarField::OPEN, BarField::HIGH, BarField::LOW, BarField::CLOSE])?;
+    if range <= 0.0 {
+        return Err(IndicatorError::InvalidParameters("Range must be a positive number".to_string()));
+    }
+    let open = data.open.unwrap();
+    let high = data.high.unwrap();
+    let low = data.low.unwrap();
+    let close = data.close.unwrap();
+
+    let new_bar = |timestamp: i64, price: f64| Bar { timestamp, open: price, high: price, low: price, close: price, volume: 0.0 };
+    let mut bars: Vec<Bar> = Vec::new();
+    let mut current = new_bar(timestamp_at(data, 0), open[0]);
+    for i in 0..open.len() {
+        let path = if close[i] >= open[i] {
+            [open[i], low[i], high[i], close[i]]
+        } else {
+            [open[i], high[i], low[i], close[i]]
+        };
+        for price in path.into_iter().filter(|price| price.is_finite()) {
+            loop {
+                if current.low + range == current.low || current.high - range == current.high {
+                    return Err(IndicatorError::InvalidParameters(format!("Range {} is too small for price {}", range, current.close)));
+                }
+                if price > current.low + range {
+                    current.close = current.low + range;
+                    current.high = current.close;
+                } else if price < current.high - range {
+                    current.close = current.high - range;
+                    current.low = current.close;
+                } else {
+                    current.high = current.high.max(price);
+                    current.low = current.low.min(price);
+                    current.close = price;
+                    break;
+                }
+                bars.push(current);
+                current = new_bar(timestamp_at(data, i), current.close);
+            }
+        }
+        current.volume += volume_at(data, i);
+    }
+    bars.push(current);
+
+    Ok(to_input_data(&bars, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+    use crate::models::data::InputData;
+    use super::*;
+
+    #[test]
+    fn test_range_bars() {
+        let data = InputData {
+            open: Some(array![10.0, 11.5]),
+            high: Some(array![11.0, 13.4]),
+            low: Some(array![9.5, 11.2]),
+            close: Some(array![10.8, 13.0]),
+            volume: Some(array![100.0, 200.0]),
+        }.with_timestamps(array![0, 60_000]);
+
+        let TimestampedData { bars, timestamp } = range_bars(&data.view(), 1.0).unwrap();
+
+        // 10 -> 9.5 -> 11 -> 10.8 -> 11.5 -> 11.2 -> 13.4 -> 13.0
+        assert_eq!(bars.open.as_ref().unwrap(), array![10.0, 10.5, 11.5, 12.5]);
+        assert_eq!(bars.close.as_ref().unwrap(), array![10.5, 11.5, 12.5, 13.0]);
+        let ranges = bars.high.as_ref().unwrap() - bars.low.as_ref().unwrap();
+        assert!(ranges.iter().take(3).all(|&range| (range - 1.0).abs() < 1e-12));
+        assert_eq!(timestamp.unwrap(), array![0, 0, 60_000, 60_000]);
+        assert_eq!(bars.volume.unwrap(), array![0.0, 100.0, 0.0, 200.0]);
+    }
+
+    #[test]
+    fn test_range_bars_reject_range_below_price_resolution() {
+        // At 1e17 adding 1.0 rounds back to the same price; the infinite high is skipped.
+        let data = InputData {
+            open: Some(array![1e17, 1e17]),
+            high: Some(array![1e17 + 64.0, f64::INFINITY]),
+            low: Some(array![1e17, 1e17]),
+            close: Some(array![1e17 + 64.0, 1e17]),
+            volume: None,
+        };
+
+        assert!(matches!(range_bars(&data.view(), 1.0), Err(IndicatorError::InvalidParameters(_))));
+        assert_eq!(range_bars(&data.view(), 32.0).unwrap().bars.close.unwrap(), array![1e17 + 32.0, 1e17 + 32.0, 1e17]);
+    }
+}
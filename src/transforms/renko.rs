@@ -0,0 +1,129 @@
+use serde_json::json;
+use crate::bars::Bar;
+use crate::indicators::atr::ATR;
+use crate::models::data::{BarField, InputDataView, TimestampedData};
+use crate::models::indicator::{Indicator, IndicatorError};
+use crate::transforms::{line_bar, timestamp_at, to_input_data, validate_fields, volume_at};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoxSize {
+    Fixed(f64),
+    /// The last ATR value over the source bars.
+    Atr { period: usize },
+}
+
+impl BoxSize {
+    fn resolve(&self, data: &InputDataView) -> Result<f64, IndicatorError> {
+        let size = match self {
+            BoxSize::Fixed(size) => *size,
+            BoxSize::Atr { period } => {
+                let atr = ATR::new().calculate_view(data, json!({ "period": period }))?;
+                let atr = atr.series("real")?;
+                atr[atr.len() - 1]
+            }
+        };
+        if size > 0.0 && size.is_finite() {
+            Ok(size)
+        } else {
+            Err(IndicatorError::InvalidParameters("Box size must be a positive number".to_string()))
+        }
+    }
+}
+
+/// Renko bricks of `box_size` built from closes.
+///
+/// A brick is added each time the close moves a full box beyond the last brick; reversing
+/// takes two boxes, measured from the far end of the last brick. Closes that are not finite add
+/// no bricks, and a box too small to change the price it is added to is rejected.
+pub fn renko(data: &InputDataView, box_size: BoxSize) -> Result<TimestampedData, IndicatorError> {
+    validate_fields(data, vec![BarField::CLOSE])?;
+    let size = box_size.resolve(data)?;
+    let close = data.close.unwrap();
+
+    let mut bricks: Vec<Bar> = Vec::new();
+    let (mut top, mut bottom) = (close[0], close[0]);
+    let mut volume = 0.0;
+    for i in 0..close.len() {
+        volume += volume_at(data, i);
+        if !close[i].is_finite() {
+            continue;
+        }
+        loop {
+            let (open, brick_close) = if close[i] >= top + size {
+                (top, top + size)
+            } else if close[i] <= bottom - size {
+                (bottom, bottom - size)
+            } else {
+                break;
+            };
+            if brick_close == open {
+                return Err(IndicatorError::InvalidParameters(format!("Box size {} is too small for price {}", size, open)));
+            }
+            bricks.push(line_bar(timestamp_at(data, i), open, brick_close, volume));
+            volume = 0.0;
+            top = open.max(brick_close);
+            bottom = open.min(brick_close);
+        }
+    }
+
+    Ok(to_input_data(&bricks, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+    use crate::models::data::InputData;
+    use super::*;
+
+    #[test]
+    fn test_renko_bricks_and_reversal() {
+        let data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(array![10.0, 12.1, 11.0, 9.5, 7.9, 8.5]),
+            volume: Some(array![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]),
+        };
+
+        let bricks = renko(&data.view(), BoxSize::Fixed(1.0)).unwrap().bars;
+
+        // Two up bricks, then a reversal once the close is two boxes below the top.
+        assert_eq!(bricks.open.unwrap(), array![10.0, 11.0, 11.0, 10.0, 9.0]);
+        assert_eq!(bricks.close.unwrap(), array![11.0, 12.0, 10.0, 9.0, 8.0]);
+        assert_eq!(bricks.volume.unwrap(), array![3.0, 0.0, 7.0, 5.0, 0.0]);
+    }
+
+    #[test]
+    fn test_renko_atr_box_size() {
+        let close = ndarray::Array1::from_shape_fn(30, |i| 100.0 + i as f64);
+        let data = InputData {
+            open: None,
+            high: Some(&close + 1.0),
+            low: Some(&close - 1.0),
+            close: Some(close),
+            volume: None,
+        };
+
+        // Every true range is 2, so bricks are 2 wide.
+        let bricks = renko(&data.view(), BoxSize::Atr { period: 5 }).unwrap().bars;
+
+        assert_eq!(bricks.close.unwrap().len(), 14);
+        assert!(renko(&data.view(), BoxSize::Fixed(0.0)).is_err());
+    }
+
+    #[test]
+    fn test_renko_rejects_box_size_below_price_resolution() {
+        // At 1e17 adding 1.0 rounds back to the same price, so no brick could ever be completed.
+        let data = InputData {
+            open: None,
+            high: None,
+            low: None,
+            close: Some(array![1e17, 1e17 + 64.0, f64::INFINITY]),
+            volume: None,
+        };
+
+        assert!(matches!(renko(&data.view(), BoxSize::Fixed(1.0)), Err(IndicatorError::InvalidParameters(_))));
+        assert!(renko(&data.view(), BoxSize::Fixed(f64::INFINITY)).is_err());
+        assert_eq!(renko(&data.view(), BoxSize::Fixed(32.0)).unwrap().bars.close.unwrap(), array![1e17 + 32.0, 1e17 + 64.0]);
+    }
+}
@@ -1,7 +1,8 @@
+use std::ops::Bound;
 use ndarray::Array1;
 use serde::Serialize;
 use serde_json::Value;
-use crate::models::data::{BarField, InputData};
+use crate::models::data::{BarField, Candles};
 use crate::models::indicator::IndicatorError;
 
 pub struct Validator {
@@ -24,13 +25,13 @@ pub trait IParameter {
 }
 
 impl CandleValidator {
-    fn is_field_missing(data: &InputData, field: &BarField) -> bool {
-        data.get_by_bar_field(field).is_none()
+    fn is_field_missing(data: &dyn Candles, field: &BarField) -> bool {
+        data.field_len(field).is_none()
     }
 
-    fn validate_required_fields_presence(&self, data: &InputData) -> Result<(), IndicatorError> {
+    fn validate_required_fields_presence(&self, data: &dyn Candles) -> Result<(), IndicatorError> {
         for field in &self.required_fields {
-            if CandleValidator::is_field_missing(data, &field) {
+            if CandleValidator::is_field_missing(data, field) {
                 let field_missing_error = format!("Field '{}' is required but missing.", &field.to_str());
                 return Err(IndicatorError::InvalidInput(field_missing_error));
             }
@@ -39,9 +40,9 @@ impl CandleValidator {
         Ok(())
     }
 
-    fn validate_same_length(&self, data: &InputData) -> Result<(), IndicatorError> {
+    fn validate_same_length(&self, data: &dyn Candles) -> Result<(), IndicatorError> {
         let lengths: Array1<usize> = self.required_fields.iter().filter_map(
-            |field| data.get_by_bar_field(&field).map(|arr| arr.len())
+            |field| data.field_len(field)
         ).collect();
 
         if lengths.is_empty() {
@@ -55,7 +56,7 @@ impl CandleValidator {
         Ok(())
     }
 
-    pub fn validate_candle(&self, data: &InputData) -> Result<(), IndicatorError> {
+    pub fn validate_candle(&self, data: &dyn Candles) -> Result<(), IndicatorError> {
         self.validate_required_fields_presence(data)?;
         self.validate_same_length(data)?;
         Ok(())
@@ -113,13 +114,56 @@ impl ParameterValidator {
         }
     }
 
-    fn validate_params(&self, params: &Value, data: &InputData) -> Result<(), IndicatorError> {
+    fn validate_number_in_range(&self, params: &Value, param_name: &str, min: Bound<f64>, max: Bound<f64>) -> Result<(), IndicatorError> {
+        let above_min = |value: f64| match min {
+            Bound::Included(min) => value >= min,
+            Bound::Excluded(min) => value > min,
+            Bound::Unbounded => true,
+        };
+        let below_max = |value: f64| match max {
+            Bound::Included(max) => value <= max,
+            Bound::Excluded(max) => value < max,
+            Bound::Unbounded => true,
+        };
+        match params.get(param_name).and_then(|v| v.as_f64()) {
+            Some(value) if above_min(value) && below_max(value) => Ok(()),
+            _ => {
+                let lower = match min {
+                    Bound::Included(min) => format!("[{}", min),
+                    Bound::Excluded(min) => format!("({}", min),
+                    Bound::Unbounded => "(-inf".to_string(),
+                };
+                let upper = match max {
+                    Bound::Included(max) => format!("{}]", max),
+                    Bound::Excluded(max) => format!("{})", max),
+                    Bound::Unbounded => "inf)".to_string(),
+                };
+                Err(IndicatorError::InvalidParameters(format!("Parameter '{}' must be a number in {}, {}", param_name, lower, upper)))
+            }
+        }
+    }
+
+    fn validate_not_greater_than(&self, params: &Value, left: &str, right: &str) -> Result<(), IndicatorError> {
+        let left_number = params.get(left).and_then(|v| v.as_f64())
+            .ok_or_else(|| IndicatorError::InvalidParameters(format!("Parameter '{}' must be a number", left)))?;
+        let right_number = params.get(right).and_then(|v| v.as_f64())
+            .ok_or_else(|| IndicatorError::InvalidParameters(format!("Parameter '{}' must be a number", right)))?;
+        if left_number <= right_number {
+            Ok(())
+        } else {
+            Err(IndicatorError::InvalidParameters(format!("Parameter '{}' must not be greater than '{}'", left, right)))
+        }
+    }
+
+    fn validate_params(&self, params: &Value, data: &dyn Candles) -> Result<(), IndicatorError> {
         for rule in &self.param_rules {
             match rule {
                 ParamRule::Required(param_name) => self.validate_required_param(params, param_name)?,
                 ParamRule::PositiveInteger(param_name) => self.validate_positive_integer_param(params, param_name)?,
                 ParamRule::PositiveNumber(param_name) => self.validate_positive_number_param(params, param_name)?,
                 ParamRule::CorrectPeriod { left, right } => self.validate_correct_period(params, left, right)?,
+                ParamRule::NumberInRange { name, min, max } => self.validate_number_in_range(params, name, *min, *max)?,
+                ParamRule::NotGreaterThan { left, right } => self.validate_not_greater_than(params, left, right)?,
                 ParamRule::Custom(func) => {
                     func(params, data)?;
                 }
@@ -137,27 +181,33 @@ impl Validator {
         }
     }
 
-    pub fn validate_data(&self, data: &InputData) -> Result<(), IndicatorError> {
+    pub fn validate_data(&self, data: &dyn Candles) -> Result<(), IndicatorError> {
         self.candle_validator.validate_candle(data)?;
         Ok(())
     }
 
-    pub fn validate_params<T: IParameter + Serialize>(&self, data: &InputData, params: &T) -> Result<(), IndicatorError> {
+    pub fn validate_params<T: IParameter + Serialize>(&self, data: &dyn Candles, params: &T) -> Result<(), IndicatorError> {
         self.parameter_validator.validate_params(&params.to_value(), data)?;
         Ok(())
     }
 
-    pub fn validate<T: IParameter + Serialize>(&self, data: &InputData, params: &T) -> Result<(), IndicatorError> {
+    pub fn validate<T: IParameter + Serialize>(&self, data: &dyn Candles, params: &T) -> Result<(), IndicatorError> {
         self.candle_validator.validate_candle(data)?;
         self.parameter_validator.validate_params(&params.to_value(), data)?;
         Ok(())
     }
 }
 
+pub type CustomRule = Box<dyn Fn(&Value, &dyn Candles) -> Result<(), IndicatorError> + Send + Sync>;
+
 pub enum ParamRule {
     Required(&'static str),
     PositiveInteger(&'static str),
     PositiveNumber(&'static str),
     CorrectPeriod { left: &'static str, right: &'static str },
-    Custom(Box<dyn Fn(&Value, &InputData) -> Result<(), IndicatorError>>),
+    /// A number within `min` and `max`, e.g. `(0, 1]` with `Excluded(0.0)` and `Included(1.0)`.
+    NumberInRange { name: &'static str, min: Bound<f64>, max: Bound<f64> },
+    /// Number `left` must be at most number `right`.
+    NotGreaterThan { left: &'static str, right: &'static str },
+    Custom(CustomRule),
 }